@@ -1175,6 +1175,12 @@ impl Context {
         CollapsingHeader::new("🔠 Font texture")
             .default_open(false)
             .show(ui, |ui| {
+                let stats = self.fonts().font_atlas_stats();
+                ui.label(format!(
+                    "Glyph cache: {} hits, {} misses. {} glyphs evicted in {} repacks.",
+                    stats.hits, stats.misses, stats.evictions, stats.repacks
+                ));
+
                 let font_image_size = self.fonts().font_image_size();
                 crate::introspection::font_texture_ui(ui, font_image_size);
             });
@@ -2,8 +2,8 @@ use std::borrow::Cow;
 use std::sync::Arc;
 
 use crate::{
-    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, Galley, Pos2,
-    Style, TextStyle, Ui, Visuals,
+    style::WidgetVisuals, text::LayoutJob, Align, Color32, FontFamily, FontSelection, FontWeight,
+    Galley, Pos2, Style, TextStyle, Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -26,6 +26,9 @@ pub struct RichText {
     text: String,
     size: Option<f32>,
     family: Option<FontFamily>,
+    weight: Option<FontWeight>,
+    extra_letter_spacing: f32,
+    line_height: Option<f32>,
     text_style: Option<TextStyle>,
     background_color: Color32,
     text_color: Option<Color32>,
@@ -115,9 +118,38 @@ impl RichText {
     /// This overrides the value from [`Self::text_style`].
     #[inline]
     pub fn font(mut self, font_id: crate::FontId) -> Self {
-        let crate::FontId { size, family } = font_id;
+        let crate::FontId {
+            size,
+            family,
+            weight,
+        } = font_id;
         self.size = Some(size);
         self.family = Some(family);
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Select how bold the text is.
+    ///
+    /// This overrides the value from [`Self::text_style`].
+    /// [`Self::strong`] text is at least [`FontWeight::BOLD`].
+    #[inline]
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Extra horizontal space between characters, in points.
+    #[inline]
+    pub fn extra_letter_spacing(mut self, extra_letter_spacing: f32) -> Self {
+        self.extra_letter_spacing = extra_letter_spacing;
+        self
+    }
+
+    /// Row height as a multiple of the font height. Default: `1.0`.
+    #[inline]
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
         self
     }
 
@@ -154,7 +186,7 @@ impl RichText {
         self.text_style(TextStyle::Monospace)
     }
 
-    /// Extra strong text (stronger color).
+    /// Extra strong text (bold, with a stronger color).
     #[inline]
     pub fn strong(mut self) -> Self {
         self.strong = true;
@@ -254,12 +286,15 @@ impl RichText {
             text,
             size,
             family,
+            weight,
+            extra_letter_spacing,
+            line_height,
             text_style,
             background_color,
             text_color: _, // already used by `get_text_color`
             code,
-            strong: _, // already used by `get_text_color`
-            weak: _,   // already used by `get_text_color`
+            strong,
+            weak: _, // already used by `get_text_color`
             strikethrough,
             underline,
             italics,
@@ -283,6 +318,12 @@ impl RichText {
             if let Some(family) = family {
                 font_id.family = family;
             }
+            if let Some(weight) = weight {
+                font_id.weight = weight;
+            }
+            if strong {
+                font_id.weight = font_id.weight.max(FontWeight::BOLD);
+            }
             font_id
         };
 
@@ -315,6 +356,8 @@ impl RichText {
             underline,
             strikethrough,
             valign,
+            extra_letter_spacing,
+            line_height: line_height.unwrap_or(1.0),
         };
 
         let job = LayoutJob::single_section(text, text_format);
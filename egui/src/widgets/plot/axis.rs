@@ -0,0 +1,186 @@
+//! Axis titles and the gutters next to the plot frame that hold the tick labels.
+
+use crate::*;
+
+/// Padding between the plot frame, the tick labels and the axis title.
+const GUTTER_PADDING: f32 = 4.0;
+
+/// Which of the two y axes of a [`Plot`](super::Plot) an item is plotted against.
+///
+/// See [`PlotUi::set_y_axis`](super::PlotUi::set_y_axis).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YAxis {
+    /// The y axis on the left side of the plot.
+    Primary,
+
+    /// The y axis on the right side of the plot, with its own bounds.
+    Secondary,
+}
+
+impl Default for YAxis {
+    fn default() -> Self {
+        Self::Primary
+    }
+}
+
+/// Per-axis configuration of the title and the gutter it is shown in.
+///
+/// An axis gets a gutter as soon as it has a label or a unit.
+/// The gutter is reserved outside of the plot frame (below it for the x axis, to the left for
+/// the y axis and to the right for the secondary y axis) and the tick labels of that axis are moved into it, instead of being painted on
+/// top of the data.
+#[derive(Clone)]
+pub(super) struct AxisHints {
+    pub label: Option<String>,
+    pub unit: Option<String>,
+
+    /// Width of the tick labels of a y axis, in number of digits.
+    pub digits: usize,
+}
+
+impl Default for AxisHints {
+    fn default() -> Self {
+        Self {
+            label: None,
+            unit: None,
+            digits: 5,
+        }
+    }
+}
+
+impl AxisHints {
+    /// Whether tick labels and title are shown in a gutter outside of the plot frame.
+    pub fn has_gutter(&self) -> bool {
+        self.label.is_some() || self.unit.is_some()
+    }
+
+    /// The title text, e.g. "Time [s]".
+    pub fn title(&self) -> String {
+        match (&self.label, &self.unit) {
+            (Some(label), Some(unit)) if !label.is_empty() => format!("{} [{}]", label, unit),
+            (_, Some(unit)) => format!("[{}]", unit),
+            (Some(label), None) => label.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Thickness of the gutter, i.e. its height for the x axis and its width for the y axis.
+    pub fn thickness(&self, ui: &Ui, axis: usize) -> f32 {
+        if !self.has_gutter() {
+            return 0.0;
+        }
+
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let row_height = ui.fonts().row_height(&font_id);
+
+        let ticks = if axis == 0 {
+            row_height
+        } else {
+            self.digits as f32 * ui.fonts().glyph_width(&font_id, '0')
+        };
+
+        let title = if self.title().is_empty() {
+            0.0
+        } else {
+            row_height + GUTTER_PADDING
+        };
+
+        GUTTER_PADDING + ticks + title + GUTTER_PADDING
+    }
+}
+
+/// Split the allocated `rect` into the plot frame and the gutters of the x, y and secondary y axes.
+///
+/// Returns `(frame, [x_gutter, y_gutter, y2_gutter])`. A gutter is an empty rectangle if its
+/// axis has none.
+pub(super) fn layout(ui: &Ui, rect: Rect, hints: &[AxisHints; 3]) -> (Rect, [Rect; 3]) {
+    let x_thickness = hints[0].thickness(ui, 0).at_most(rect.height() / 2.0);
+    let y_thickness = hints[1].thickness(ui, 1).at_most(rect.width() / 3.0);
+    let y2_thickness = hints[2].thickness(ui, 2).at_most(rect.width() / 3.0);
+
+    let frame = Rect::from_min_max(
+        pos2(rect.left() + y_thickness, rect.top()),
+        pos2(rect.right() - y2_thickness, rect.bottom() - x_thickness),
+    );
+    let x_gutter = Rect::from_min_max(
+        pos2(frame.left(), frame.bottom()),
+        pos2(frame.right(), rect.bottom()),
+    );
+    let y_gutter = Rect::from_min_max(
+        pos2(rect.left(), frame.top()),
+        pos2(frame.left(), frame.bottom()),
+    );
+    let y2_gutter = Rect::from_min_max(
+        pos2(frame.right(), frame.top()),
+        pos2(rect.right(), frame.bottom()),
+    );
+    (frame, [x_gutter, y_gutter, y2_gutter])
+}
+
+/// Position of a tick label of the given `axis` inside its gutter, for a tick at `pos_in_gui`.
+pub(super) fn tick_label_pos(gutter: Rect, axis: usize, pos_in_gui: Pos2, size: Vec2) -> Pos2 {
+    match axis {
+        0 => pos2(pos_in_gui.x - size.x / 2.0, gutter.top() + GUTTER_PADDING),
+        1 => pos2(
+            gutter.right() - GUTTER_PADDING - size.x,
+            pos_in_gui.y - size.y / 2.0,
+        ),
+        _ => pos2(gutter.left() + GUTTER_PADDING, pos_in_gui.y - size.y / 2.0),
+    }
+}
+
+/// Paint the title of the given `axis` into its gutter. The y axis title is rotated to read
+/// from bottom to top, the secondary y axis title from top to bottom.
+pub(super) fn paint_title(
+    ui: &Ui,
+    hints: &AxisHints,
+    axis: usize,
+    gutter: Rect,
+    shapes: &mut Vec<Shape>,
+) {
+    let title = hints.title();
+    if title.is_empty() {
+        return;
+    }
+
+    let font_id = TextStyle::Body.resolve(ui.style());
+    let galley = ui
+        .painter()
+        .layout_no_wrap(title, font_id, ui.visuals().text_color());
+    let size = galley.size();
+
+    let text_shape = match axis {
+        0 => {
+            let pos = pos2(
+                gutter.center().x - size.x / 2.0,
+                gutter.bottom() - GUTTER_PADDING - size.y,
+            );
+            epaint::TextShape::new(pos, galley)
+        }
+        1 => {
+            // Rotating counter-clockwise around the top left corner makes the text extend upwards
+            // from `pos`, with its height along the x axis.
+            let pos = pos2(
+                gutter.left() + GUTTER_PADDING,
+                gutter.center().y + size.x / 2.0,
+            );
+            epaint::TextShape {
+                angle: -std::f32::consts::TAU / 4.0,
+                ..epaint::TextShape::new(pos, galley)
+            }
+        }
+        _ => {
+            // Rotating clockwise makes the text extend downwards from `pos`, with its height
+            // extending to the left.
+            let pos = pos2(
+                gutter.right() - GUTTER_PADDING,
+                gutter.center().y - size.x / 2.0,
+            );
+            epaint::TextShape {
+                angle: std::f32::consts::TAU / 4.0,
+                ..epaint::TextShape::new(pos, galley)
+            }
+        }
+    };
+    shapes.push(text_shape.into());
+}
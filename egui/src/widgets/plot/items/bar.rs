@@ -183,7 +183,7 @@ impl RectElement for Bar {
     }
 
     fn default_values_format(&self, transform: &ScreenTransform) -> String {
-        let scale = transform.dvalue_dpos();
+        let scale = transform.dvalue_dpos_at(&self.point_at(self.argument, self.value));
         let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
         format!("\n{:.*}", y_decimals, self.value)
     }
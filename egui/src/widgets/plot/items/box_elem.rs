@@ -267,7 +267,7 @@ impl RectElement for BoxElem {
     }
 
     fn default_values_format(&self, transform: &ScreenTransform) -> String {
-        let scale = transform.dvalue_dpos();
+        let scale = transform.dvalue_dpos_at(&self.point_at(self.argument, self.spread.median));
         let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
         format!(
             "\nMax = {max:.decimals$}\
@@ -0,0 +1,357 @@
+use crate::emath::{NumExt, Rect};
+use crate::epaint::{Color32, RectShape, Rounding, Shape, Stroke};
+
+use super::{add_rulers_and_text, highlighted_color, Orientation, PlotConfig, RectElement};
+use crate::plot::{CandleChart, ScreenTransform, Value};
+
+/// The open, high, low and close price of a single candle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ohlc {
+    /// Value at the start of the period.
+    pub open: f64,
+
+    /// Highest value during the period, the top of the upper wick.
+    pub high: f64,
+
+    /// Lowest value during the period, the bottom of the lower wick.
+    pub low: f64,
+
+    /// Value at the end of the period.
+    pub close: f64,
+}
+
+impl Ohlc {
+    pub fn new(open: f64, high: f64, low: f64, close: f64) -> Self {
+        Self {
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    /// Whether the value went up (or stayed the same) during the period.
+    pub fn is_rising(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+/// A candle in a [`CandleChart`]. The body spans from the open to the close value, the wick from
+/// the low to the high value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CandleElem {
+    /// Name of plot element in the diagram (annotated by default formatter).
+    pub name: String,
+
+    /// Which direction the candle faces in the diagram.
+    pub orientation: Orientation,
+
+    /// Position on the argument (input) axis -- X if vertical, Y if horizontal.
+    pub argument: f64,
+
+    /// Values of the candle
+    pub ohlc: Ohlc,
+
+    /// Traded volume during the period, shown as a bar if the [`CandleChart`] has a volume area.
+    pub volume: Option<f64>,
+
+    /// Thickness of the candle body
+    pub candle_width: f64,
+
+    /// Line width and color. A transparent color means the up or down color of the
+    /// [`CandleChart`] is used.
+    pub stroke: Stroke,
+
+    /// Fill color of the body. A transparent color means the up or down color of the
+    /// [`CandleChart`] is used.
+    pub fill: Color32,
+}
+
+impl CandleElem {
+    /// Create a candle element. Its `orientation` is set by its [`CandleChart`] parent.
+    ///
+    /// Check [`CandleElem`] fields for detailed description.
+    pub fn new(argument: f64, ohlc: Ohlc) -> Self {
+        Self {
+            argument,
+            orientation: Orientation::default(),
+            name: String::default(),
+            ohlc,
+            volume: None,
+            candle_width: 0.6,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            fill: Color32::TRANSPARENT,
+        }
+    }
+
+    /// Name of this candle element.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Traded volume during the period of this candle.
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Add a custom stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Add a custom fill color.
+    pub fn fill(mut self, color: impl Into<Color32>) -> Self {
+        self.fill = color.into();
+        self
+    }
+
+    /// Set the width of the candle body.
+    pub fn candle_width(mut self, width: f64) -> Self {
+        self.candle_width = width;
+        self
+    }
+
+    /// Set orientation of the element as vertical. Argument axis is X.
+    pub fn vertical(mut self) -> Self {
+        self.orientation = Orientation::Vertical;
+        self
+    }
+
+    /// Set orientation of the element as horizontal. Argument axis is Y.
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Stroke and fill, with transparent colors replaced by the up or down color of `parent`.
+    fn colors(&self, parent: &CandleChart) -> (Stroke, Color32) {
+        let color = if self.ohlc.is_rising() {
+            parent.up_color
+        } else {
+            parent.down_color
+        };
+        let mut stroke = self.stroke;
+        if stroke.color == Color32::TRANSPARENT {
+            stroke.color = color;
+        }
+        let fill = if self.fill == Color32::TRANSPARENT {
+            color
+        } else {
+            self.fill
+        };
+        (stroke, fill)
+    }
+
+    pub(super) fn add_shapes(
+        &self,
+        parent: &CandleChart,
+        transform: &ScreenTransform,
+        highlighted: bool,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let (stroke, fill) = self.colors(parent);
+        let (stroke, fill) = if highlighted {
+            highlighted_color(stroke, fill)
+        } else {
+            (stroke, fill)
+        };
+
+        if let Some(volume_rect) = self.volume_rect(parent, transform) {
+            shapes.push(Shape::rect_filled(
+                volume_rect,
+                Rounding::none(),
+                fill.linear_multiply(0.4),
+            ));
+        }
+
+        let wick = Shape::line_segment(
+            [
+                transform.position_from_value(&self.point_at(self.argument, self.ohlc.low)),
+                transform.position_from_value(&self.point_at(self.argument, self.ohlc.high)),
+            ],
+            stroke,
+        );
+        shapes.push(wick);
+
+        let body = transform.rect_from_values(
+            &self.point_at(self.argument - self.candle_width / 2.0, self.ohlc.open),
+            &self.point_at(self.argument + self.candle_width / 2.0, self.ohlc.close),
+        );
+        shapes.push(Shape::Rect(RectShape {
+            rect: body,
+            rounding: Rounding::none(),
+            fill,
+            stroke,
+        }));
+    }
+
+    /// The screen rectangle of the volume bar, if both the candle and its parent have a volume.
+    fn volume_rect(&self, parent: &CandleChart, transform: &ScreenTransform) -> Option<Rect> {
+        let (base, height) = parent.volume_area?;
+        let volume = self.volume?;
+        let max_volume = parent.max_volume;
+        let top = if max_volume > 0.0 {
+            base + height * volume / max_volume
+        } else {
+            base
+        };
+        Some(transform.rect_from_values(
+            &self.point_at(self.argument - self.candle_width / 2.0, base),
+            &self.point_at(self.argument + self.candle_width / 2.0, top),
+        ))
+    }
+
+    pub(super) fn add_rulers_and_text(
+        &self,
+        parent: &CandleChart,
+        plot: &PlotConfig<'_>,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let text: Option<String> = parent
+            .element_formatter
+            .as_ref()
+            .map(|fmt| fmt(self, parent));
+
+        add_rulers_and_text(self, plot, text, shapes);
+    }
+}
+
+impl RectElement for CandleElem {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn bounds_min(&self) -> Value {
+        let argument = self.argument - self.candle_width / 2.0;
+        self.point_at(argument, self.ohlc.low)
+    }
+
+    fn bounds_max(&self) -> Value {
+        let argument = self.argument + self.candle_width / 2.0;
+        self.point_at(argument, self.ohlc.high)
+    }
+
+    fn values_with_ruler(&self) -> Vec<Value> {
+        let open = self.point_at(self.argument, self.ohlc.open);
+        let high = self.point_at(self.argument, self.ohlc.high);
+        let low = self.point_at(self.argument, self.ohlc.low);
+        let close = self.point_at(self.argument, self.ohlc.close);
+
+        vec![open, high, low, close]
+    }
+
+    fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    fn corner_value(&self) -> Value {
+        self.point_at(self.argument, self.ohlc.high)
+    }
+
+    fn default_values_format(&self, transform: &ScreenTransform) -> String {
+        let scale = transform.dvalue_dpos_at(&self.point_at(self.argument, self.ohlc.close));
+        let scale = match self.orientation {
+            Orientation::Horizontal => scale[0],
+            Orientation::Vertical => scale[1],
+        };
+        let decimals = ((-scale.abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+        let mut text = format!(
+            "\nOpen = {open:.decimals$}\
+             \nHigh = {high:.decimals$}\
+             \nLow = {low:.decimals$}\
+             \nClose = {close:.decimals$}",
+            open = self.ohlc.open,
+            high = self.ohlc.high,
+            low = self.ohlc.low,
+            close = self.ohlc.close,
+            decimals = decimals
+        );
+        // The volume is on its own scale, so it doesn't follow the decimals of the prices:
+        if let Some(volume) = self.volume {
+            text += &format!("\nVolume = {}", crate::emath::round_to_decimals(volume, 6));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plot::{AxisScale, PlotBounds};
+    use epaint::{pos2, vec2};
+
+    #[test]
+    fn candle_colors_and_volume() {
+        let rising = CandleElem::new(1.0, Ohlc::new(1.0, 4.0, 0.5, 3.0)).volume(10.0);
+        let falling = CandleElem::new(2.0, Ohlc::new(3.0, 3.5, 1.0, 2.0))
+            .volume(5.0)
+            .fill(Color32::BLUE);
+        let chart = CandleChart::new(vec![rising.clone(), falling.clone()])
+            .up_color(Color32::GREEN)
+            .down_color(Color32::RED)
+            .volume_area(0.0, 2.0);
+        assert_eq!(chart.max_volume, 10.0);
+
+        assert_eq!(
+            rising.colors(&chart),
+            (Stroke::new(1.0, Color32::GREEN), Color32::GREEN)
+        );
+        let (stroke, fill) = falling.colors(&chart);
+        assert_eq!((stroke.color, fill), (Color32::RED, Color32::BLUE));
+
+        let frame = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+        let bounds = PlotBounds {
+            min: [0.0, 0.0],
+            max: [10.0, 10.0],
+        };
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2]);
+
+        // The largest volume fills the area, the others are scaled to it.
+        let width = rising.candle_width;
+        let rect = |x: f64, top: f64| {
+            transform.rect_from_values(
+                &Value::new(x - width / 2.0, 0.0),
+                &Value::new(x + width / 2.0, top),
+            )
+        };
+        assert_eq!(rising.volume_rect(&chart, &transform), Some(rect(1.0, 2.0)));
+        assert_eq!(
+            falling.volume_rect(&chart, &transform),
+            Some(rect(2.0, 1.0))
+        );
+
+        let no_volume = CandleElem::new(3.0, Ohlc::new(1.0, 2.0, 0.0, 1.0));
+        assert_eq!(no_volume.volume_rect(&chart, &transform), None);
+    }
+
+    #[test]
+    fn candle_values_format() {
+        let frame = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+        let bounds = PlotBounds {
+            min: [0.0, 0.0],
+            max: [10.0, 10.0],
+        };
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2]);
+        let candle = CandleElem::new(1.0, Ohlc::new(1.0, 4.0, 0.5, 3.0)).volume(1234.5678);
+        assert_eq!(
+            candle.default_values_format(&transform),
+            "\nOpen = 1.0\nHigh = 4.0\nLow = 0.5\nClose = 3.0\nVolume = 1234.5678"
+        );
+
+        // Zooming in on the prices adds decimals to them, but not to the volume:
+        let zoomed_bounds = PlotBounds {
+            min: [0.0, 0.0],
+            max: [10.0, 0.01],
+        };
+        let zoomed =
+            ScreenTransform::new(frame, zoomed_bounds, false, false, [AxisScale::Linear; 2]);
+        assert_eq!(
+            candle.default_values_format(&zoomed),
+            "\nOpen = 1.0000\nHigh = 4.0000\nLow = 0.5000\nClose = 3.0000\nVolume = 1234.5678"
+        );
+    }
+}
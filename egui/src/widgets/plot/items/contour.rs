@@ -0,0 +1,636 @@
+use std::ops::{Range, RangeInclusive};
+
+use epaint::ahash::AHashMap;
+use epaint::Mesh;
+
+use crate::*;
+
+use super::heatmap::decimals_for;
+use super::{
+    rulers_at_value, ClosestElem, Colormap, LabelFormatter, LineStyle, PlotConfig, PlotGeometry,
+    PlotItem,
+};
+use crate::plot::{AxisScale, PlotBounds, ScreenTransform, Value};
+
+/// How many levels a [`Contour`] gets if none are given.
+const DEFAULT_LEVEL_COUNT: usize = 10;
+
+/// Iso-lines of a grid of values, i.e. the lines along which the values equal given levels.
+///
+/// The lines are computed with marching squares, interpolating linearly between neighboring
+/// values. Optionally, the bands between the levels are filled with the colors of a
+/// [`Colormap`], and each line is labeled with its level.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Colormap, Contour, Plot};
+/// let cols = 20;
+/// let values = (0..cols * cols)
+///     .map(|i| {
+///         let (x, y) = ((i % cols) as f64 / 4.0 - 2.5, (i / cols) as f64 / 4.0 - 2.5);
+///         (-(x * x + y * y)).exp()
+///     })
+///     .collect();
+/// let contour = Contour::new(values, cols)
+///     .levels(vec![0.2, 0.4, 0.6, 0.8])
+///     .fill_bands(Colormap::Viridis)
+///     .labels(true);
+/// Plot::new("contour").show(ui, |plot_ui| plot_ui.contour(contour));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Contour {
+    pub(crate) values: Vec<f64>,
+    pub(crate) cols: usize,
+    origin: Value,
+    spacing: [f64; 2],
+    levels: Option<Vec<f64>>,
+    level_count: usize,
+    pub(crate) stroke: Stroke,
+    style: LineStyle,
+    fill: Option<Colormap>,
+    labels: bool,
+    name: String,
+    highlight: bool,
+
+    /// The points of all lines, computed by [`Self::build`].
+    points: Vec<Value>,
+    /// The range of each line in `points`, and its level.
+    lines: Vec<(Range<usize>, f64)>,
+    /// The filled parts of the bands, as convex polygons.
+    bands: Vec<(Vec<Value>, Color32)>,
+}
+
+impl Contour {
+    /// Create iso-lines of `values` in row-major order, with `cols` values per row.
+    ///
+    /// The first row is at the bottom of the plot. The first value lies at the origin and
+    /// neighboring values are `1.0` apart. To draw the lines on top of a [`super::Heatmap`]
+    /// of the same values, move the origin to the center of its first cell, e.g. `(0.5, 0.5)`.
+    /// `NaN` values leave the squares around them empty.
+    pub fn new(values: Vec<f64>, cols: usize) -> Self {
+        Self {
+            values,
+            cols,
+            origin: Value::new(0.0, 0.0),
+            spacing: [1.0, 1.0],
+            levels: None,
+            level_count: DEFAULT_LEVEL_COUNT,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            style: LineStyle::Solid,
+            fill: None,
+            labels: false,
+            name: Default::default(),
+            highlight: false,
+            points: Vec::new(),
+            lines: Vec::new(),
+            bands: Vec::new(),
+        }
+    }
+
+    /// Position of the first value, in plot coordinates.
+    pub fn origin(mut self, origin: Value) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Distance between neighboring values along the x and y axis, in plot coordinates.
+    pub fn spacing(mut self, x: f64, y: f64) -> Self {
+        self.spacing = [x, y];
+        self
+    }
+
+    /// The values to draw lines at.
+    ///
+    /// By default there are ten levels, evenly spaced between the smallest and largest value.
+    pub fn levels(mut self, levels: Vec<f64>) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    /// The number of levels, evenly spaced between the smallest and largest value.
+    /// Ignored if the levels are set with [`Self::levels`].
+    pub fn level_count(mut self, count: usize) -> Self {
+        self.level_count = count;
+        self
+    }
+
+    /// Stroke of the lines.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Stroke width. A high value means the plot thickens.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Set the lines' style. Default is `LineStyle::Solid`.
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Fill the bands between the levels, and below the lowest and above the highest level,
+    /// with evenly spaced colors of the colormap.
+    pub fn fill_bands(mut self, colormap: Colormap) -> Self {
+        self.fill = Some(colormap);
+        self
+    }
+
+    /// Label each line with its level, if it is long enough on the screen. Default is `false`.
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Highlight the lines in the plot.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Name of the lines.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub(crate) fn rows(&self) -> usize {
+        if self.cols == 0 {
+            0
+        } else {
+            self.values.len() / self.cols
+        }
+    }
+
+    /// Compute the lines and the filled bands.
+    pub(crate) fn build(mut self) -> Self {
+        let grid = Grid {
+            values: &self.values,
+            cols: self.cols,
+            rows: self.rows(),
+            origin: self.origin,
+            spacing: self.spacing,
+        };
+        let levels = match self.levels.take() {
+            Some(mut levels) => {
+                levels.retain(|level| level.is_finite());
+                levels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                levels.dedup();
+                levels
+            }
+            None => grid.even_levels(self.level_count),
+        };
+
+        for &level in &levels {
+            for line in grid.iso_lines(level) {
+                let start = self.points.len();
+                self.points.extend(line);
+                self.lines.push((start..self.points.len(), level));
+            }
+        }
+        if let Some(colormap) = &self.fill {
+            self.bands = grid.bands(&levels, colormap);
+        }
+        self.levels = Some(levels);
+        self
+    }
+
+    /// The level of the line the point at `index` belongs to.
+    fn level_of(&self, index: usize) -> f64 {
+        let line = self.lines.partition_point(|(range, _)| range.end <= index);
+        self.lines[line].1
+    }
+
+    /// Enough decimals to tell the levels apart.
+    fn decimals(&self) -> usize {
+        let levels = self.levels.as_deref().unwrap_or_default();
+        match (levels.first(), levels.last()) {
+            (Some(first), Some(last)) if first < last => decimals_for(last - first),
+            (Some(level), _) => decimals_for(level.abs()),
+            _ => 3,
+        }
+    }
+
+    fn add_label(
+        &self,
+        ui: &Ui,
+        line: &[Pos2],
+        level: f64,
+        decimals: usize,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let galley = ui.fonts().layout_no_wrap(
+            format!("{:.*}", decimals, level),
+            TextStyle::Small.resolve(ui.style()),
+            self.stroke.color,
+        );
+
+        // Only label lines that are a lot longer than the label, in their middle.
+        let length: f32 = line.windows(2).map(|w| w[0].distance(w[1])).sum();
+        if length < 3.0 * galley.size().x {
+            return;
+        }
+        let mut remaining = length / 2.0;
+        let mut center = line[0];
+        for w in line.windows(2) {
+            let distance = w[0].distance(w[1]);
+            if distance >= remaining {
+                center = w[0] + (w[1] - w[0]) * (remaining / distance);
+                break;
+            }
+            remaining -= distance;
+        }
+
+        let rect = Rect::from_center_size(center, galley.size());
+        shapes.push(Shape::rect_filled(
+            rect.expand(1.0),
+            2.0,
+            ui.visuals().extreme_bg_color,
+        ));
+        shapes.push(Shape::galley(rect.min, galley));
+    }
+}
+
+impl PlotItem for Contour {
+    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        if !self.bands.is_empty() {
+            let mut mesh = Mesh::default();
+            for (polygon, color) in &self.bands {
+                let first = mesh.vertices.len() as u32;
+                for value in polygon {
+                    mesh.colored_vertex(transform.position_from_value(value), *color);
+                }
+                for i in 1..polygon.len() as u32 - 1 {
+                    mesh.add_triangle(first, first + i, first + i + 1);
+                }
+            }
+            shapes.push(Shape::mesh(mesh));
+        }
+
+        let decimals = self.decimals();
+        let mut labels = Vec::new();
+        for (range, level) in &self.lines {
+            let line: Vec<Pos2> = self.points[range.clone()]
+                .iter()
+                .map(|value| transform.position_from_value(value))
+                .collect();
+            if self.labels {
+                self.add_label(ui, &line, *level, decimals, &mut labels);
+            }
+            self.style
+                .style_line(line, self.stroke, self.highlight, shapes);
+        }
+        shapes.extend(labels);
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(&self.points)
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        let rows = self.rows();
+        if rows > 0 {
+            bounds.extend_with(&self.origin);
+            bounds.extend_with(&Value::new(
+                self.origin.x + (self.cols - 1) as f64 * self.spacing[0],
+                self.origin.y + (rows - 1) as f64 * self.spacing[1],
+            ));
+        }
+        bounds
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let value = self.points[elem.index];
+        let pointer = plot.transform.position_from_value(&value);
+        shapes.push(Shape::circle_filled(pointer, 3.0, self.stroke.color));
+
+        let level = self.level_of(elem.index);
+        let decimals = self.decimals();
+        let name = if self.name.is_empty() {
+            format!("level = {:.*}", decimals, level)
+        } else {
+            format!("{}: {:.*}", self.name, decimals, level)
+        };
+        rulers_at_value(pointer, value, &name, plot, shapes, label_formatter);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The values of a [`Contour`] and where they lie.
+struct Grid<'a> {
+    values: &'a [f64],
+    cols: usize,
+    rows: usize,
+    origin: Value,
+    spacing: [f64; 2],
+}
+
+impl<'a> Grid<'a> {
+    fn value(&self, col: usize, row: usize) -> f64 {
+        self.values[row * self.cols + col]
+    }
+
+    fn position(&self, col: f64, row: f64) -> Value {
+        Value::new(
+            self.origin.x + col * self.spacing[0],
+            self.origin.y + row * self.spacing[1],
+        )
+    }
+
+    /// `count` levels evenly spaced between the smallest and largest finite value,
+    /// excluding both.
+    fn even_levels(&self, count: usize) -> Vec<f64> {
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        if min >= max {
+            return Vec::new();
+        }
+        let step = (max - min) / (count + 1) as f64;
+        (1..=count).map(|i| min + i as f64 * step).collect()
+    }
+
+    /// The corners of the square with its lower left corner at `(col, row)`, counterclockwise
+    /// from there, or `None` if any of their values is `NaN`.
+    fn square(&self, col: usize, row: usize) -> Option<[(usize, usize); 4]> {
+        let corners = [
+            (col, row),
+            (col + 1, row),
+            (col + 1, row + 1),
+            (col, row + 1),
+        ];
+        corners
+            .iter()
+            .all(|&(col, row)| !self.value(col, row).is_nan())
+            .then(|| corners)
+    }
+
+    /// The lines along which the values equal `level`, each as the points where it crosses
+    /// the edges between neighboring values. Closed lines end with their first point.
+    fn iso_lines(&self, level: f64) -> Vec<Vec<Value>> {
+        // Each crossed edge is identified by its lower left corner and direction.
+        let edge_id = |(col, row): (usize, usize), vertical: bool| {
+            2 * (row * self.cols + col) + vertical as usize
+        };
+        let mut segments: Vec<[usize; 2]> = Vec::new();
+        for row in 0..self.rows.saturating_sub(1) {
+            for col in 0..self.cols.saturating_sub(1) {
+                let corners = match self.square(col, row) {
+                    Some(corners) => corners,
+                    None => continue,
+                };
+                let above = corners.map(|(col, row)| self.value(col, row) >= level);
+                let edges = [
+                    edge_id(corners[0], false),
+                    edge_id(corners[1], true),
+                    edge_id(corners[3], false),
+                    edge_id(corners[0], true),
+                ];
+                let crossed: Vec<usize> = (0..4)
+                    .filter(|&i| above[i] != above[(i + 1) % 4])
+                    .map(|i| edges[i])
+                    .collect();
+                match crossed[..] {
+                    [a, b] => segments.push([a, b]),
+                    [e0, e1, e2, e3] => {
+                        // A saddle: the center decides whether the corners above the level are
+                        // connected, in which case the line cuts off the ones below.
+                        let center = corners
+                            .iter()
+                            .map(|&(col, row)| self.value(col, row))
+                            .sum::<f64>()
+                            / 4.0;
+                        if (center >= level) == above[0] {
+                            segments.push([e0, e1]);
+                            segments.push([e2, e3]);
+                        } else {
+                            segments.push([e3, e0]);
+                            segments.push([e1, e2]);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let crossing = |edge: usize| {
+            let (index, vertical) = (edge / 2, edge % 2 == 1);
+            let (col, row) = (index % self.cols, index / self.cols);
+            let (next_col, next_row) = if vertical {
+                (col, row + 1)
+            } else {
+                (col + 1, row)
+            };
+            let (a, b) = (self.value(col, row), self.value(next_col, next_row));
+            let t = (level - a) / (b - a);
+            if vertical {
+                self.position(col as f64, row as f64 + t)
+            } else {
+                self.position(col as f64 + t, row as f64)
+            }
+        };
+        join_segments(&segments)
+            .into_iter()
+            .map(|edges| edges.into_iter().map(crossing).collect())
+            .collect()
+    }
+
+    /// The parts of each square within the bands between `levels`, with the color of their
+    /// band. The lowest band is below the first level, the highest above the last one.
+    fn bands(&self, levels: &[f64], colormap: &Colormap) -> Vec<(Vec<Value>, Color32)> {
+        let colors: Vec<Color32> = (0..=levels.len())
+            .map(|band| colormap.color_at(band as f32 / levels.len().max(1) as f32))
+            .collect();
+        let mut polygons = Vec::new();
+        for row in 0..self.rows.saturating_sub(1) {
+            for col in 0..self.cols.saturating_sub(1) {
+                let corners = match self.square(col, row) {
+                    Some(corners) => corners,
+                    None => continue,
+                };
+                let square: Vec<(Value, f64)> = corners
+                    .iter()
+                    .map(|&(col, row)| {
+                        (self.position(col as f64, row as f64), self.value(col, row))
+                    })
+                    .collect();
+                let (min, max) = square.iter().fold(
+                    (f64::INFINITY, f64::NEG_INFINITY),
+                    |(min, max), (_, value)| (min.min(*value), max.max(*value)),
+                );
+
+                for (band, color) in colors.iter().enumerate() {
+                    let lower = band.checked_sub(1).map_or(f64::NEG_INFINITY, |i| levels[i]);
+                    let upper = levels.get(band).copied().unwrap_or(f64::INFINITY);
+                    if max < lower || upper < min {
+                        continue;
+                    }
+                    let polygon = clip(&clip(&square, lower, true), upper, false);
+                    if polygon.len() >= 3 {
+                        let points = polygon.into_iter().map(|(point, _)| point).collect();
+                        polygons.push((points, *color));
+                    }
+                }
+            }
+        }
+        polygons
+    }
+}
+
+/// The part of a convex polygon where the interpolated value is above (or below) `level`.
+fn clip(polygon: &[(Value, f64)], level: f64, keep_above: bool) -> Vec<(Value, f64)> {
+    let inside = |value: f64| {
+        if keep_above {
+            value >= level
+        } else {
+            value <= level
+        }
+    };
+    let mut clipped = Vec::with_capacity(polygon.len() + 2);
+    for (i, &(point, value)) in polygon.iter().enumerate() {
+        let (next_point, next_value) = polygon[(i + 1) % polygon.len()];
+        if inside(value) {
+            clipped.push((point, value));
+        }
+        if inside(value) != inside(next_value) {
+            let t = (level - value) / (next_value - value);
+            let crossing = Value::new(
+                point.x + t * (next_point.x - point.x),
+                point.y + t * (next_point.y - point.y),
+            );
+            clipped.push((crossing, level));
+        }
+    }
+    clipped
+}
+
+/// Join segments between edges into lines through consecutive edges.
+fn join_segments(segments: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    // An edge is shared by at most two squares, so at most two segments meet at an edge.
+    let mut by_edge: AHashMap<usize, Vec<usize>> = AHashMap::default();
+    for (index, segment) in segments.iter().enumerate() {
+        for edge in segment {
+            by_edge.entry(*edge).or_default().push(index);
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut lines = Vec::new();
+    for start in 0..segments.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let mut forward = segments[start].to_vec();
+        let mut backward = Vec::new();
+        for line in [&mut forward, &mut backward] {
+            let mut end = *line.last().unwrap_or(&segments[start][0]);
+            while let Some(&next) = by_edge[&end].iter().find(|&&segment| !used[segment]) {
+                used[next] = true;
+                let [a, b] = segments[next];
+                end = if a == end { b } else { a };
+                line.push(end);
+            }
+        }
+        backward.reverse();
+        backward.extend(forward);
+        lines.push(backward);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contour_lines() {
+        // A peak in the middle of a 3x3 grid.
+        #[rustfmt::skip]
+        let values = vec![
+            0.0, 0.0, 0.0,
+            0.0, 2.0, 0.0,
+            0.0, 0.0, 0.0,
+        ];
+        let contour = Contour::new(values, 3).levels(vec![1.0]).build();
+        assert_eq!(contour.lines.len(), 1);
+
+        // One closed line through the midpoints of the edges around the peak.
+        let line = &contour.points[contour.lines[0].0.clone()];
+        assert_eq!(line.len(), 5);
+        assert_eq!(line.first(), line.last());
+        for point in &line[..4] {
+            assert!(
+                ((point.x - 1.0).abs() - 0.5).abs() + (point.y - 1.0).abs() < 1e-9
+                    || ((point.y - 1.0).abs() - 0.5).abs() + (point.x - 1.0).abs() < 1e-9
+            );
+        }
+
+        // The filled bands cover the whole grid.
+        let contour = Contour::new(vec![0.0, 1.0, 2.0, 3.0], 2)
+            .levels(vec![0.5, 1.5])
+            .fill_bands(Colormap::Grayscale)
+            .build();
+        let area: f64 = contour
+            .bands
+            .iter()
+            .map(|(polygon, _)| {
+                let n = polygon.len();
+                (0..n)
+                    .map(|i| {
+                        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                        a.x * b.y - b.x * a.y
+                    })
+                    .sum::<f64>()
+                    / 2.0
+            })
+            .sum();
+        assert!((area - 1.0).abs() < 1e-9);
+    }
+}
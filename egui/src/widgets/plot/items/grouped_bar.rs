@@ -0,0 +1,125 @@
+use super::BarChart;
+
+/// Several [`BarChart`]s side by side, e.g. to compare a few series over the same categories.
+///
+/// The `i`-th bar of each chart is placed in the `i`-th category, next to the `i`-th bars of
+/// the other charts. The categories are centered at `0.0`, `1.0`, `2.0`, … on the argument axis,
+/// so their names can be shown with [`Plot::x_axis_categories`](crate::plot::Plot::x_axis_categories)
+/// (or [`Plot::y_axis_categories`](crate::plot::Plot::y_axis_categories) for horizontal bars).
+///
+/// Each chart keeps its own color and legend entry.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Bar, BarChart, GroupedBarChart, Legend, Plot};
+/// let chart = |name: &str, values: &[f64]| {
+///     BarChart::new(values.iter().map(|value| Bar::new(0.0, *value)).collect()).name(name)
+/// };
+/// let quarters = ["Q1", "Q2", "Q3", "Q4"];
+/// let group = GroupedBarChart::new(vec![
+///     chart("2021", &[3.0, 4.5, 2.0, 5.0]),
+///     chart("2022", &[3.5, 4.0, 3.0, 6.0]),
+/// ])
+/// .categories(quarters);
+/// Plot::new("sales")
+///     .legend(Legend::default())
+///     .x_axis_categories(quarters)
+///     .show(ui, |plot_ui| plot_ui.grouped_bar_chart(group));
+/// # });
+/// ```
+pub struct GroupedBarChart {
+    charts: Vec<BarChart>,
+    categories: Vec<String>,
+    group_width: f64,
+    bar_gap: f64,
+}
+
+impl GroupedBarChart {
+    /// Group the bars of the `charts`, in the given order within each category.
+    ///
+    /// The arguments and widths of the bars are replaced when the group is added to the plot.
+    pub fn new(charts: Vec<BarChart>) -> Self {
+        Self {
+            charts,
+            categories: Vec::new(),
+            group_width: 0.8,
+            bar_gap: 0.1,
+        }
+    }
+
+    /// Names of the categories. Bars without a name get the name of their category, which shows
+    /// up when hovering them.
+    pub fn categories(mut self, categories: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.categories = categories
+            .into_iter()
+            .map(|category| category.to_string())
+            .collect();
+        self
+    }
+
+    /// Width of all bars in a category together, including the gaps between them.
+    /// Default: `0.8`, i.e. the categories are `0.2` apart.
+    pub fn group_width(mut self, width: f64) -> Self {
+        self.group_width = width;
+        self
+    }
+
+    /// Fraction of the space of each bar left empty, to separate it from its neighbors.
+    /// Default: `0.1`.
+    pub fn bar_gap(mut self, gap: f64) -> Self {
+        self.bar_gap = gap.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The charts, with their bars moved next to each other within the categories.
+    pub(crate) fn layout(self) -> Vec<BarChart> {
+        let slot = self.group_width / self.charts.len().max(1) as f64;
+        let categories = self.categories;
+        self.charts
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut chart)| {
+                let offset = (index as f64 + 0.5) * slot - self.group_width / 2.0;
+                for (category, bar) in chart.bars.iter_mut().enumerate() {
+                    bar.argument = category as f64 + offset;
+                    bar.bar_width = slot * (1.0 - self.bar_gap);
+                    if bar.name.is_empty() {
+                        if let Some(name) = categories.get(category) {
+                            bar.name = name.clone();
+                        }
+                    }
+                }
+                chart
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Bar;
+    use super::*;
+
+    #[test]
+    fn bars_side_by_side() {
+        let chart = || BarChart::new(vec![Bar::new(0.0, 1.0), Bar::new(0.0, 2.0)]);
+        let charts = GroupedBarChart::new(vec![chart(), chart()])
+            .categories(["a", "b"])
+            .group_width(0.8)
+            .bar_gap(0.5)
+            .layout();
+
+        let arguments: Vec<Vec<f64>> = charts
+            .iter()
+            .map(|chart| chart.bars.iter().map(|bar| bar.argument).collect())
+            .collect();
+        let expected = [vec![-0.2, 0.8], vec![0.2, 1.2]];
+        for (arguments, expected) in arguments.iter().zip(expected) {
+            for (argument, expected) in arguments.iter().zip(expected) {
+                assert!((argument - expected).abs() < 1e-9);
+            }
+        }
+        assert!((charts[0].bars[0].bar_width - 0.2).abs() < 1e-9);
+        assert_eq!(charts[1].bars[1].name, "b");
+    }
+}
@@ -0,0 +1,528 @@
+use std::ops::RangeInclusive;
+
+use epaint::Mesh;
+
+use crate::*;
+
+use super::{ClosestElem, LabelFormatter, PlotConfig, PlotGeometry, PlotItem};
+use crate::plot::{AxisScale, Corner, PlotBounds, ScreenTransform, Value};
+
+/// Maps values in `0.0..=1.0` to colors, e.g. for a [`Heatmap`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Colormap {
+    /// Perceptually uniform, from dark blue over green to yellow.
+    Viridis,
+
+    /// Perceptually uniform, from black over red to light yellow.
+    Inferno,
+
+    /// From black to white.
+    Grayscale,
+
+    /// Diverging, from blue over light gray to red. Suited for data centered around zero,
+    /// e.g. correlation matrices.
+    Coolwarm,
+
+    /// Linear interpolation between the given colors, which are spread evenly over `0.0..=1.0`.
+    Custom(Vec<Color32>),
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Self::Viridis
+    }
+}
+
+impl Colormap {
+    /// The color for `t` in `0.0..=1.0`. Values outside of that range are clamped.
+    pub fn color_at(&self, t: f32) -> Color32 {
+        let stops: &[Color32] = match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Inferno => &INFERNO,
+            Self::Grayscale => &[Color32::BLACK, Color32::WHITE],
+            Self::Coolwarm => &COOLWARM,
+            Self::Custom(colors) => colors,
+        };
+
+        match stops {
+            [] => Color32::TRANSPARENT,
+            [color] => *color,
+            _ => {
+                let t = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+                let i = (t.floor() as usize).min(stops.len() - 2);
+                lerp_color(stops[i], stops[i + 1], t - i as f32)
+            }
+        }
+    }
+}
+
+fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
+    let channel = |i: usize| emath::lerp(a[i] as f32..=b[i] as f32, t).round() as u8;
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+/// Samples of matplotlib's colormaps at steps of `0.1`.
+const VIRIDIS: [Color32; 11] = [
+    Color32::from_rgb(0x44, 0x01, 0x54),
+    Color32::from_rgb(0x48, 0x24, 0x75),
+    Color32::from_rgb(0x41, 0x44, 0x87),
+    Color32::from_rgb(0x35, 0x5f, 0x8d),
+    Color32::from_rgb(0x2a, 0x78, 0x8e),
+    Color32::from_rgb(0x21, 0x91, 0x8c),
+    Color32::from_rgb(0x22, 0xa8, 0x84),
+    Color32::from_rgb(0x44, 0xbf, 0x70),
+    Color32::from_rgb(0x7a, 0xd1, 0x51),
+    Color32::from_rgb(0xbd, 0xdf, 0x26),
+    Color32::from_rgb(0xfd, 0xe7, 0x25),
+];
+
+const INFERNO: [Color32; 11] = [
+    Color32::from_rgb(0x00, 0x00, 0x04),
+    Color32::from_rgb(0x16, 0x0b, 0x39),
+    Color32::from_rgb(0x42, 0x0a, 0x68),
+    Color32::from_rgb(0x6a, 0x17, 0x6e),
+    Color32::from_rgb(0x93, 0x26, 0x67),
+    Color32::from_rgb(0xbc, 0x37, 0x54),
+    Color32::from_rgb(0xdd, 0x51, 0x3a),
+    Color32::from_rgb(0xf3, 0x78, 0x19),
+    Color32::from_rgb(0xfc, 0xa5, 0x0a),
+    Color32::from_rgb(0xf6, 0xd7, 0x46),
+    Color32::from_rgb(0xfc, 0xff, 0xa4),
+];
+
+const COOLWARM: [Color32; 11] = [
+    Color32::from_rgb(0x3b, 0x4c, 0xc0),
+    Color32::from_rgb(0x59, 0x77, 0xe3),
+    Color32::from_rgb(0x7b, 0x9f, 0xf9),
+    Color32::from_rgb(0x9e, 0xbe, 0xff),
+    Color32::from_rgb(0xc0, 0xd4, 0xf5),
+    Color32::from_rgb(0xdd, 0xdc, 0xdc),
+    Color32::from_rgb(0xf2, 0xcb, 0xb7),
+    Color32::from_rgb(0xf7, 0xac, 0x8e),
+    Color32::from_rgb(0xee, 0x84, 0x68),
+    Color32::from_rgb(0xd6, 0x52, 0x44),
+    Color32::from_rgb(0xb4, 0x04, 0x26),
+];
+
+// ----------------------------------------------------------------------------
+
+/// A grid of values, each shown as a rectangular cell colored by a [`Colormap`].
+///
+/// Useful for spectrograms, correlation matrices and other 2D data. By default, a colorbar
+/// showing how values map to colors is drawn in a corner of the plot.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Colormap, Heatmap, Plot};
+/// let values = (0..12).map(|i| (i as f64).sin()).collect();
+/// let heatmap = Heatmap::new(values, 4)
+///     .colormap(Colormap::Coolwarm)
+///     .value_range(-1.0..=1.0);
+/// Plot::new("heatmap").show(ui, |plot_ui| plot_ui.heatmap(heatmap));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Heatmap {
+    pub(super) values: Vec<f64>,
+    pub(super) cols: usize,
+    pub(super) origin: Value,
+    pub(super) cell_size: [f64; 2],
+    pub(super) colormap: Colormap,
+    pub(super) value_range: Option<RangeInclusive<f64>>,
+    pub(super) colorbar: Option<Corner>,
+    pub(super) name: String,
+    pub(super) highlight: bool,
+}
+
+impl Heatmap {
+    /// Create a heatmap from `values` in row-major order, with `cols` values per row.
+    ///
+    /// The first row is at the bottom of the plot. Cells are `1.0` wide and high, with the
+    /// bottom left corner of the first cell at the origin. `NaN` values leave their cell empty.
+    pub fn new(values: Vec<f64>, cols: usize) -> Self {
+        Self {
+            values,
+            cols,
+            origin: Value::new(0.0, 0.0),
+            cell_size: [1.0, 1.0],
+            colormap: Colormap::default(),
+            value_range: None,
+            colorbar: Some(Corner::RightBottom),
+            name: Default::default(),
+            highlight: false,
+        }
+    }
+
+    /// Position of the bottom left corner of the first cell, in plot coordinates.
+    pub fn origin(mut self, origin: Value) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Width and height of each cell, in plot coordinates.
+    pub fn cell_size(mut self, width: f64, height: f64) -> Self {
+        self.cell_size = [width, height];
+        self
+    }
+
+    /// How values are mapped to colors. Default: [`Colormap::Viridis`].
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The values mapped to the first and last color of the [`Colormap`]. Values outside of
+    /// the range get the color of the closest end.
+    ///
+    /// By default this is the range of the finite values in the heatmap.
+    pub fn value_range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.value_range = Some(range);
+        self
+    }
+
+    /// Whether to draw a colorbar, and in which corner of the plot. Default: `Some(Corner::RightBottom)`.
+    pub fn colorbar(mut self, corner: Option<Corner>) -> Self {
+        self.colorbar = corner;
+        self
+    }
+
+    /// Highlight this heatmap in the plot.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Name of this heatmap.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub(crate) fn rows(&self) -> usize {
+        if self.cols == 0 {
+            0
+        } else {
+            self.values.len() / self.cols
+        }
+    }
+
+    fn value_range_or_auto(&self) -> RangeInclusive<f64> {
+        if let Some(range) = &self.value_range {
+            return range.clone();
+        }
+
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        if min <= max {
+            min..=max
+        } else {
+            0.0..=1.0
+        }
+    }
+
+    fn color_of(&self, value: f64, range: &RangeInclusive<f64>) -> Color32 {
+        let span = range.end() - range.start();
+        let t = if span == 0.0 {
+            0.5
+        } else {
+            (value - range.start()) / span
+        };
+        self.colormap.color_at(t as f32)
+    }
+
+    /// Plot coordinates of the lower left and upper right corner of a cell.
+    fn cell_corners(&self, col: usize, row: usize) -> (Value, Value) {
+        let min = Value::new(
+            self.origin.x + col as f64 * self.cell_size[0],
+            self.origin.y + row as f64 * self.cell_size[1],
+        );
+        let max = Value::new(min.x + self.cell_size[0], min.y + self.cell_size[1]);
+        (min, max)
+    }
+
+    fn add_colorbar(
+        &self,
+        ui: &Ui,
+        transform: &ScreenTransform,
+        corner: Corner,
+        range: &RangeInclusive<f64>,
+        shapes: &mut Vec<Shape>,
+    ) {
+        const STEPS: usize = 64;
+        const BAR_WIDTH: f32 = 12.0;
+        const PADDING: f32 = 4.0;
+
+        let frame = transform.frame().shrink(2.0 * PADDING);
+        let bar_height = (0.4 * frame.height()).clamp(40.0, 200.0);
+
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let text_color = ui.visuals().text_color();
+        let decimals = decimals_for(range.end() - range.start());
+        let labels = [*range.end(), *range.start()].map(|value| {
+            ui.fonts().layout_no_wrap(
+                format!("{:.*}", decimals, value),
+                font_id.clone(),
+                text_color,
+            )
+        });
+        let label_width = labels[0].size().x.max(labels[1].size().x);
+        let label_height = labels[0].size().y;
+
+        let size = vec2(BAR_WIDTH + PADDING + label_width, bar_height + label_height);
+        let min = match corner {
+            Corner::LeftTop => frame.left_top(),
+            Corner::RightTop => pos2(frame.right() - size.x, frame.top()),
+            Corner::LeftBottom => pos2(frame.left(), frame.bottom() - size.y),
+            Corner::RightBottom => frame.right_bottom() - size,
+        };
+        let outer = Rect::from_min_size(min, size);
+
+        let visuals = &ui.style().visuals;
+        shapes.push(Shape::rect_filled(
+            outer.expand(PADDING),
+            visuals.window_rounding,
+            visuals.extreme_bg_color.linear_multiply(0.75),
+        ));
+
+        // The bar is centered vertically on its labels, with the highest value on top.
+        let bar = Rect::from_min_size(
+            outer.min + vec2(0.0, label_height / 2.0),
+            vec2(BAR_WIDTH, bar_height),
+        );
+        let mut mesh = Mesh::default();
+        for step in 0..STEPS {
+            let t0 = step as f32 / STEPS as f32;
+            let t1 = (step + 1) as f32 / STEPS as f32;
+            let rect = Rect::from_x_y_ranges(
+                bar.x_range(),
+                bar.bottom() - t1 * bar_height..=bar.bottom() - t0 * bar_height,
+            );
+            mesh.add_colored_rect(rect, self.colormap.color_at((t0 + t1) / 2.0));
+        }
+        shapes.push(Shape::mesh(mesh));
+        shapes.push(Shape::rect_stroke(
+            bar,
+            0.0,
+            visuals.widgets.noninteractive.bg_stroke,
+        ));
+
+        let [max_label, min_label] = labels;
+        let label_x = bar.right() + PADDING;
+        shapes.push(Shape::galley(pos2(label_x, outer.top()), max_label));
+        shapes.push(Shape::galley(
+            pos2(label_x, outer.bottom() - label_height),
+            min_label,
+        ));
+    }
+}
+
+impl PlotItem for Heatmap {
+    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let rows = self.rows();
+        if rows == 0 {
+            return;
+        }
+        let range = self.value_range_or_auto();
+
+        // The axes are independent of each other, so the screen positions of the cell borders
+        // only have to be computed once per column and row.
+        let xs: Vec<f32> = (0..=self.cols)
+            .map(|col| {
+                let x = self.origin.x + col as f64 * self.cell_size[0];
+                transform.position_from_value(&Value::new(x, 0.0)).x
+            })
+            .collect();
+        let ys: Vec<f32> = (0..=rows)
+            .map(|row| {
+                let y = self.origin.y + row as f64 * self.cell_size[1];
+                transform.position_from_value(&Value::new(0.0, y)).y
+            })
+            .collect();
+
+        let frame = *transform.frame();
+        let mut mesh = Mesh::default();
+        for row in 0..rows {
+            for col in 0..self.cols {
+                let value = self.values[row * self.cols + col];
+                if value.is_nan() {
+                    continue;
+                }
+                let rect =
+                    Rect::from_two_pos(pos2(xs[col], ys[row]), pos2(xs[col + 1], ys[row + 1]));
+                if rect.intersects(frame) {
+                    mesh.add_colored_rect(rect, self.color_of(value, &range));
+                }
+            }
+        }
+        shapes.push(Shape::mesh(mesh));
+
+        if self.highlight {
+            let (min, _) = self.cell_corners(0, 0);
+            let (_, max) = self.cell_corners(self.cols - 1, rows - 1);
+            shapes.push(Shape::rect_stroke(
+                transform.rect_from_values(&min, &max),
+                0.0,
+                Stroke::new(1.0, ui.visuals().strong_text_color()),
+            ));
+        }
+
+        if let Some(corner) = self.colorbar {
+            self.add_colorbar(ui, transform, corner, &range, shapes);
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.colormap.color_at(0.5)
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        let rows = self.rows();
+        if rows > 0 {
+            let (min, _) = self.cell_corners(0, 0);
+            let (_, max) = self.cell_corners(self.cols - 1, rows - 1);
+            bounds.extend_with(&min);
+            bounds.extend_with(&max);
+        }
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let value = transform.value_from_position(point);
+        let col = ((value.x - self.origin.x) / self.cell_size[0]).floor();
+        let row = ((value.y - self.origin.y) / self.cell_size[1]).floor();
+        if !(0.0..self.cols as f64).contains(&col) || !(0.0..self.rows() as f64).contains(&row) {
+            return None;
+        }
+
+        let index = row as usize * self.cols + col as usize;
+        (!self.values[index].is_nan()).then(|| ClosestElem {
+            index,
+            dist_sq: 0.0,
+        })
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        _: &LabelFormatter,
+    ) {
+        let (col, row) = (elem.index % self.cols, elem.index / self.cols);
+        let (min, max) = self.cell_corners(col, row);
+        let rect = plot.transform.rect_from_values(&min, &max);
+        shapes.push(Shape::rect_stroke(
+            rect,
+            0.0,
+            Stroke::new(2.0, plot.ui.visuals().strong_text_color()),
+        ));
+
+        let mut text = String::new();
+        if !self.name.is_empty() {
+            text.push_str(&self.name);
+            text.push('\n');
+        }
+        let center = Value::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+        let scale = plot.transform.dvalue_dpos_at(&center);
+        if plot.show_x {
+            let x_decimals = ((-scale[0].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+            text.push_str(&format!("x = {:.*}\n", x_decimals, center.x));
+        }
+        if plot.show_y {
+            let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+            text.push_str(&format!("y = {:.*}\n", y_decimals, center.y));
+        }
+        let range = self.value_range_or_auto();
+        let decimals = decimals_for(range.end() - range.start());
+        text.push_str(&format!("value = {:.*}", decimals, self.values[elem.index]));
+
+        let font_id = TextStyle::Body.resolve(plot.ui.style());
+        shapes.push(Shape::text(
+            &*plot.ui.fonts(),
+            rect.right_top() + vec2(3.0, -2.0),
+            Align2::LEFT_BOTTOM,
+            text,
+            font_id,
+            plot.ui.visuals().text_color(),
+        ));
+    }
+}
+
+/// Enough decimals to tell apart values about a hundredth of `span` apart.
+pub(super) fn decimals_for(span: f64) -> usize {
+    if span > 0.0 {
+        ((2.0 - span.log10()).ceil().at_least(0.0) as usize).at_most(6)
+    } else {
+        3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epaint::{pos2, vec2, Rect};
+
+    #[test]
+    fn heatmap_colors_and_cells() {
+        let grayscale = Colormap::Grayscale;
+        assert_eq!(grayscale.color_at(-1.0), Color32::BLACK);
+        assert_eq!(grayscale.color_at(0.5), Color32::from_gray(128));
+        assert_eq!(grayscale.color_at(2.0), Color32::WHITE);
+        assert_eq!(Colormap::Custom(vec![]).color_at(0.5), Color32::TRANSPARENT);
+
+        // Two rows of three cells, the first row at the bottom.
+        let heatmap = Heatmap::new(vec![0.0, 1.0, 2.0, 3.0, f64::NAN, 4.0], 3)
+            .origin(Value::new(10.0, 20.0))
+            .cell_size(2.0, 0.5)
+            .colormap(Colormap::Grayscale);
+        assert_eq!(heatmap.rows(), 2);
+        assert_eq!(heatmap.value_range_or_auto(), 0.0..=4.0);
+        assert_eq!(heatmap.color_of(4.0, &(0.0..=4.0)), Color32::WHITE);
+
+        let bounds = heatmap.get_bounds();
+        assert_eq!(bounds.min, [10.0, 20.0]);
+        assert_eq!(bounds.max, [16.0, 21.0]);
+
+        let frame = Rect::from_min_size(pos2(0.0, 0.0), vec2(60.0, 10.0));
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2]);
+        let closest = |x, y| {
+            let pos = transform.position_from_value(&Value::new(x, y));
+            heatmap.find_closest(pos, &transform).map(|elem| elem.index)
+        };
+        assert_eq!(closest(13.0, 20.25), Some(1));
+        assert_eq!(closest(15.0, 20.75), Some(5));
+        assert_eq!(closest(11.0, 20.75), Some(3));
+        // The `NaN` cell is empty:
+        assert_eq!(closest(13.0, 20.75), None);
+    }
+}
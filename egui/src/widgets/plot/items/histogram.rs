@@ -0,0 +1,424 @@
+use std::ops::RangeInclusive;
+
+use crate::*;
+
+use super::{
+    find_closest_rect, Bar, BarChart, ClosestElem, LabelFormatter, Orientation, PlotConfig,
+    PlotGeometry, PlotItem, RectElement,
+};
+use crate::plot::{AxisScale, PlotBounds, ScreenTransform, Value};
+
+/// The most bins a [`Histogram`] will create, no matter how small the bin width is.
+const MAX_BINS: usize = 10_000;
+
+/// How the samples of a [`Histogram`] are divided into bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Binning {
+    /// The given number of equally wide bins between the smallest and the largest sample.
+    Count(usize),
+
+    /// Bins of the given width, aligned to multiples of the width.
+    Width(f64),
+
+    /// The bin width is `2 * IQR / n^(1/3)`, where `IQR` is the interquartile range of the `n`
+    /// samples. Robust against outliers. Falls back to Sturges' rule (`log2(n) + 1` bins)
+    /// if the interquartile range is zero.
+    FreedmanDiaconis,
+}
+
+impl Default for Binning {
+    fn default() -> Self {
+        Self::FreedmanDiaconis
+    }
+}
+
+/// What the height of a bin in a [`Histogram`] shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization {
+    /// The number of samples in the bin.
+    Count,
+
+    /// The fraction of samples in the bin, divided by the bin width.
+    /// The total area of all bins is one.
+    Density,
+
+    /// The fraction of samples in the bin or any bin before it, i.e. the empirical
+    /// cumulative distribution. The last bin has a height of one.
+    Cumulative,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::Count
+    }
+}
+
+/// A histogram of raw samples, drawn as a [`BarChart`] of its bins or as a step line.
+///
+/// Non-finite samples are ignored.
+pub struct Histogram {
+    pub(crate) samples: Vec<f64>,
+    binning: Binning,
+    normalization: Normalization,
+    orientation: Orientation,
+    step: bool,
+    pub(crate) chart: BarChart,
+}
+
+impl Histogram {
+    /// Create a histogram of `samples`. It defaults to vertical bars, binned with
+    /// [`Binning::FreedmanDiaconis`] and showing counts.
+    pub fn new(samples: Vec<f64>) -> Self {
+        Self {
+            samples,
+            binning: Binning::default(),
+            normalization: Normalization::default(),
+            orientation: Orientation::default(),
+            step: false,
+            chart: BarChart::new(Vec::new()),
+        }
+    }
+
+    /// How to divide the samples into bins.
+    pub fn binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self
+    }
+
+    /// What the height of the bins shows.
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Draw the outline of the bins as a step line instead of bars. Default is `false`.
+    pub fn step(mut self, step: bool) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Stroke color of the bars or step line. The bars are filled with a lighter version of it.
+    /// Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.chart.default_color = color.into();
+        self
+    }
+
+    /// Name of this histogram.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.chart.name = name.to_string();
+        self
+    }
+
+    /// Bins along the X axis, with their heights on the Y axis. This is the default.
+    pub fn vertical(mut self) -> Self {
+        self.orientation = Orientation::Vertical;
+        self
+    }
+
+    /// Bins along the Y axis, with their heights on the X axis.
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Highlight the histogram.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.chart.highlight = highlight;
+        self
+    }
+
+    /// Add a custom way to format a bin when hovered.
+    /// By default, the range of the bin and its height are shown.
+    pub fn element_formatter(mut self, formatter: Box<dyn Fn(&Bar, &BarChart) -> String>) -> Self {
+        self.chart.element_formatter = Some(formatter);
+        self
+    }
+
+    /// Bin the samples into the bars of the chart.
+    pub(crate) fn build(mut self) -> Self {
+        let bins = bin_samples(&self.samples, self.binning, self.normalization);
+        let color = self.chart.default_color;
+        self.chart.bars = bins
+            .into_iter()
+            .map(|(lower, upper, height)| {
+                let mut bar = Bar::new((lower + upper) / 2.0, height)
+                    .width(upper - lower)
+                    .name(format!(
+                        "{} – {}",
+                        emath::format_with_decimals_in_range(lower, 0..=6),
+                        emath::format_with_decimals_in_range(upper, 0..=6)
+                    ));
+                bar.orientation = self.orientation;
+                bar
+            })
+            .collect();
+        self.chart = self.chart.color(color);
+        self
+    }
+
+    /// The outline of the bins, starting and ending on the argument axis.
+    fn step_values(&self) -> Vec<Value> {
+        let bars = &self.chart.bars;
+        let mut values = Vec::with_capacity(2 * bars.len() + 2);
+        for (index, bar) in bars.iter().enumerate() {
+            let lower = bar.argument - bar.bar_width / 2.0;
+            let upper = bar.argument + bar.bar_width / 2.0;
+            let previous = if index == 0 {
+                0.0
+            } else {
+                bars[index - 1].value
+            };
+            values.push(bar.point_at(lower, previous));
+            values.push(bar.point_at(lower, bar.value));
+            values.push(bar.point_at(upper, bar.value));
+        }
+        if let Some(last) = bars.last() {
+            values.push(last.point_at(last.argument + last.bar_width / 2.0, 0.0));
+        }
+        values
+    }
+}
+
+impl PlotItem for Histogram {
+    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        if !self.step {
+            self.chart.get_shapes(ui, transform, shapes);
+            return;
+        }
+
+        let width = if self.chart.highlight { 2.0 } else { 1.0 };
+        let points = self
+            .step_values()
+            .iter()
+            .map(|value| transform.position_from_value(value))
+            .collect();
+        shapes.push(Shape::line(
+            points,
+            Stroke::new(width, self.chart.default_color),
+        ));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
+
+    fn name(&self) -> &str {
+        self.chart.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.chart.default_color
+    }
+
+    fn highlight(&mut self) {
+        self.chart.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.chart.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        self.chart.get_bounds()
+    }
+
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        self.chart.value_at_x(x)
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        find_closest_rect(&self.chart.bars, point, transform)
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        if !self.step {
+            self.chart.on_hover(elem, shapes, plot, label_formatter);
+            return;
+        }
+
+        // There are no bars to highlight, only the top of the bin in the step line:
+        let bar = &self.chart.bars[elem.index];
+        let top = [
+            bar.point_at(bar.argument - bar.bar_width / 2.0, bar.value),
+            bar.point_at(bar.argument + bar.bar_width / 2.0, bar.value),
+        ];
+        shapes.push(Shape::line_segment(
+            top.map(|value| plot.transform.position_from_value(&value)),
+            Stroke::new(2.0, self.chart.default_color),
+        ));
+        bar.add_rulers_and_text(&self.chart, plot, shapes);
+    }
+}
+
+/// The `(lower edge, upper edge, height)` of each bin. Empty if there are no finite samples.
+fn bin_samples(
+    samples: &[f64],
+    binning: Binning,
+    normalization: Normalization,
+) -> Vec<(f64, f64, f64)> {
+    let mut sorted: Vec<f64> = samples.iter().copied().filter(|x| x.is_finite()).collect();
+    if sorted.is_empty() {
+        return Vec::new();
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+
+    let n = sorted.len() as f64;
+    let (start, width, count) = if min == max {
+        // All samples are equal: one bin of unit width around them.
+        (min - 0.5, 1.0, 1)
+    } else {
+        let aligned = |width: f64| {
+            let start = (min / width).floor() * width;
+            (start, width, ((max - start) / width).ceil() as usize)
+        };
+        let sturges = || {
+            let count = n.log2().ceil() as usize + 1;
+            (min, (max - min) / count as f64, count)
+        };
+        match binning {
+            Binning::Count(count) => {
+                let count = count.max(1);
+                (min, (max - min) / count as f64, count)
+            }
+            Binning::Width(width) if width > 0.0 => aligned(width),
+            Binning::Width(_) => sturges(),
+            Binning::FreedmanDiaconis => {
+                let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+                if iqr > 0.0 {
+                    aligned(2.0 * iqr / n.cbrt())
+                } else {
+                    sturges()
+                }
+            }
+        }
+    };
+    let (width, count) = if count > MAX_BINS {
+        ((max - start) / MAX_BINS as f64, MAX_BINS)
+    } else {
+        (width, count.max(1))
+    };
+
+    let mut counts = vec![0_usize; count];
+    for &sample in &sorted {
+        // Bins include their lower edge, the last bin also its upper edge.
+        let index = (((sample - start) / width).floor() as usize).min(count - 1);
+        counts[index] += 1;
+    }
+
+    let mut cumulative = 0;
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| {
+            let lower = start + index as f64 * width;
+            let height = match normalization {
+                Normalization::Count => count as f64,
+                Normalization::Density => count as f64 / (n * width),
+                Normalization::Cumulative => {
+                    cumulative += count;
+                    cumulative as f64 / n
+                }
+            };
+            (lower, lower + width, height)
+        })
+        .collect()
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let index = position.floor() as usize;
+    let next = (index + 1).min(sorted.len() - 1);
+    let t = position - index as f64;
+    sorted[index] + t * (sorted[next] - sorted[index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_bins() {
+        let samples = [0.0, 0.5, 1.0, 1.5, 2.0, 4.0, f64::NAN];
+
+        let bins = bin_samples(&samples, Binning::Count(2), Normalization::Count);
+        assert_eq!(bins, vec![(0.0, 2.0, 4.0), (2.0, 4.0, 2.0)]);
+
+        let bins = bin_samples(&samples, Binning::Width(1.5), Normalization::Count);
+        assert_eq!(
+            bins,
+            vec![(0.0, 1.5, 3.0), (1.5, 3.0, 2.0), (3.0, 4.5, 1.0)]
+        );
+
+        let bins = bin_samples(&samples, Binning::Width(2.0), Normalization::Density);
+        let area: f64 = bins
+            .iter()
+            .map(|(lower, upper, h)| (upper - lower) * h)
+            .sum();
+        assert!((area - 1.0).abs() < 1e-9);
+
+        let bins = bin_samples(&samples, Binning::Width(2.0), Normalization::Cumulative);
+        assert_eq!(bins.last().unwrap().2, 1.0);
+
+        let bins = bin_samples(&[3.0, 3.0], Binning::FreedmanDiaconis, Normalization::Count);
+        assert_eq!(bins, vec![(2.5, 3.5, 2.0)]);
+        assert!(bin_samples(&[], Binning::default(), Normalization::Count).is_empty());
+    }
+
+    #[test]
+    fn step_histogram_hover() {
+        let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+        let bounds = PlotBounds::from_min_max([0.0, 0.0], [10.0, 10.0]);
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2]);
+        let samples = vec![1.0, 2.0, 2.5, 4.0];
+        let histogram = |step| {
+            Histogram::new(samples.clone())
+                .binning(Binning::Width(2.0))
+                .color(Color32::RED)
+                .step(step)
+                .build()
+        };
+        let is_bar = |shape: &Shape| matches!(shape, Shape::Rect(_));
+
+        crate::__run_test_ui(|ui| {
+            let plot = PlotConfig {
+                ui,
+                transform: &transform,
+                show_x: true,
+                show_y: true,
+            };
+            let elem = || ClosestElem {
+                index: 1,
+                dist_sq: 0.0,
+            };
+
+            let mut shapes = vec![];
+            histogram(false).on_hover(elem(), &mut shapes, &plot, &None);
+            assert!(shapes.iter().any(is_bar));
+
+            // The top of the bin from 2 to 4, with its three samples, is highlighted instead:
+            let mut shapes = vec![];
+            histogram(true).on_hover(elem(), &mut shapes, &plot, &None);
+            assert!(!shapes.iter().any(is_bar));
+            let top = [pos2(20.0, 70.0), pos2(40.0, 70.0)];
+            assert!(shapes.iter().any(|shape| matches!(
+                shape,
+                Shape::LineSegment { points, .. } if *points == top
+            )));
+        });
+    }
+}
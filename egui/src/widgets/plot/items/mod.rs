@@ -7,21 +7,39 @@ use epaint::Mesh;
 
 use crate::*;
 
-use super::{LabelFormatter, PlotBounds, ScreenTransform};
+use super::{AxisScale, LabelFormatter, PlotBounds, ScreenTransform};
 use rect_elem::*;
-use values::{ClosestElem, PlotGeometry};
+use values::ClosestElem;
+pub(super) use values::PlotGeometry;
 
 pub use bar::Bar;
 pub use box_elem::{BoxElem, BoxSpread};
+pub use candle_elem::{CandleElem, Ohlc};
+pub use contour::Contour;
+pub use grouped_bar::GroupedBarChart;
+pub use heatmap::{Colormap, Heatmap};
+pub use histogram::{Binning, Histogram, Normalization};
+pub use pie::{PieChart, PieSlice};
+pub use stream::ValueStream;
 pub use values::{LineStyle, MarkerShape, Orientation, Value, Values};
 
 mod bar;
 mod box_elem;
+mod candle_elem;
+mod contour;
+mod grouped_bar;
+mod heatmap;
+mod histogram;
+mod pie;
 mod rect_elem;
+mod stream;
 mod values;
 
 const DEFAULT_FILL_ALPHA: f32 = 0.05;
 
+/// Length of the caps at the ends of error bars, in points.
+const ERROR_BAR_CAP_LENGTH: f32 = 6.0;
+
 /// Container to pass-through several parameters related to plot visualization
 pub(super) struct PlotConfig<'a> {
     pub ui: &'a Ui,
@@ -33,7 +51,7 @@ pub(super) struct PlotConfig<'a> {
 /// Trait shared by things that can be drawn in the plot.
 pub(super) trait PlotItem {
     fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>);
-    fn initialize(&mut self, x_range: RangeInclusive<f64>);
+    fn initialize(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale);
     fn name(&self) -> &str;
     fn color(&self) -> Color32;
     fn highlight(&mut self);
@@ -41,6 +59,12 @@ pub(super) trait PlotItem {
     fn geometry(&self) -> PlotGeometry<'_>;
     fn get_bounds(&self) -> PlotBounds;
 
+    /// The y value of the item at the given `x`, for series such as lines, points and bars.
+    /// Used for the readout of a linked cursor.
+    fn value_at_x(&self, _x: f64) -> Option<f64> {
+        None
+    }
+
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
         match self.geometry() {
             PlotGeometry::None => None,
@@ -175,7 +199,7 @@ impl PlotItem for HLine {
         style.style_line(points, *stroke, *highlight, shapes);
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
 
     fn name(&self) -> &str {
         &self.name
@@ -203,6 +227,10 @@ impl PlotItem for HLine {
         bounds.max[1] = self.y;
         bounds
     }
+
+    fn value_at_x(&self, _x: f64) -> Option<f64> {
+        Some(self.y)
+    }
 }
 
 /// A vertical line in a plot, filling the full width
@@ -285,7 +313,7 @@ impl PlotItem for VLine {
         style.style_line(points, *stroke, *highlight, shapes);
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
 
     fn name(&self) -> &str {
         &self.name
@@ -404,11 +432,7 @@ impl PlotItem for Line {
             ..
         } = self;
 
-        let values_tf: Vec<_> = series
-            .values
-            .iter()
-            .map(|v| transform.position_from_value(v))
-            .collect();
+        let values_tf = series.decimated_line(transform);
         let n_values = values_tf.len();
 
         // Fill the area between the line and a reference line, if required.
@@ -453,8 +477,8 @@ impl PlotItem for Line {
         style.style_line(values_tf, *stroke, *highlight, shapes);
     }
 
-    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
-        self.series.generate_points(x_range);
+    fn initialize(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale) {
+        self.series.generate_points(x_range, x_scale);
     }
 
     fn name(&self) -> &str {
@@ -480,6 +504,10 @@ impl PlotItem for Line {
     fn get_bounds(&self) -> PlotBounds {
         self.series.get_bounds()
     }
+
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        self.series.interpolate_y(x)
+    }
 }
 
 /// A convex polygon.
@@ -583,8 +611,8 @@ impl PlotItem for Polygon {
         style.style_line(values_tf, *stroke, *highlight, shapes);
     }
 
-    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
-        self.series.generate_points(x_range);
+    fn initialize(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale) {
+        self.series.generate_points(x_range, x_scale);
     }
 
     fn name(&self) -> &str {
@@ -612,6 +640,206 @@ impl PlotItem for Polygon {
     }
 }
 
+/// The area between two series, e.g. a confidence interval around a [`Line`].
+///
+/// The `i`-th value of `lower` is paired with the `i`-th value of `upper`, so both series
+/// should have the same length and usually share their x values.
+pub struct Band {
+    pub(super) lower: Values,
+    pub(super) upper: Values,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) highlight: bool,
+    pub(super) fill_alpha: f32,
+}
+
+impl Band {
+    pub fn new(lower: Values, upper: Values) -> Self {
+        Self {
+            lower,
+            upper,
+            stroke: Stroke::new(0.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            highlight: false,
+            fill_alpha: 0.2,
+        }
+    }
+
+    /// Highlight this band in the plot by reducing the fill transparency.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a stroke along the lower and upper edge.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Width of the edges. Default is `0.0`, i.e. only the area is filled.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Color of the band. Default is `Color32::TRANSPARENT` which means a color will be
+    /// auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Alpha of the filled area. Default is `0.2`.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
+        self
+    }
+
+    /// Name of this band.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// The value with the given index into the lower values followed by the upper values.
+    fn value(&self, index: usize) -> Value {
+        let n_lower = self.lower.values.len();
+        if index < n_lower {
+            self.lower.values[index]
+        } else {
+            self.upper.values[index - n_lower]
+        }
+    }
+}
+
+impl PlotItem for Band {
+    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let Self {
+            lower,
+            upper,
+            stroke,
+            highlight,
+            mut fill_alpha,
+            ..
+        } = self;
+
+        if *highlight {
+            fill_alpha = (2.0 * fill_alpha).at_most(1.0);
+        }
+        let fill_color = Rgba::from(stroke.color)
+            .to_opaque()
+            .multiply(fill_alpha)
+            .into();
+
+        let lower_tf: Vec<_> = lower
+            .values
+            .iter()
+            .map(|v| transform.position_from_value(v))
+            .collect();
+        let upper_tf: Vec<_> = upper
+            .values
+            .iter()
+            .map(|v| transform.position_from_value(v))
+            .collect();
+        let n_values = lower_tf.len().min(upper_tf.len());
+
+        if n_values >= 2 {
+            // A strip of quads between each pair of values and the next one.
+            let mut mesh = Mesh::default();
+            mesh.reserve_vertices(n_values * 2);
+            mesh.reserve_triangles((n_values - 1) * 2);
+            for (lower, upper) in lower_tf.iter().zip(&upper_tf) {
+                mesh.colored_vertex(*lower, fill_color);
+                mesh.colored_vertex(*upper, fill_color);
+            }
+            for i in 0..n_values as u32 - 1 {
+                mesh.add_triangle(2 * i, 2 * i + 1, 2 * i + 2);
+                mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+            }
+            shapes.push(Shape::Mesh(mesh));
+        }
+
+        if stroke.width > 0.0 {
+            let style = LineStyle::Solid;
+            style.style_line(lower_tf, *stroke, *highlight, shapes);
+            style.style_line(upper_tf, *stroke, *highlight, shapes);
+        }
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale) {
+        self.lower.generate_points(x_range.clone(), x_scale);
+        self.upper.generate_points(x_range, x_scale);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = self.lower.get_bounds();
+        bounds.merge(&self.upper.get_bounds());
+        bounds
+    }
+
+    /// The center of the band.
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        let lower = self.lower.interpolate_y(x)?;
+        let upper = self.upper.interpolate_y(x)?;
+        Some((lower + upper) / 2.0)
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        self.lower
+            .values
+            .iter()
+            .chain(self.upper.values.iter())
+            .enumerate()
+            .map(|(index, value)| {
+                let pos = transform.position_from_value(value);
+                let dist_sq = point.distance_sq(pos);
+                ClosestElem { index, dist_sq }
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let value = self.value(elem.index);
+        let pointer = plot.transform.position_from_value(&value);
+        shapes.push(Shape::circle_filled(pointer, 3.0, rulers_color(plot.ui)));
+
+        rulers_at_value(pointer, value, self.name(), plot, shapes, label_formatter);
+    }
+}
+
 /// Text inside the plot.
 #[derive(Clone)]
 pub struct Text {
@@ -699,7 +927,7 @@ impl PlotItem for Text {
         }
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
 
     fn name(&self) -> &str {
         self.name.as_str()
@@ -741,6 +969,8 @@ pub struct Points {
     pub(super) name: String,
     pub(super) highlight: bool,
     pub(super) stems: Option<f32>,
+    /// Lengths of the error bars below and above each value, for the x and y axis.
+    pub(super) errors: [Option<(Vec<f64>, Vec<f64>)>; 2],
 }
 
 impl Points {
@@ -754,6 +984,7 @@ impl Points {
             name: Default::default(),
             highlight: false,
             stems: None,
+            errors: [None, None],
         }
     }
 
@@ -793,6 +1024,50 @@ impl Points {
         self
     }
 
+    /// Add horizontal error bars, extending by `errors[i]` to both sides of the `i`-th value.
+    pub fn x_errors(mut self, errors: Vec<f64>) -> Self {
+        self.errors[0] = Some((errors.clone(), errors));
+        self
+    }
+
+    /// Add horizontal error bars, extending by `minus[i]` to the left and `plus[i]` to the right
+    /// of the `i`-th value.
+    pub fn x_errors_asymmetric(mut self, minus: Vec<f64>, plus: Vec<f64>) -> Self {
+        self.errors[0] = Some((minus, plus));
+        self
+    }
+
+    /// Add vertical error bars, extending by `errors[i]` below and above the `i`-th value.
+    pub fn y_errors(mut self, errors: Vec<f64>) -> Self {
+        self.errors[1] = Some((errors.clone(), errors));
+        self
+    }
+
+    /// Add vertical error bars, extending by `minus[i]` below and `plus[i]` above the `i`-th
+    /// value.
+    pub fn y_errors_asymmetric(mut self, minus: Vec<f64>, plus: Vec<f64>) -> Self {
+        self.errors[1] = Some((minus, plus));
+        self
+    }
+
+    /// The error bar of the `index`-th value along `axis`, as the values at its two ends.
+    fn error_bar(&self, index: usize, axis: usize) -> Option<(Value, Value)> {
+        let (minus, plus) = self.errors[axis].as_ref()?;
+        let (minus, plus) = (*minus.get(index)?, *plus.get(index)?);
+        let value = self.series.values[index];
+        Some(if axis == 0 {
+            (
+                Value::new(value.x - minus, value.y),
+                Value::new(value.x + plus, value.y),
+            )
+        } else {
+            (
+                Value::new(value.x, value.y - minus),
+                Value::new(value.x, value.y + plus),
+            )
+        })
+    }
+
     /// Name of this set of points.
     ///
     /// This name will show up in the plot legend, if legends are turned on.
@@ -840,10 +1115,50 @@ impl PlotItem for Points {
         let y_reference =
             stems.map(|y| transform.position_from_value(&Value::new(0.0, y)).y as f32);
 
-        series
-            .values
+        let visible = series.visible_indices_in(transform);
+
+        // Error bars with short caps at their ends.
+        if self.errors.iter().any(Option::is_some) {
+            let error_stroke = Stroke::new(if *highlight { 2.0 } else { 1.0 }, *color);
+            let cap = ERROR_BAR_CAP_LENGTH / 2.0;
+            for index in visible.clone() {
+                for axis in 0..2 {
+                    if let Some((start, end)) = self.error_bar(index, axis) {
+                        let start = transform.position_from_value(&start);
+                        let end = transform.position_from_value(&end);
+                        let cap = if axis == 0 {
+                            vec2(0.0, cap)
+                        } else {
+                            vec2(cap, 0.0)
+                        };
+                        shapes.push(Shape::line_segment([start, end], error_stroke));
+                        shapes.push(Shape::line_segment(
+                            [start - cap, start + cap],
+                            error_stroke,
+                        ));
+                        shapes.push(Shape::line_segment([end - cap, end + cap], error_stroke));
+                    }
+                }
+            }
+        }
+
+        // Only paint markers that can be seen, and only one per pixel.
+        let visible_rect = transform.frame().expand(radius);
+        let mut last_pixel = None;
+        series.values[visible]
             .iter()
             .map(|value| transform.position_from_value(value))
+            .filter(|center| {
+                let visible = if y_reference.is_some() {
+                    visible_rect.x_range().contains(&center.x)
+                } else {
+                    visible_rect.contains(*center)
+                };
+                let pixel = Some(center.round());
+                let new_pixel = pixel != last_pixel;
+                last_pixel = pixel;
+                visible && new_pixel
+            })
             .for_each(|center| {
                 let tf = |dx: f32, dy: f32| -> Pos2 { center + radius * vec2(dx, dy) };
 
@@ -935,8 +1250,8 @@ impl PlotItem for Points {
             });
     }
 
-    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
-        self.series.generate_points(x_range);
+    fn initialize(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale) {
+        self.series.generate_points(x_range, x_scale);
     }
 
     fn name(&self) -> &str {
@@ -960,10 +1275,136 @@ impl PlotItem for Points {
     }
 
     fn get_bounds(&self) -> PlotBounds {
-        self.series.get_bounds()
+        let mut bounds = self.series.get_bounds();
+        for index in 0..self.series.values.len() {
+            for axis in 0..2 {
+                if let Some((start, end)) = self.error_bar(index, axis) {
+                    bounds.extend_with(&start);
+                    bounds.extend_with(&end);
+                }
+            }
+        }
+        bounds
+    }
+
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        self.series.nearest_y(x)
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let value = self.series.values[elem.index];
+        let pointer = plot.transform.position_from_value(&value);
+        shapes.push(Shape::circle_filled(pointer, 3.0, rulers_color(plot.ui)));
+
+        rulers_at_value(pointer, value, self.name(), plot, shapes, label_formatter);
+
+        // Show the errors below the value.
+        let scale = plot.transform.dvalue_dpos_at(&value);
+        let errors: Vec<String> = (0..2)
+            .filter_map(|axis| {
+                let (minus, plus) = self.errors[axis].as_ref()?;
+                let (minus, plus) = (minus.get(elem.index)?, plus.get(elem.index)?);
+                let decimals =
+                    ((-scale[axis].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+                let axis_name = if axis == 0 { "x" } else { "y" };
+                Some(if minus == plus {
+                    format!("{} error: ±{:.*}", axis_name, decimals, minus)
+                } else {
+                    format!(
+                        "{} error: -{:.*} / +{:.*}",
+                        axis_name, decimals, minus, decimals, plus
+                    )
+                })
+            })
+            .collect();
+        if !errors.is_empty() {
+            let font_id = TextStyle::Body.resolve(plot.ui.style());
+            shapes.push(Shape::text(
+                &*plot.ui.fonts(),
+                pointer + vec2(3.0, 2.0),
+                Align2::LEFT_TOP,
+                errors.join("\n"),
+                font_id,
+                plot.ui.visuals().text_color(),
+            ));
+        }
+    }
+}
+
+/// Points that the user can drag around with the primary mouse button, e.g. to edit the nodes
+/// of an envelope.
+///
+/// Add them with [`PlotUi::drag_handles`](super::PlotUi::drag_handles), which reports the handle
+/// that is being dragged. The plot doesn't pan while a handle is held.
+pub struct DragHandles {
+    pub(super) id: Id,
+    pub(super) values: Vec<Value>,
+    pub(super) shape: MarkerShape,
+    /// Color of the handles. `Color32::TRANSPARENT` means that it will be picked automatically.
+    pub(super) color: Color32,
+    pub(super) radius: f32,
+    pub(super) name: String,
+}
+
+impl DragHandles {
+    /// The `id_source` tells the handles apart from other handles in the same plot, and must be
+    /// the same every frame.
+    pub fn new(id_source: impl std::hash::Hash, values: Vec<Value>) -> Self {
+        Self {
+            id: Id::new(id_source),
+            values,
+            shape: MarkerShape::Circle,
+            color: Color32::TRANSPARENT,
+            radius: 5.0,
+            name: Default::default(),
+        }
+    }
+
+    /// Set the shape of the handles.
+    pub fn shape(mut self, shape: MarkerShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Set the color of the handles.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the maximum extent of the handles around their position. Default is `5.0`.
+    pub fn radius(mut self, radius: impl Into<f32>) -> Self {
+        self.radius = radius.into();
+        self
+    }
+
+    /// Name of these handles.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
     }
 }
 
+/// A handle of [`DragHandles`] that is being dragged, see
+/// [`PlotUi::drag_handles`](super::PlotUi::drag_handles).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandleDrag {
+    /// Index of the handle in the values of the [`DragHandles`].
+    pub index: usize,
+
+    /// Where the handle was dragged to.
+    pub value: Value,
+}
+
 /// A set of arrows.
 pub struct Arrows {
     pub(super) origins: Values,
@@ -1048,10 +1489,11 @@ impl PlotItem for Arrows {
             });
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {
         self.origins
-            .generate_points(f64::NEG_INFINITY..=f64::INFINITY);
-        self.tips.generate_points(f64::NEG_INFINITY..=f64::INFINITY);
+            .generate_points(f64::NEG_INFINITY..=f64::INFINITY, AxisScale::Linear);
+        self.tips
+            .generate_points(f64::NEG_INFINITY..=f64::INFINITY, AxisScale::Linear);
     }
 
     fn name(&self) -> &str {
@@ -1187,7 +1629,7 @@ impl PlotItem for PlotImage {
         }
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
 
     fn name(&self) -> &str {
         self.name.as_str()
@@ -1346,7 +1788,7 @@ impl PlotItem for BarChart {
         }
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {
         // nothing to do
     }
 
@@ -1378,6 +1820,12 @@ impl PlotItem for BarChart {
         bounds
     }
 
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        rect_value_at_x(&self.bars, x, |bar| {
+            bar.base_offset.unwrap_or(0.0) + bar.value
+        })
+    }
+
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
         find_closest_rect(&self.bars, point, transform)
     }
@@ -1488,7 +1936,7 @@ impl PlotItem for BoxPlot {
         }
     }
 
-    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {
         // nothing to do
     }
 
@@ -1520,6 +1968,10 @@ impl PlotItem for BoxPlot {
         bounds
     }
 
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        rect_value_at_x(&self.boxes, x, |box_elem| box_elem.spread.median)
+    }
+
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
         find_closest_rect(&self.boxes, point, transform)
     }
@@ -1538,6 +1990,173 @@ impl PlotItem for BoxPlot {
     }
 }
 
+/// A candlestick chart, showing the open, high, low and close value of each period.
+pub struct CandleChart {
+    pub(super) candles: Vec<CandleElem>,
+    pub(super) up_color: Color32,
+    pub(super) down_color: Color32,
+    /// Base value and height of the area in which volume bars are shown
+    pub(super) volume_area: Option<(f64, f64)>,
+    /// The largest volume of all candles, used to scale the volume bars
+    pub(super) max_volume: f64,
+    pub(super) name: String,
+    /// A custom element formatter
+    pub(super) element_formatter: Option<Box<dyn Fn(&CandleElem, &CandleChart) -> String>>,
+    highlight: bool,
+}
+
+impl CandleChart {
+    /// Create a chart containing multiple `candles`. It defaults to vertically oriented elements.
+    pub fn new(candles: Vec<CandleElem>) -> Self {
+        let max_volume = candles
+            .iter()
+            .filter_map(|candle| candle.volume)
+            .fold(0.0, f64::max);
+        Self {
+            candles,
+            max_volume,
+            up_color: Color32::from_rgb(38, 166, 91),
+            down_color: Color32::from_rgb(230, 70, 70),
+            volume_area: None,
+            name: String::new(),
+            element_formatter: None,
+            highlight: false,
+        }
+    }
+
+    /// Color of candles that closed at or above their open value. Default is green.
+    /// This is the color that shows up in the legend.
+    pub fn up_color(mut self, color: impl Into<Color32>) -> Self {
+        self.up_color = color.into();
+        self
+    }
+
+    /// Color of candles that closed below their open value. Default is red.
+    pub fn down_color(mut self, color: impl Into<Color32>) -> Self {
+        self.down_color = color.into();
+        self
+    }
+
+    /// Show the volume of the candles as bars starting at the value `base`, e.g. below the lowest
+    /// candle. The bar of the largest volume extends `height` above `base`, the others are
+    /// scaled accordingly.
+    ///
+    /// Candles without a [`CandleElem::volume`] get no bar.
+    pub fn volume_area(mut self, base: f64, height: f64) -> Self {
+        self.volume_area = Some((base, height));
+        self
+    }
+
+    /// Name of this candle chart.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on. Multiple series may
+    /// share the same name, in which case they will also share an entry in the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set all elements to be in a vertical orientation.
+    /// Argument axis will be X and values will be on the Y axis.
+    pub fn vertical(mut self) -> Self {
+        for candle in &mut self.candles {
+            candle.orientation = Orientation::Vertical;
+        }
+        self
+    }
+
+    /// Set all elements to be in a horizontal orientation.
+    /// Argument axis will be Y and values will be on the X axis.
+    pub fn horizontal(mut self) -> Self {
+        for candle in &mut self.candles {
+            candle.orientation = Orientation::Horizontal;
+        }
+        self
+    }
+
+    /// Highlight all plot elements.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom way to format an element.
+    /// Can be used to display a set number of decimals or custom labels.
+    pub fn element_formatter(
+        mut self,
+        formatter: Box<dyn Fn(&CandleElem, &CandleChart) -> String>,
+    ) -> Self {
+        self.element_formatter = Some(formatter);
+        self
+    }
+}
+
+impl PlotItem for CandleChart {
+    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        for candle in &self.candles {
+            candle.add_shapes(self, transform, self.highlight, shapes);
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {
+        // nothing to do
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.up_color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for candle in &self.candles {
+            bounds.merge(&candle.bounds());
+            if let (Some((base, height)), Some(_)) = (self.volume_area, candle.volume) {
+                bounds.extend_with(&candle.point_at(candle.argument, base));
+                bounds.extend_with(&candle.point_at(candle.argument, base + height));
+            }
+        }
+        bounds
+    }
+
+    fn value_at_x(&self, x: f64) -> Option<f64> {
+        rect_value_at_x(&self.candles, x, |candle| candle.ohlc.close)
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        find_closest_rect(&self.candles, point, transform)
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        _: &LabelFormatter,
+    ) {
+        let candle = &self.candles[elem.index];
+
+        candle.add_shapes(self, plot.transform, true, shapes);
+        candle.add_rulers_and_text(self, plot, shapes);
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Helper functions
 
@@ -1653,11 +2272,17 @@ pub(super) fn rulers_at_value(
     label_formatter: &LabelFormatter,
 ) {
     let line_color = rulers_color(plot.ui);
-    if plot.show_x {
-        shapes.push(vertical_line(pointer, plot.transform, line_color));
-    }
-    if plot.show_y {
-        shapes.push(horizontal_line(pointer, plot.transform, line_color));
+    let polar = plot.transform.polar().is_some();
+    if polar {
+        let show = [plot.show_x, plot.show_y];
+        super::polar::rulers(pointer, plot.transform, show, line_color, shapes);
+    } else {
+        if plot.show_x {
+            shapes.push(vertical_line(pointer, plot.transform, line_color));
+        }
+        if plot.show_y {
+            shapes.push(horizontal_line(pointer, plot.transform, line_color));
+        }
     }
 
     let mut prefix = String::new();
@@ -1667,11 +2292,18 @@ pub(super) fn rulers_at_value(
     }
 
     let text = {
-        let scale = plot.transform.dvalue_dpos();
+        let scale = plot.transform.dvalue_dpos_at(&value);
         let x_decimals = ((-scale[0].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
         let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
         if let Some(custom_label) = label_formatter {
             custom_label(name, &value)
+        } else if polar {
+            let show = [plot.show_x, plot.show_y];
+            format!(
+                "{}{}",
+                prefix,
+                super::polar::format_value(&value, plot.transform, show)
+            )
         } else if plot.show_x && plot.show_y {
             format!(
                 "{}x = {:.*}\ny = {:.*}",
@@ -1698,6 +2330,25 @@ pub(super) fn rulers_at_value(
     ));
 }
 
+/// The value of the first vertical element whose width covers `x`, see [`PlotItem::value_at_x`].
+fn rect_value_at_x<'a, T>(
+    rects: impl IntoIterator<Item = &'a T>,
+    x: f64,
+    value: impl Fn(&T) -> f64,
+) -> Option<f64>
+where
+    T: 'a + RectElement,
+{
+    rects
+        .into_iter()
+        .find(|rect| {
+            rect.orientation() == Orientation::Vertical
+                && rect.bounds_min().x <= x
+                && x <= rect.bounds_max().x
+        })
+        .map(value)
+}
+
 fn find_closest_rect<'a, T>(
     rects: impl IntoIterator<Item = &'a T>,
     point: Pos2,
@@ -1717,3 +2368,121 @@ where
         })
         .min_by_key(|e| e.dist_sq.ord())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_at_x_of_series() {
+        let points = Points::new(Values::from_values(vec![
+            Value::new(0.0, 1.0),
+            Value::new(2.0, 3.0),
+        ]));
+        assert_eq!(points.value_at_x(0.8), Some(1.0));
+        assert_eq!(points.value_at_x(1.5), Some(3.0));
+
+        let band = Band::new(
+            Values::from_values(vec![Value::new(0.0, 0.0), Value::new(2.0, 2.0)]),
+            Values::from_values(vec![Value::new(0.0, 2.0), Value::new(2.0, 4.0)]),
+        );
+        assert_eq!(band.value_at_x(1.0), Some(2.0));
+        assert_eq!(band.value_at_x(3.0), None);
+
+        let histogram = Histogram::new(vec![0.5, 1.5, 1.6])
+            .binning(Binning::Width(1.0))
+            .step(true)
+            .build();
+        assert_eq!(histogram.value_at_x(0.2), Some(1.0));
+        assert_eq!(histogram.value_at_x(1.9), Some(2.0));
+        assert_eq!(histogram.value_at_x(5.0), None);
+
+        let horizontal = BarChart::new(vec![Bar::new(0.0, 1.0).horizontal()]);
+        assert_eq!(horizontal.value_at_x(0.0), None);
+    }
+
+    fn test_transform() -> ScreenTransform {
+        let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+        let bounds = PlotBounds {
+            min: [0.0, 0.0],
+            max: [10.0, 10.0],
+        };
+        ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2])
+    }
+
+    #[test]
+    fn band_mesh() {
+        let band = Band::new(
+            Values::from_values(vec![Value::new(0.0, 1.0), Value::new(5.0, 2.0)]),
+            Values::from_values(vec![
+                Value::new(0.0, 3.0),
+                Value::new(5.0, 4.0),
+                Value::new(9.0, 5.0),
+            ]),
+        )
+        .color(Color32::RED);
+
+        let transform = test_transform();
+        let mut shapes = vec![];
+        crate::__run_test_ui(|ui| {
+            shapes.clear();
+            band.get_shapes(ui, &transform, &mut shapes);
+        });
+
+        // One quad between the two pairs of values, the unpaired upper value is left out.
+        assert_eq!(shapes.len(), 1);
+        let mesh = match &shapes[0] {
+            Shape::Mesh(mesh) => mesh,
+            _ => panic!("expected a mesh"),
+        };
+        let positions: Vec<_> = mesh.vertices.iter().map(|vertex| vertex.pos).collect();
+        assert_eq!(
+            positions,
+            vec![
+                pos2(0.0, 90.0),
+                pos2(0.0, 70.0),
+                pos2(50.0, 80.0),
+                pos2(50.0, 60.0)
+            ]
+        );
+        assert_eq!(mesh.indices, vec![0, 1, 2, 1, 2, 3]);
+    }
+
+    #[test]
+    fn error_bar_whiskers() {
+        let points = Points::new(Values::from_values(vec![Value::new(5.0, 5.0)]))
+            .x_errors(vec![1.0])
+            .y_errors_asymmetric(vec![2.0], vec![3.0])
+            .color(Color32::RED);
+        assert_eq!(
+            points.error_bar(0, 0),
+            Some((Value::new(4.0, 5.0), Value::new(6.0, 5.0)))
+        );
+        assert_eq!(
+            points.error_bar(0, 1),
+            Some((Value::new(5.0, 3.0), Value::new(5.0, 8.0)))
+        );
+        assert_eq!(points.error_bar(1, 1), None);
+
+        let transform = test_transform();
+        let mut shapes = vec![];
+        crate::__run_test_ui(|ui| {
+            shapes.clear();
+            points.get_shapes(ui, &transform, &mut shapes);
+        });
+
+        // Each error bar is a whisker with a cap at both ends.
+        let segments: Vec<_> = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::LineSegment { points, .. } => Some(*points),
+                _ => None,
+            })
+            .collect();
+        let cap = ERROR_BAR_CAP_LENGTH / 2.0;
+        assert!(segments.contains(&[pos2(40.0, 50.0), pos2(60.0, 50.0)]));
+        assert!(segments.contains(&[pos2(40.0, 50.0 - cap), pos2(40.0, 50.0 + cap)]));
+        assert!(segments.contains(&[pos2(50.0, 70.0), pos2(50.0, 20.0)]));
+        assert!(segments.contains(&[pos2(50.0 - cap, 20.0), pos2(50.0 + cap, 20.0)]));
+    }
+}
@@ -0,0 +1,470 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::ops::RangeInclusive;
+
+use epaint::ahash::AHashSet;
+use epaint::Mesh;
+
+use crate::*;
+
+use super::{ClosestElem, LabelFormatter, PlotConfig, PlotGeometry, PlotItem};
+use crate::plot::{AxisScale, PlotBounds, ScreenTransform, Value};
+
+/// One slice of a [`PieChart`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieSlice {
+    /// Name of the slice, shown as its label and in the legend.
+    pub name: String,
+
+    /// Size of the slice, relative to the sum of all slices. Must not be negative.
+    pub value: f64,
+
+    /// Fill color. `Color32::TRANSPARENT` means a color will be auto-assigned.
+    pub color: Color32,
+}
+
+impl PieSlice {
+    pub fn new(value: impl Into<f64>) -> Self {
+        Self {
+            name: String::new(),
+            value: value.into(),
+            color: Color32::TRANSPARENT,
+        }
+    }
+
+    /// Name of this slice.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Fill color of this slice.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+}
+
+/// A pie chart, or a donut chart with [`Self::inner_radius`].
+///
+/// Each named slice gets its own entry in the [`Legend`](crate::plot::Legend). Hiding a slice
+/// there divides the pie among the other slices. Clicking a slice moves it out of the pie, see
+/// [`PlotUi::pie_chart`](crate::plot::PlotUi::pie_chart).
+///
+/// The pie is drawn in plot coordinates, so use [`Plot::data_aspect`](crate::plot::Plot::data_aspect)
+/// to keep it round.
+pub struct PieChart {
+    pub(crate) id: Id,
+    pub(crate) slices: Vec<PieSlice>,
+    center: Value,
+    radius: f64,
+    inner_radius: f64,
+    start_angle: f64,
+    explode_distance: f64,
+    show_labels: bool,
+}
+
+impl PieChart {
+    /// Create a pie chart. The `id_source` must be unique among the pie charts of the plot, as
+    /// it is used to remember the exploded slice.
+    pub fn new(id_source: impl std::hash::Hash, slices: Vec<PieSlice>) -> Self {
+        Self {
+            id: Id::new(id_source),
+            slices,
+            center: Value::new(0.0, 0.0),
+            radius: 1.0,
+            inner_radius: 0.0,
+            start_angle: FRAC_PI_2,
+            explode_distance: 0.1,
+            show_labels: true,
+        }
+    }
+
+    /// Center of the pie in plot coordinates. Default is the origin.
+    pub fn center(mut self, center: Value) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Radius of the pie in plot coordinates. Default is `1.0`.
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Turn the pie into a donut with a hole of the given fraction of the radius, e.g. `0.5`.
+    /// Default is `0.0`.
+    pub fn inner_radius(mut self, fraction: f64) -> Self {
+        self.inner_radius = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Angle in radians where the first slice starts, counter-clockwise from the positive x axis.
+    /// The slices follow each other clockwise. Default is `PI / 2`, at the top.
+    pub fn start_angle(mut self, angle: f64) -> Self {
+        self.start_angle = angle;
+        self
+    }
+
+    /// How far the exploded slice is moved out of the pie, as a fraction of the radius.
+    /// Default is `0.1`.
+    pub fn explode_distance(mut self, fraction: f64) -> Self {
+        self.explode_distance = fraction;
+        self
+    }
+
+    /// Whether to show the names of the slices on them. Default is `true`.
+    pub fn show_labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
+        self
+    }
+
+    /// One plot item per slice, with the `hidden` slices left out of the pie.
+    pub(crate) fn slice_items(
+        self,
+        hidden: &AHashSet<String>,
+        exploded: Option<usize>,
+    ) -> Vec<PieSliceItem> {
+        let is_shown = |slice: &PieSlice| slice.value > 0.0 && !hidden.contains(&slice.name);
+        let total: f64 = self
+            .slices
+            .iter()
+            .filter(|slice| is_shown(slice))
+            .map(|slice| slice.value)
+            .sum();
+
+        let mut start = self.start_angle;
+        self.slices
+            .into_iter()
+            .enumerate()
+            .map(|(index, slice)| {
+                let fraction = if is_shown(&slice) {
+                    slice.value / total
+                } else {
+                    0.0
+                };
+                let item = PieSliceItem {
+                    slice,
+                    fraction,
+                    center: self.center,
+                    radius: self.radius,
+                    inner_radius: self.inner_radius * self.radius,
+                    start,
+                    sweep: fraction * TAU,
+                    explode_distance: self.explode_distance * self.radius,
+                    exploded: exploded == Some(index),
+                    show_label: self.show_labels,
+                    highlight: false,
+                };
+                start -= item.sweep;
+                item
+            })
+            .collect()
+    }
+}
+
+/// A single slice of a [`PieChart`] in the plot.
+pub(crate) struct PieSliceItem {
+    slice: PieSlice,
+    fraction: f64,
+    center: Value,
+    radius: f64,
+    inner_radius: f64,
+    /// Angle of the first edge, counter-clockwise from the positive x axis.
+    start: f64,
+    /// Clockwise angle from the first to the second edge.
+    sweep: f64,
+    explode_distance: f64,
+    pub(crate) exploded: bool,
+    show_label: bool,
+    highlight: bool,
+}
+
+impl PieSliceItem {
+    /// Center of the pie, moved outwards if the slice is exploded.
+    fn origin(&self) -> Value {
+        if self.exploded {
+            self.point_at(self.explode_distance, self.mid_angle())
+        } else {
+            self.center
+        }
+    }
+
+    fn mid_angle(&self) -> f64 {
+        self.start - self.sweep / 2.0
+    }
+
+    /// The name, value and share of the slice, or what the `label_formatter` makes of
+    /// the name and the value (as x) and fraction (as y) of the slice.
+    fn hover_text(&self, label_formatter: &LabelFormatter) -> String {
+        if let Some(formatter) = label_formatter {
+            return formatter(
+                &self.slice.name,
+                &Value::new(self.slice.value, self.fraction),
+            );
+        }
+        let mut text = self.slice.name.clone();
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&format!(
+            "{} ({:.1}%)",
+            emath::round_to_decimals(self.slice.value, 6),
+            100.0 * self.fraction
+        ));
+        text
+    }
+
+    fn point_at(&self, radius: f64, angle: f64) -> Value {
+        Value::new(
+            self.center.x + radius * angle.cos(),
+            self.center.y + radius * angle.sin(),
+        )
+    }
+
+    /// The point at `radius` and `angle` around the origin of the slice, on the screen.
+    fn screen_point(&self, transform: &ScreenTransform, radius: f64, angle: f64) -> Pos2 {
+        let origin = self.origin();
+        transform.position_from_value(&Value::new(
+            origin.x + radius * angle.cos(),
+            origin.y + radius * angle.sin(),
+        ))
+    }
+
+    /// Whether the value lies on the slice.
+    pub(crate) fn contains(&self, value: Value) -> bool {
+        if self.sweep <= 0.0 {
+            return false;
+        }
+        let origin = self.origin();
+        let (dx, dy) = (value.x - origin.x, value.y - origin.y);
+        let distance = dx.hypot(dy);
+        if distance < self.inner_radius || distance > self.radius {
+            return false;
+        }
+        // Clockwise angle from the first edge.
+        let angle = (self.start - dy.atan2(dx)).rem_euclid(TAU);
+        angle <= self.sweep
+    }
+
+    /// Outline of the slice on the screen: the outer arc, then the inner arc backwards.
+    fn outline(&self, transform: &ScreenTransform) -> Vec<Pos2> {
+        // About one point per 3 degrees.
+        let steps = (self.sweep / TAU * 120.0).ceil().max(1.0) as usize;
+        let angles = (0..=steps).map(|i| self.start - self.sweep * i as f64 / steps as f64);
+        let mut points: Vec<Pos2> = angles
+            .clone()
+            .map(|angle| self.screen_point(transform, self.radius, angle))
+            .collect();
+        if self.inner_radius > 0.0 {
+            points.extend(
+                angles
+                    .rev()
+                    .map(|angle| self.screen_point(transform, self.inner_radius, angle)),
+            );
+        } else {
+            points.push(transform.position_from_value(&self.origin()));
+        }
+        points
+    }
+
+    fn add_shapes(
+        &self,
+        ui: &Ui,
+        transform: &ScreenTransform,
+        highlighted: bool,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let outline = self.outline(transform);
+        let color = self.slice.color;
+
+        let mut mesh = Mesh::default();
+        if self.inner_radius > 0.0 {
+            // A strip of quads between the outer and the inner arc.
+            let n = outline.len() / 2;
+            for i in 0..n {
+                mesh.colored_vertex(outline[i], color);
+                mesh.colored_vertex(outline[outline.len() - 1 - i], color);
+            }
+            for i in 0..n as u32 - 1 {
+                mesh.add_triangle(2 * i, 2 * i + 1, 2 * i + 2);
+                mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+            }
+        } else {
+            // A fan around the origin, which is the last point.
+            for pos in &outline {
+                mesh.colored_vertex(*pos, color);
+            }
+            let origin = outline.len() as u32 - 1;
+            for i in 0..origin - 1 {
+                mesh.add_triangle(origin, i, i + 1);
+            }
+        }
+        shapes.push(Shape::Mesh(mesh));
+
+        let stroke = if highlighted {
+            Stroke::new(2.0, ui.visuals().strong_text_color())
+        } else {
+            Stroke::new(1.0, ui.visuals().extreme_bg_color)
+        };
+        shapes.push(Shape::closed_line(outline, stroke));
+    }
+
+    /// Where to put text about the slice: just outside of it, aligned away from it.
+    fn outer_anchor(&self, transform: &ScreenTransform) -> (Pos2, Align2) {
+        let angle = self.mid_angle();
+        let pos = self.screen_point(transform, 1.08 * self.radius, angle);
+        let direction = pos - transform.position_from_value(&self.origin());
+        let align = |d: f32| {
+            if d > 0.3 {
+                Align::Min
+            } else if d < -0.3 {
+                Align::Max
+            } else {
+                Align::Center
+            }
+        };
+        let direction = direction.normalized();
+        (pos, Align2([align(direction.x), align(direction.y)]))
+    }
+}
+
+impl PlotItem for PieSliceItem {
+    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        if self.sweep <= 0.0 {
+            return;
+        }
+        self.add_shapes(ui, transform, self.highlight, shapes);
+
+        if self.show_label && !self.slice.name.is_empty() {
+            // In the middle of the slice, in a color that stands out against it.
+            let radius = (self.radius + self.inner_radius) / 2.0;
+            let radius = if self.inner_radius > 0.0 {
+                radius
+            } else {
+                0.65 * self.radius
+            };
+            let pos = self.screen_point(transform, radius, self.mid_angle());
+            let text_color = if Rgba::from(self.slice.color).intensity() > 0.5 {
+                Color32::BLACK
+            } else {
+                Color32::WHITE
+            };
+            shapes.push(Shape::text(
+                &*ui.fonts(),
+                pos,
+                Align2::CENTER_CENTER,
+                &self.slice.name,
+                TextStyle::Small.resolve(ui.style()),
+                text_color,
+            ));
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>, _x_scale: AxisScale) {}
+
+    fn name(&self) -> &str {
+        self.slice.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.slice.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        // The whole pie, so hiding or exploding slices doesn't move it.
+        let reach = self.radius + self.explode_distance.max(0.0);
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.extend_with(&Value::new(self.center.x - reach, self.center.y - reach));
+        bounds.extend_with(&Value::new(self.center.x + reach, self.center.y + reach));
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        self.contains(transform.value_from_position(point))
+            .then(|| ClosestElem {
+                index: 0,
+                dist_sq: 0.0,
+            })
+    }
+
+    fn on_hover(
+        &self,
+        _elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        self.add_shapes(plot.ui, plot.transform, true, shapes);
+
+        let (pos, align) = self.outer_anchor(plot.transform);
+        let text = self.hover_text(label_formatter);
+        shapes.push(Shape::text(
+            &*plot.ui.fonts(),
+            pos,
+            align,
+            text,
+            TextStyle::Body.resolve(plot.ui.style()),
+            plot.ui.visuals().text_color(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pie_slices() {
+        let pie = PieChart::new(
+            "pie",
+            vec![
+                PieSlice::new(1.0).name("a"),
+                PieSlice::new(1.0).name("b"),
+                PieSlice::new(2.0).name("c"),
+            ],
+        );
+        let mut hidden = AHashSet::default();
+        hidden.insert("b".to_owned());
+        let items = pie.slice_items(&hidden, None);
+        let fractions: Vec<f64> = items.iter().map(|item| item.fraction).collect();
+        assert_eq!(fractions, vec![1.0 / 3.0, 0.0, 2.0 / 3.0]);
+
+        // The first slice starts at the top and goes clockwise, to the right side.
+        assert!(items[0].contains(Value::new(0.5, 0.5)));
+        assert!(!items[0].contains(Value::new(-0.5, 0.5)));
+        assert!(items[2].contains(Value::new(-0.5, 0.5)));
+        assert!(!items[1].contains(Value::new(0.5, 0.5)));
+        assert!(!items[2].contains(Value::new(2.0, 0.0)));
+    }
+
+    #[test]
+    fn pie_slice_hover_text() {
+        let pie = PieChart::new(
+            "pie",
+            vec![
+                PieSlice::new(0.1 + 0.2).name("a"),
+                PieSlice::new(0.6).name("b"),
+            ],
+        );
+        let items = pie.slice_items(&AHashSet::default(), None);
+        assert_eq!(items[0].hover_text(&None), "a\n0.3 (33.3%)");
+
+        let formatter: LabelFormatter = Some(Box::new(|name, value| {
+            format!("{}: {:.0}%", name, 100.0 * value.y)
+        }));
+        assert_eq!(items[1].hover_text(&formatter), "b: 67%");
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use epaint::mutex::Mutex;
+
+use super::{Value, Values};
+
+/// A ring buffer of values for plotting live data, e.g. telemetry.
+///
+/// Values can be appended from any thread through a clone of the stream. Each frame, show them
+/// with [`Values::from_stream`] or [`Values::from_stream_window`], which share the buffer
+/// instead of copying the values. Once the buffer is full, the oldest values are dropped.
+///
+/// The x values must be in ascending order, e.g. timestamps.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Line, Plot, Value, ValueStream, Values};
+/// let stream = ValueStream::new(10_000);
+///
+/// // On the data thread:
+/// let sender = stream.clone();
+/// for i in 0..100 {
+///     let t = i as f64 * 0.1;
+///     sender.push(Value::new(t, t.sin()));
+/// }
+///
+/// // Every frame, show the last five seconds:
+/// Plot::new("telemetry").show(ui, |plot_ui| {
+///     plot_ui.line(Line::new(Values::from_stream_window(&stream, 5.0)));
+/// });
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct ValueStream {
+    buffer: Arc<Mutex<StreamBuffer>>,
+}
+
+struct StreamBuffer {
+    /// The live values are `values[start..]`. Older values are removed in batches, so
+    /// dropping one value at a time stays cheap.
+    values: Arc<Vec<Value>>,
+    start: usize,
+    capacity: usize,
+}
+
+impl StreamBuffer {
+    fn live(&self) -> &[Value] {
+        &self.values[self.start..]
+    }
+}
+
+impl ValueStream {
+    /// Create a stream keeping at most `capacity` values.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            buffer: Arc::new(Mutex::new(StreamBuffer {
+                values: Arc::new(Vec::with_capacity(capacity)),
+                start: 0,
+                capacity,
+            })),
+        }
+    }
+
+    /// Append a value, dropping the oldest one if the stream is full.
+    ///
+    /// If the values of a previous frame are still shown, they are copied first.
+    pub fn push(&self, value: Value) {
+        self.extend(std::iter::once(value));
+    }
+
+    /// Append values, dropping the oldest ones if the stream is full.
+    pub fn extend(&self, values: impl IntoIterator<Item = Value>) {
+        let mut buffer = self.buffer.lock();
+        let StreamBuffer {
+            values: shared,
+            start,
+            capacity,
+        } = &mut *buffer;
+
+        let values_mut = Arc::make_mut(shared);
+        for value in values {
+            values_mut.push(value);
+            if values_mut.len() - *start > *capacity {
+                *start += 1;
+            }
+        }
+        if *start >= *capacity {
+            values_mut.drain(..*start);
+            *start = 0;
+        }
+    }
+
+    /// Remove all values.
+    pub fn clear(&self) {
+        let mut buffer = self.buffer.lock();
+        buffer.values = Arc::new(Vec::with_capacity(buffer.capacity));
+        buffer.start = 0;
+    }
+
+    /// The number of values in the stream.
+    pub fn len(&self) -> usize {
+        self.buffer.lock().live().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The most values the stream keeps.
+    pub fn capacity(&self) -> usize {
+        self.buffer.lock().capacity
+    }
+
+    /// The newest value.
+    pub fn latest(&self) -> Option<Value> {
+        self.buffer.lock().live().last().copied()
+    }
+
+    /// The values with an x value at most `x_window` before the newest one, or all of them.
+    pub(super) fn values(&self, x_window: Option<f64>) -> Values {
+        let buffer = self.buffer.lock();
+        let live = buffer.live();
+        let skipped = match (x_window, live.last()) {
+            (Some(x_window), Some(latest)) => {
+                let min_x = latest.x - x_window;
+                live.partition_point(|value| value.x < min_x)
+            }
+            _ => 0,
+        };
+        let end = buffer.values.len();
+        Values::from_shared(buffer.values.clone(), buffer.start + skipped..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_drops_oldest_values() {
+        let stream = ValueStream::new(3);
+        let shown = Values::from_stream(&stream);
+        stream.extend((0..5).map(|i| Value::new(i, i)));
+        assert!(shown.values.is_empty());
+        assert_eq!(stream.len(), 3);
+
+        let all = Values::from_stream(&stream);
+        let xs: Vec<f64> = all.values.iter().map(|value| value.x).collect();
+        assert_eq!(xs, vec![2.0, 3.0, 4.0]);
+
+        // Appending while the values are shown doesn't change them.
+        stream.push(Value::new(5, 5));
+        assert_eq!(all.values.len(), 3);
+        assert_eq!(all.values[0].x, 2.0);
+
+        let window = Values::from_stream_window(&stream, 1.0);
+        let xs: Vec<f64> = window.values.iter().map(|value| value.x).collect();
+        assert_eq!(xs, vec![4.0, 5.0]);
+        assert_eq!(stream.latest(), Some(Value::new(5, 5)));
+    }
+}
@@ -1,7 +1,10 @@
+use epaint::util::FloatOrd;
 use epaint::{Pos2, Shape, Stroke, Vec2};
-use std::ops::{Bound, RangeBounds, RangeInclusive};
+use std::ops::{Bound, Deref, Range, RangeBounds, RangeInclusive};
+use std::sync::Arc;
 
-use crate::plot::transform::PlotBounds;
+use super::ValueStream;
+use crate::plot::transform::{AxisScale, PlotBounds, ScreenTransform};
 
 /// A value in the value-space of the plot.
 ///
@@ -140,17 +143,53 @@ impl Default for Orientation {
 
 // ----------------------------------------------------------------------------
 
+/// The values of a series: either owned, or a part of the values shared with a
+/// [`ValueStream`](super::ValueStream).
+pub(super) enum Storage {
+    Owned(Vec<Value>),
+    Shared(Arc<Vec<Value>>, Range<usize>),
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl Deref for Storage {
+    type Target = [Value];
+
+    fn deref(&self) -> &[Value] {
+        match self {
+            Self::Owned(values) => values,
+            Self::Shared(values, range) => &values[range.clone()],
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Values {
-    pub(super) values: Vec<Value>,
+    pub(super) values: Storage,
     generator: Option<ExplicitGenerator>,
+    /// Whether the x values are known to be in ascending order.
+    sorted_x: bool,
 }
 
 impl Values {
     pub fn from_values(values: Vec<Value>) -> Self {
         Self {
-            values,
+            values: Storage::Owned(values),
+            generator: None,
+            sorted_x: false,
+        }
+    }
+
+    /// The values of `range` in `values`, which are sorted by x.
+    pub(super) fn from_shared(values: Arc<Vec<Value>>, range: Range<usize>) -> Self {
+        Self {
+            values: Storage::Shared(values, range),
             generator: None,
+            sorted_x: true,
         }
     }
 
@@ -181,8 +220,9 @@ impl Values {
         };
 
         Self {
-            values: Vec::new(),
+            values: Storage::default(),
             generator: Some(generator),
+            sorted_x: true,
         }
     }
 
@@ -216,6 +256,20 @@ impl Values {
         Self::from_values_iter(values)
     }
 
+    /// All values of a [`ValueStream`]. They are shared with the stream, not copied.
+    pub fn from_stream(stream: &ValueStream) -> Self {
+        stream.values(None)
+    }
+
+    /// The values of a [`ValueStream`] with an x value at most `x_window` before the newest one,
+    /// e.g. the last ten seconds of a stream with timestamps in seconds. Like with
+    /// [`Self::from_stream`], they are shared with the stream.
+    ///
+    /// The plot's automatic bounds only include these values, so the plot follows the stream.
+    pub fn from_stream_window(stream: &ValueStream, x_window: f64) -> Self {
+        stream.values(Some(x_window))
+    }
+
     /// From a series of y-values.
     /// The x-values will be the indices of these values
     pub fn from_ys_f32(ys: &[f32]) -> Self {
@@ -227,7 +281,20 @@ impl Values {
                 y: y as f64,
             })
             .collect();
-        Self::from_values(values)
+        Self::from_values(values).sorted_x(true)
+    }
+
+    /// Promise that the x values are in ascending order, e.g. for time series.
+    ///
+    /// This lets the plot skip the values outside of the visible x range and reduce long series
+    /// to a few points per pixel column before painting them, which makes plotting millions of
+    /// values fast. If the values are not actually sorted, parts of the series may not be shown.
+    ///
+    /// Series created with [`Self::from_explicit_callback`] and [`Self::from_ys_f32`] are
+    /// always sorted.
+    pub fn sorted_x(mut self, sorted: bool) -> Self {
+        self.sorted_x = sorted;
+        self
     }
 
     /// Returns true if there are no data points available and there is no function to generate any.
@@ -235,22 +302,133 @@ impl Values {
         self.generator.is_none() && self.values.is_empty()
     }
 
-    /// If initialized with a generator function, this will generate `n` evenly spaced points in the
-    /// given range.
-    pub(super) fn generate_points(&mut self, x_range: RangeInclusive<f64>) {
+    /// If initialized with a generator function, this will generate `n` points in the given range,
+    /// evenly spaced on an x axis with the given scale.
+    pub(super) fn generate_points(&mut self, x_range: RangeInclusive<f64>, x_scale: AxisScale) {
         if let Some(generator) = self.generator.take() {
             if let Some(intersection) = Self::range_intersection(&x_range, &generator.x_range) {
-                let increment =
-                    (intersection.end() - intersection.start()) / (generator.points - 1) as f64;
-                self.values = (0..generator.points)
-                    .map(|i| {
-                        let x = intersection.start() + i as f64 * increment;
-                        let y = (generator.function)(x);
-                        Value { x, y }
-                    })
-                    .collect();
+                let start = x_scale.scale(*intersection.start());
+                let end = x_scale.scale(*intersection.end());
+                let increment = (end - start) / (generator.points - 1) as f64;
+                self.values = Storage::Owned(
+                    (0..generator.points)
+                        .map(|i| {
+                            let x = x_scale.unscale(start + i as f64 * increment);
+                            let y = (generator.function)(x);
+                            Value { x, y }
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    /// The indices of the values that may be visible in the given x range, including one value
+    /// on either side so lines continue to the edge of the plot.
+    ///
+    /// Unless the values are sorted by x, this is all of them.
+    pub(super) fn visible_indices(&self, x_range: &RangeInclusive<f64>) -> Range<usize> {
+        let len = self.values.len();
+        if !self.sorted_x {
+            return 0..len;
+        }
+
+        let start = self
+            .values
+            .partition_point(|value| value.x < *x_range.start());
+        let end = self
+            .values
+            .partition_point(|value| value.x <= *x_range.end());
+        start.saturating_sub(1)..(end + 1).min(len)
+    }
+
+    /// The indices of the values that may be visible in the plot of `transform`,
+    /// see [`Self::visible_indices`].
+    ///
+    /// In a polar plot the x values are angles, which aren't limited by the bounds of the plot.
+    pub(super) fn visible_indices_in(&self, transform: &ScreenTransform) -> Range<usize> {
+        if transform.polar().is_some() {
+            0..self.values.len()
+        } else {
+            self.visible_indices(&transform.bounds().range_x())
+        }
+    }
+
+    /// The y value of the line through the values at the given `x`, interpolated linearly
+    /// between the two values around it. `None` if the line doesn't reach `x`.
+    ///
+    /// If the values aren't sorted by x, the first segment of the line that spans `x` is used.
+    pub(super) fn interpolate_y(&self, x: f64) -> Option<f64> {
+        let segment = if self.sorted_x {
+            let end = self.values.partition_point(|value| value.x < x);
+            if end == self.values.len() {
+                return None;
+            }
+            if self.values[end].x == x {
+                return Some(self.values[end].y);
+            }
+            self.values.get(end.checked_sub(1)?..=end)?
+        } else {
+            self.values.windows(2).find(|pair| {
+                (pair[0].x <= x && x <= pair[1].x) || (pair[1].x <= x && x <= pair[0].x)
+            })?
+        };
+
+        let (a, b) = (segment[0], segment[1]);
+        if a.x == b.x {
+            return Some(a.y);
+        }
+        Some(a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x))
+    }
+
+    /// The y value of the value with the x closest to the given `x`. `None` if there are no values.
+    pub(super) fn nearest_y(&self, x: f64) -> Option<f64> {
+        let candidates = if self.sorted_x {
+            let index = self.values.partition_point(|value| value.x < x);
+            &self.values[index.saturating_sub(1)..(index + 1).min(self.values.len())]
+        } else {
+            &self.values[..]
+        };
+        candidates
+            .iter()
+            .min_by_key(|value| (value.x - x).abs().ord())
+            .map(|value| value.y)
+    }
+
+    /// The screen positions of the visible part of the series, reduced to at most four points
+    /// per pixel column for long series with sorted x values.
+    ///
+    /// For each column, the first and last point and those with the smallest and largest y are
+    /// kept, in their original order. A line through them looks the same as one through all
+    /// of the values. Polar plots are never decimated, as the columns don't follow the x values.
+    pub(super) fn decimated_line(&self, transform: &ScreenTransform) -> Vec<Pos2> {
+        let visible = &self.values[self.visible_indices_in(transform)];
+        let positions = visible
+            .iter()
+            .map(|value| transform.position_from_value(value));
+
+        let max_points = 4 * transform.frame().width().ceil() as usize;
+        if !self.sorted_x || transform.polar().is_some() || visible.len() <= max_points {
+            return positions.collect();
+        }
+
+        let mut line = Vec::with_capacity(max_points + 4);
+        let mut column: Option<PixelColumn> = None;
+        for (index, pos) in positions.enumerate() {
+            match &mut column {
+                Some(column) if column.x == pos.x.floor() => column.add(index, pos),
+                _ => {
+                    if let Some(column) = column.take() {
+                        column.flush(&mut line);
+                    }
+                    column = Some(PixelColumn::new(index, pos));
+                }
             }
         }
+        if let Some(column) = column {
+            column.flush(&mut line);
+        }
+        line
     }
 
     /// Returns the intersection of two ranges if they intersect.
@@ -272,7 +450,7 @@ impl Values {
             }
         } else {
             let mut bounds = PlotBounds::NOTHING;
-            for value in &self.values {
+            for value in self.values.iter() {
                 bounds.extend_with(value);
             }
             bounds
@@ -280,6 +458,50 @@ impl Values {
     }
 }
 
+/// The points of a line that fall into one pixel column, see [`Values::decimated_line`].
+struct PixelColumn {
+    x: f32,
+    first: (usize, Pos2),
+    min: (usize, Pos2),
+    max: (usize, Pos2),
+    last: (usize, Pos2),
+}
+
+impl PixelColumn {
+    fn new(index: usize, pos: Pos2) -> Self {
+        Self {
+            x: pos.x.floor(),
+            first: (index, pos),
+            min: (index, pos),
+            max: (index, pos),
+            last: (index, pos),
+        }
+    }
+
+    fn add(&mut self, index: usize, pos: Pos2) {
+        if pos.y < self.min.1.y {
+            self.min = (index, pos);
+        }
+        if pos.y > self.max.1.y {
+            self.max = (index, pos);
+        }
+        self.last = (index, pos);
+    }
+
+    fn flush(self, line: &mut Vec<Pos2>) {
+        let mut points = [self.first, self.min, self.max, self.last];
+        points.sort_by_key(|(index, _)| *index);
+
+        let mut previous = None;
+        for (index, pos) in points {
+            if previous != Some(index) {
+                line.push(pos);
+                previous = Some(index);
+            }
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -365,3 +587,70 @@ pub(crate) struct ClosestElem {
     /// Squared distance from the mouse cursor (needed to compare against other PlotItems, which might be nearer)
     pub dist_sq: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use epaint::{pos2, Rect};
+
+    #[test]
+    fn decimated_line_keeps_extremes_per_column() {
+        let frame = Rect::from_min_size(pos2(0.0, 0.0), epaint::vec2(10.0, 100.0));
+        let bounds = PlotBounds {
+            min: [0.0, -1.0],
+            max: [10.0, 1.0],
+        };
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2]);
+
+        let mut values = Values::from_explicit_callback(|x| (100.0 * x).sin(), -5.0..15.0, 10_000);
+        values.generate_points(-5.0..=15.0, AxisScale::Linear);
+
+        let visible = values.visible_indices(&(0.0..=10.0));
+        assert!(values.values[visible.start].x < 0.0);
+        assert!(values.values[visible.end - 1].x > 10.0);
+
+        let line = values.decimated_line(&transform);
+        assert!(line.len() <= 4 * 12);
+        let (min_y, max_y) = line
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), pos| {
+                (min.min(pos.y), max.max(pos.y))
+            });
+        assert!(min_y < 1.0 && max_y > 99.0);
+    }
+
+    #[test]
+    fn polar_line_keeps_all_angles() {
+        let frame = Rect::from_min_size(pos2(0.0, 0.0), epaint::vec2(10.0, 10.0));
+        let bounds = PlotBounds {
+            min: [-1.0, -1.0],
+            max: [1.0, 1.0],
+        };
+        let transform = ScreenTransform::new(frame, bounds, false, false, [AxisScale::Linear; 2])
+            .with_polar(Some(Default::default()));
+
+        let mut values = Values::from_explicit_callback(|_| 1.0, 0.0..360.0, 1000);
+        values.generate_points(0.0..=360.0, AxisScale::Linear);
+        assert!(values.sorted_x);
+
+        assert_eq!(values.visible_indices_in(&transform), 0..1000);
+        assert_eq!(values.decimated_line(&transform).len(), 1000);
+    }
+
+    #[test]
+    fn interpolate_y_between_values() {
+        let values = Values::from_values(vec![
+            Value::new(0.0, 0.0),
+            Value::new(1.0, 2.0),
+            Value::new(3.0, 0.0),
+        ]);
+        assert_eq!(values.interpolate_y(0.5), Some(1.0));
+        assert_eq!(values.interpolate_y(2.0), Some(1.0));
+        assert_eq!(values.interpolate_y(4.0), None);
+
+        let sorted = values.sorted_x(true);
+        assert_eq!(sorted.interpolate_y(1.0), Some(2.0));
+        assert_eq!(sorted.interpolate_y(2.5), Some(0.5));
+        assert_eq!(sorted.interpolate_y(-1.0), None);
+    }
+}
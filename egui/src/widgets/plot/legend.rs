@@ -66,6 +66,12 @@ impl Legend {
     }
 }
 
+/// Space between the plot frame and the legend.
+const LEGEND_PADDING: f32 = 4.0;
+
+/// Space between the legend frame and its entries.
+const LEGEND_MARGIN: Vec2 = vec2(8.0, 4.0);
+
 #[derive(Clone)]
 struct LegendEntry {
     color: Color32,
@@ -82,73 +88,78 @@ impl LegendEntry {
         }
     }
 
-    fn ui(&mut self, ui: &mut Ui, text: String, text_style: &TextStyle) -> Response {
-        let Self {
-            color,
-            checked,
-            hovered,
-        } = self;
-
-        let font_id = text_style.resolve(ui.style());
-
-        let galley = ui
-            .fonts()
-            .layout_delayed_color(text, font_id, f32::INFINITY);
-
+    /// The size of an entry: a round checkbox as high as the text, next to the text.
+    fn size(galley: &Galley) -> Vec2 {
         let icon_size = galley.size().y;
         let icon_spacing = icon_size / 5.0;
-        let total_extra = vec2(icon_size + icon_spacing, 0.0);
-
-        let desired_size = total_extra + galley.size();
-        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
-
-        response
-            .widget_info(|| WidgetInfo::selected(WidgetType::Checkbox, *checked, galley.text()));
-
-        let visuals = ui.style().interact(&response);
-        let label_on_the_left = ui.layout().horizontal_placement() == Align::RIGHT;
+        vec2(icon_size + icon_spacing, 0.0) + galley.size()
+    }
 
-        let icon_position_x = if label_on_the_left {
-            rect.right() - icon_size / 2.0
+    /// The center of the checkbox and the left top of the text in the `rect` of an entry.
+    fn layout(rect: Rect, galley: &Galley, label_on_the_left: bool) -> (Pos2, Pos2) {
+        let icon_size = galley.size().y;
+        let (icon_x, text_x) = if label_on_the_left {
+            (rect.right() - icon_size / 2.0, rect.left())
         } else {
-            rect.left() + icon_size / 2.0
+            (
+                rect.left() + icon_size / 2.0,
+                rect.right() - galley.size().x,
+            )
         };
-        let icon_position = pos2(icon_position_x, rect.center().y);
-        let icon_rect = Rect::from_center_size(icon_position, vec2(icon_size, icon_size));
-
-        let painter = ui.painter();
+        (
+            pos2(icon_x, rect.center().y),
+            pos2(text_x, rect.center().y - 0.5 * galley.size().y),
+        )
+    }
 
-        painter.add(epaint::CircleShape {
-            center: icon_rect.center(),
+    /// The shapes of the checkbox, which is filled with the color of the entry if checked.
+    fn icon_shapes(
+        &self,
+        ui: &Ui,
+        center: Pos2,
+        icon_size: f32,
+        visuals: &style::WidgetVisuals,
+    ) -> Vec<Shape> {
+        let mut shapes = vec![Shape::Circle(epaint::CircleShape {
+            center,
             radius: icon_size * 0.5,
             fill: visuals.bg_fill,
             stroke: visuals.bg_stroke,
-        });
-
-        if *checked {
-            let fill = if *color == Color32::TRANSPARENT {
+        })];
+        if self.checked {
+            let fill = if self.color == Color32::TRANSPARENT {
                 ui.visuals().noninteractive().fg_stroke.color
             } else {
-                *color
+                self.color
             };
-            painter.add(epaint::Shape::circle_filled(
-                icon_rect.center(),
-                icon_size * 0.4,
-                fill,
-            ));
+            shapes.push(Shape::circle_filled(center, icon_size * 0.4, fill));
         }
+        shapes
+    }
 
-        let text_position_x = if label_on_the_left {
-            rect.right() - icon_size - icon_spacing - galley.size().x
-        } else {
-            rect.left() + icon_size + icon_spacing
-        };
+    fn ui(&mut self, ui: &mut Ui, text: String, text_style: &TextStyle) -> Response {
+        let font_id = text_style.resolve(ui.style());
+
+        let galley = ui
+            .fonts()
+            .layout_delayed_color(text, font_id, f32::INFINITY);
+
+        let (rect, response) = ui.allocate_exact_size(Self::size(&galley), Sense::click());
+
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::Checkbox, self.checked, galley.text())
+        });
 
-        let text_position = pos2(text_position_x, rect.center().y - 0.5 * galley.size().y);
+        let visuals = ui.style().interact(&response);
+        let label_on_the_left = ui.layout().horizontal_placement() == Align::RIGHT;
+        let (icon_center, text_position) = Self::layout(rect, &galley, label_on_the_left);
+
+        let painter = ui.painter();
+        painter.extend(self.icon_shapes(ui, icon_center, galley.size().y, visuals));
         painter.galley_with_color(text_position, galley, visuals.text_color());
 
-        *checked ^= response.clicked_by(PointerButton::Primary);
-        *hovered = response.hovered();
+        self.checked ^= response.clicked_by(PointerButton::Primary);
+        self.hovered = response.hovered();
 
         response
     }
@@ -164,17 +175,16 @@ pub(super) struct LegendWidget {
 impl LegendWidget {
     /// Create a new legend from items, the names of items that are hidden and the style of the
     /// text. Returns `None` if the legend has no entries.
-    pub(super) fn try_new(
+    pub(super) fn try_new<'a>(
         rect: Rect,
         config: Legend,
-        items: &[Box<dyn PlotItem>],
+        items: impl Iterator<Item = &'a dyn PlotItem>,
         hidden_items: &AHashSet<String>,
     ) -> Option<Self> {
         // Collect the legend entries. If multiple items have the same name, they share a
         // checkbox. If their colors don't match, we pick a neutral color for the checkbox.
         let mut entries: BTreeMap<String, LegendEntry> = BTreeMap::new();
         items
-            .iter()
             .filter(|item| !item.name().is_empty())
             .for_each(|item| {
                 entries
@@ -214,6 +224,99 @@ impl LegendWidget {
             .find(|(_, entry)| entry.hovered)
             .map(|(name, _)| name.to_string())
     }
+
+    /// The shapes of the legend as the widget shows it, but without any interaction and leaving
+    /// out the hidden entries. Used when the plot isn't shown in a [`Ui`], e.g. for exports.
+    pub(super) fn shapes(&self, ui: &Ui) -> Vec<Shape> {
+        let font_id = self.config.text_style.resolve(ui.style());
+        let text_color = ui.visuals().text_color();
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.checked)
+            .map(|(name, entry)| {
+                let galley = ui
+                    .fonts()
+                    .layout_no_wrap(name.clone(), font_id.clone(), text_color);
+                (galley, entry)
+            })
+            .collect();
+        if entries.is_empty() {
+            return Vec::new();
+        }
+
+        let spacing = ui.spacing().item_spacing.y;
+        let inner_size = entries.iter().fold(Vec2::ZERO, |size, (galley, _)| {
+            let entry_size = LegendEntry::size(galley);
+            vec2(size.x.max(entry_size.x), size.y + entry_size.y + spacing)
+        }) - vec2(0.0, spacing);
+        let size = inner_size + 2.0 * LEGEND_MARGIN;
+
+        let legend_rect = self.rect.shrink(LEGEND_PADDING);
+        let (frame_rect, label_on_the_left, bottom_up) = match self.config.position {
+            Corner::LeftTop => (
+                Rect::from_min_size(legend_rect.left_top(), size),
+                false,
+                false,
+            ),
+            Corner::RightTop => (
+                Rect::from_min_size(legend_rect.right_top() - vec2(size.x, 0.0), size),
+                true,
+                false,
+            ),
+            Corner::LeftBottom => (
+                Rect::from_min_size(legend_rect.left_bottom() - vec2(0.0, size.y), size),
+                false,
+                true,
+            ),
+            Corner::RightBottom => (
+                Rect::from_min_size(legend_rect.right_bottom() - size, size),
+                true,
+                true,
+            ),
+        };
+
+        let alpha = self.config.background_alpha;
+        let window_stroke = ui.visuals().window_stroke();
+        let mut shapes = vec![Shape::Rect(epaint::RectShape {
+            rect: frame_rect,
+            rounding: ui.visuals().window_rounding,
+            fill: ui.visuals().extreme_bg_color.linear_multiply(alpha),
+            stroke: Stroke::new(
+                window_stroke.width,
+                window_stroke.color.linear_multiply(alpha),
+            ),
+        })];
+
+        let visuals = ui.visuals().widgets.inactive;
+        let inner_rect = frame_rect.shrink2(LEGEND_MARGIN);
+        let mut y = if bottom_up {
+            inner_rect.bottom()
+        } else {
+            inner_rect.top()
+        };
+        for (galley, entry) in entries {
+            let entry_size = LegendEntry::size(&galley);
+            let top = if bottom_up { y - entry_size.y } else { y };
+            let rect = if label_on_the_left {
+                Rect::from_min_size(pos2(inner_rect.right() - entry_size.x, top), entry_size)
+            } else {
+                Rect::from_min_size(pos2(inner_rect.left(), top), entry_size)
+            };
+
+            let (icon_center, text_position) =
+                LegendEntry::layout(rect, &galley, label_on_the_left);
+            shapes.extend(entry.icon_shapes(ui, icon_center, galley.size().y, &visuals));
+            shapes.push(Shape::galley(text_position, galley));
+
+            if bottom_up {
+                y -= entry_size.y + spacing;
+            } else {
+                y += entry_size.y + spacing;
+            }
+        }
+        shapes
+    }
 }
 
 impl Widget for &mut LegendWidget {
@@ -233,13 +336,12 @@ impl Widget for &mut LegendWidget {
             Corner::RightTop | Corner::RightBottom => Align::RIGHT,
         };
         let layout = Layout::from_main_dir_and_cross_align(main_dir, cross_align);
-        let legend_pad = 4.0;
-        let legend_rect = rect.shrink(legend_pad);
+        let legend_rect = rect.shrink(LEGEND_PADDING);
         let mut legend_ui = ui.child_ui(legend_rect, layout);
         legend_ui
             .scope(|ui| {
                 let background_frame = Frame {
-                    inner_margin: vec2(8.0, 4.0).into(),
+                    inner_margin: LEGEND_MARGIN.into(),
                     rounding: ui.style().visuals.window_rounding,
                     shadow: epaint::Shadow::default(),
                     fill: ui.style().visuals.extreme_bg_color,
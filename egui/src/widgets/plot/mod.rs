@@ -7,24 +7,37 @@ use epaint::ahash::AHashSet;
 use epaint::color::Hsva;
 use epaint::util::FloatOrd;
 
-use items::PlotItem;
+use axis::AxisHints;
+use items::{PlotGeometry, PlotItem};
 use legend::LegendWidget;
+use selection::SelectionDrag;
 use transform::ScreenTransform;
 
+pub use axis::YAxis;
 pub use items::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, HLine, Line, LineStyle, MarkerShape,
-    Orientation, PlotImage, Points, Polygon, Text, VLine, Value, Values,
+    Arrows, Band, Bar, BarChart, Binning, BoxElem, BoxPlot, BoxSpread, CandleChart, CandleElem,
+    Colormap, Contour, DragHandles, GroupedBarChart, HLine, HandleDrag, Heatmap, Histogram, Line,
+    LineStyle, MarkerShape, Normalization, Ohlc, Orientation, PieChart, PieSlice, PlotImage,
+    Points, Polygon, Text, VLine, Value, ValueStream, Values,
 };
 pub use legend::{Corner, Legend};
-pub use transform::PlotBounds;
+pub use polar::PolarAxes;
+pub use selection::{PlotSelection, SelectionMode};
+pub use time::{time_axis_formatter, time_grid_spacer};
+pub use transform::{AxisScale, PlotBounds};
 
+mod axis;
 mod items;
 mod legend;
+mod polar;
+mod selection;
+mod svg;
+mod time;
 mod transform;
 
 type LabelFormatterFn = dyn Fn(&str, &Value) -> String;
 type LabelFormatter = Option<Box<LabelFormatterFn>>;
-type AxisFormatterFn = dyn Fn(f64, &RangeInclusive<f64>) -> String;
+type AxisFormatterFn = dyn Fn(Tick, &RangeInclusive<f64>) -> String;
 type AxisFormatter = Option<Box<AxisFormatterFn>>;
 
 type GridSpacerFn = dyn Fn(GridInput) -> Vec<GridMark>;
@@ -67,20 +80,32 @@ impl Default for CoordinatesFormatter {
 
 const MIN_LINE_SPACING_IN_POINTS: f64 = 6.0; // TODO(emilk): large enough for a wide label
 
+/// Handles smaller than this can still be grabbed within this distance of their center.
+const MIN_HANDLE_INTERACT_RADIUS: f32 = 8.0;
+
+/// Length of the tick marks of the secondary y axis, which doesn't draw full grid lines.
+const SECONDARY_TICK_LENGTH: f32 = 6.0;
+
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone)]
 struct AutoBounds {
     x: bool,
     y: bool,
+    /// The secondary y axis.
+    y2: bool,
 }
 
 impl AutoBounds {
     fn from_bool(val: bool) -> Self {
-        AutoBounds { x: val, y: val }
+        AutoBounds {
+            x: val,
+            y: val,
+            y2: val,
+        }
     }
 
     fn any(&self) -> bool {
-        self.x || self.y
+        self.x || self.y || self.y2
     }
 }
 
@@ -90,6 +115,14 @@ impl From<bool> for AutoBounds {
     }
 }
 
+/// A change of the plot bounds requested through [`PlotUi`], applied before the plot is shown.
+enum BoundsModification {
+    Set(PlotBounds),
+    Translate(Vec2),
+    Zoom(Vec2, Value),
+    AutoBounds([bool; 2]),
+}
+
 /// Information about the plot that has to persist between frames.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone)]
@@ -99,8 +132,12 @@ struct PlotMemory {
     hidden_items: AHashSet<String>,
     min_auto_bounds: PlotBounds,
     last_screen_transform: ScreenTransform,
+    /// Same frame and x bounds as `last_screen_transform`, with the bounds of the secondary y axis.
+    last_secondary_transform: ScreenTransform,
     /// Allows to remember the first click position when performing a boxed zoom
     last_click_pos_for_zoom: Option<Pos2>,
+    selection: Option<PlotSelection>,
+    selection_drag: Option<SelectionDrag>,
 }
 
 impl PlotMemory {
@@ -122,7 +159,10 @@ impl PlotMemory {
 pub struct LinkedAxisGroup {
     pub(crate) link_x: bool,
     pub(crate) link_y: bool,
+    pub(crate) link_cursor: bool,
     pub(crate) bounds: Rc<Cell<Option<PlotBounds>>>,
+    /// The hovered x value and the id of the plot that is hovered.
+    pub(crate) cursor: Rc<Cell<Option<(Id, f64)>>>,
 }
 
 impl LinkedAxisGroup {
@@ -130,7 +170,9 @@ impl LinkedAxisGroup {
         Self {
             link_x,
             link_y,
+            link_cursor: false,
             bounds: Rc::new(Cell::new(None)),
+            cursor: Rc::new(Cell::new(None)),
         }
     }
 
@@ -161,6 +203,20 @@ impl LinkedAxisGroup {
         self.link_y = link;
     }
 
+    /// Share the cursor between the plots of this group: when one of them is hovered, all of them
+    /// show a vertical line at the hovered x value, with the values of their lines at that x.
+    ///
+    /// This is most useful for plots stacked on top of each other with a linked x axis.
+    pub fn link_cursor(mut self, link: bool) -> Self {
+        self.link_cursor = link;
+        self
+    }
+
+    /// Change whether the cursor is linked for this group, see [`Self::link_cursor`].
+    pub fn set_link_cursor(&mut self, link: bool) {
+        self.link_cursor = link;
+    }
+
     fn get(&self) -> Option<PlotBounds> {
         self.bounds.get()
     }
@@ -168,6 +224,20 @@ impl LinkedAxisGroup {
     fn set(&self, bounds: PlotBounds) {
         self.bounds.set(Some(bounds));
     }
+
+    /// Report the x value hovered in the plot with the given id, or that it isn't hovered, and
+    /// return the hovered x value of the group.
+    fn update_cursor(&self, plot_id: Id, hovered_x: Option<f64>) -> Option<f64> {
+        match hovered_x {
+            Some(x) => self.cursor.set(Some((plot_id, x))),
+            None => {
+                if matches!(self.cursor.get(), Some((id, _)) if id == plot_id) {
+                    self.cursor.set(None);
+                }
+            }
+        }
+        self.cursor.get().map(|(_, x)| x)
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -196,9 +266,12 @@ pub struct Plot {
     allow_drag: bool,
     allow_scroll: bool,
     min_auto_bounds: PlotBounds,
+    bounds_limits: Option<PlotBounds>,
     margin_fraction: Vec2,
     allow_boxed_zoom: bool,
     boxed_zoom_pointer_button: PointerButton,
+    selection_mode: Option<SelectionMode>,
+    selection_pointer_button: PointerButton,
     linked_axes: Option<LinkedAxisGroup>,
 
     min_size: Vec2,
@@ -211,11 +284,14 @@ pub struct Plot {
     show_y: bool,
     label_formatter: LabelFormatter,
     coordinates_formatter: Option<(Corner, CoordinatesFormatter)>,
-    axis_formatters: [AxisFormatter; 2],
+    axis_formatters: [AxisFormatter; 3],
+    axis_hints: [AxisHints; 3],
+    axis_scales: [AxisScale; 3],
+    polar: Option<PolarAxes>,
     legend_config: Option<Legend>,
     show_background: bool,
     show_axes: [bool; 2],
-    grid_spacers: [GridSpacer; 2],
+    grid_spacers: [GridSpacer; 3],
 }
 
 impl Plot {
@@ -230,9 +306,12 @@ impl Plot {
             allow_drag: true,
             allow_scroll: true,
             min_auto_bounds: PlotBounds::NOTHING,
+            bounds_limits: None,
             margin_fraction: Vec2::splat(0.05),
             allow_boxed_zoom: true,
             boxed_zoom_pointer_button: PointerButton::Secondary,
+            selection_mode: None,
+            selection_pointer_button: PointerButton::Primary,
             linked_axes: None,
 
             min_size: Vec2::splat(64.0),
@@ -245,11 +324,18 @@ impl Plot {
             show_y: true,
             label_formatter: None,
             coordinates_formatter: None,
-            axis_formatters: [None, None], // [None; 2] requires Copy
+            axis_formatters: [None, None, None], // [None; 3] requires Copy
+            axis_hints: Default::default(),
+            axis_scales: [AxisScale::Linear; 3],
+            polar: None,
             legend_config: None,
             show_background: true,
             show_axes: [true; 2],
-            grid_spacers: [log_grid_spacer(10), log_grid_spacer(10)],
+            grid_spacers: [
+                log_grid_spacer(10),
+                log_grid_spacer(10),
+                log_grid_spacer(10),
+            ],
         }
     }
 
@@ -349,6 +435,39 @@ impl Plot {
         self
     }
 
+    /// Let the user select a part of the plot by dragging with the
+    /// [`Self::selection_pointer_button`], instead of moving the view. Default: no selection.
+    ///
+    /// The selection stays on the plot as a shaded area, which the user can resize by dragging
+    /// its edges. A click clears it. Read it with [`PlotUi::selection`] and
+    /// [`PlotUi::selected_values`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Line, Plot, SelectionMode, Values};
+    /// Plot::new("my_plot")
+    ///     .selection(SelectionMode::XRange)
+    ///     .show(ui, |plot_ui| {
+    ///         plot_ui.line(Line::new(Values::from_ys_f32(&[1.0, 3.0, 2.0, 4.0])).name("data"));
+    ///         if let Some(selection) = plot_ui.selection() {
+    ///             let x_range = selection.x_range();
+    ///             let selected = plot_ui.selected_values();
+    ///         }
+    ///     });
+    /// # });
+    /// ```
+    pub fn selection(mut self, mode: SelectionMode) -> Self {
+        self.selection_mode = Some(mode);
+        self
+    }
+
+    /// Config the button pointer to use for selecting, see [`Self::selection`].
+    /// Default: [`Primary`](PointerButton::Primary)
+    pub fn selection_pointer_button(mut self, selection_pointer_button: PointerButton) -> Self {
+        self.selection_pointer_button = selection_pointer_button;
+        self
+    }
+
     /// Whether to allow dragging in the plot to move the bounds. Default: `true`.
     pub fn allow_drag(mut self, on: bool) -> Self {
         self.allow_drag = on;
@@ -357,6 +476,9 @@ impl Plot {
 
     /// Provide a function to customize the on-hover label for the x and y axis
     ///
+    /// For a slice of a [`PieChart`], the value has the value of the slice as x
+    /// and its fraction of the pie as y.
+    ///
     /// ```
     /// # egui::__run_test_ui(|ui| {
     /// use egui::plot::{Line, Plot, Value, Values};
@@ -405,7 +527,7 @@ impl Plot {
         mut self,
         func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
-        self.axis_formatters[0] = Some(Box::new(func));
+        self.axis_formatters[0] = Some(Box::new(move |tick, range| func(tick.value, range)));
         self
     }
 
@@ -419,11 +541,236 @@ impl Plot {
     pub fn y_axis_formatter(
         mut self,
         func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
+    ) -> Self {
+        self.axis_formatters[1] = Some(Box::new(move |tick, range| func(tick.value, range)));
+        self
+    }
+
+    /// Like [`Self::x_axis_formatter`], but the function gets the whole [`Tick`], e.g. to show
+    /// more on the major ticks. See [`time_axis_formatter`] for an example.
+    pub fn x_axis_tick_formatter(
+        mut self,
+        func: impl Fn(Tick, &RangeInclusive<f64>) -> String + 'static,
+    ) -> Self {
+        self.axis_formatters[0] = Some(Box::new(func));
+        self
+    }
+
+    /// Like [`Self::y_axis_formatter`], but the function gets the whole [`Tick`].
+    pub fn y_axis_tick_formatter(
+        mut self,
+        func: impl Fn(Tick, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
         self.axis_formatters[1] = Some(Box::new(func));
         self
     }
 
+    /// Provide a function to customize the labels for the secondary Y axis.
+    ///
+    /// See [`Self::y_axis_formatter`] and [`PlotUi::set_y_axis`].
+    pub fn secondary_y_axis_formatter(
+        mut self,
+        func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
+    ) -> Self {
+        self.axis_formatters[2] = Some(Box::new(move |tick, range| func(tick.value, range)));
+        self
+    }
+
+    /// Label the X axis with the names of `categories` at `0.0`, `1.0`, `2.0`, … instead of
+    /// numbers, e.g. for a [`GroupedBarChart`].
+    ///
+    /// This sets the X axis formatter and grid spacer.
+    pub fn x_axis_categories(self, categories: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.axis_categories(0, categories)
+    }
+
+    /// Label the Y axis with the names of `categories`, e.g. for horizontal bars.
+    ///
+    /// See [`Self::x_axis_categories`].
+    pub fn y_axis_categories(self, categories: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.axis_categories(1, categories)
+    }
+
+    fn axis_categories(
+        mut self,
+        axis: usize,
+        categories: impl IntoIterator<Item = impl ToString>,
+    ) -> Self {
+        let categories: Vec<String> = categories
+            .into_iter()
+            .map(|category| category.to_string())
+            .collect();
+        let count = categories.len();
+        self.grid_spacers[axis] = Box::new(move |input: GridInput| {
+            let (min, max) = input.bounds;
+            (0..count)
+                .map(|index| index as f64)
+                .filter(|value| (min..=max).contains(value))
+                .map(|value| GridMark {
+                    value,
+                    step_size: 1.0,
+                })
+                .collect()
+        });
+        self.axis_formatters[axis] = Some(Box::new(move |tick, _| {
+            let value = tick.value;
+            let index = value.round();
+            if (value - index).abs() < 1e-6 && index >= 0.0 {
+                categories.get(index as usize).cloned().unwrap_or_default()
+            } else {
+                String::new()
+            }
+        }));
+        self
+    }
+
+    /// Set a title for the X axis, e.g. `"Time"`.
+    ///
+    /// The title is shown below the plot, in a gutter that is reserved outside of the data frame.
+    /// The tick labels of the X axis are moved into that gutter too.
+    ///
+    /// Pass an empty string to get the gutter without a title.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn x_axis_label(mut self, label: impl ToString) -> Self {
+        self.axis_hints[0].label = Some(label.to_string());
+        self
+    }
+
+    /// Set a title for the Y axis, e.g. `"Voltage"`.
+    ///
+    /// The title is shown rotated to the left of the plot, in a gutter that is reserved outside
+    /// of the data frame. The tick labels of the Y axis are moved into that gutter too.
+    ///
+    /// Pass an empty string to get the gutter without a title.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn y_axis_label(mut self, label: impl ToString) -> Self {
+        self.axis_hints[1].label = Some(label.to_string());
+        self
+    }
+
+    /// Set the unit of the X axis, e.g. `"s"`. It is shown after the title: `"Time [s]"`.
+    ///
+    /// Like [`Self::x_axis_label`], this reserves a gutter for the X axis.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn x_axis_unit(mut self, unit: impl ToString) -> Self {
+        self.axis_hints[0].unit = Some(unit.to_string());
+        self
+    }
+
+    /// Set the unit of the Y axis, e.g. `"mV"`. It is shown after the title: `"Voltage [mV]"`.
+    ///
+    /// Like [`Self::y_axis_label`], this reserves a gutter for the Y axis.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn y_axis_unit(mut self, unit: impl ToString) -> Self {
+        self.axis_hints[1].unit = Some(unit.to_string());
+        self
+    }
+
+    /// Set a title for the secondary Y axis, e.g. `"Pressure"`.
+    ///
+    /// The title is shown rotated to the right of the plot, in a gutter that is reserved outside
+    /// of the data frame. The tick labels of the secondary Y axis are moved into that gutter too.
+    ///
+    /// See [`PlotUi::set_y_axis`] for how to put items on the secondary Y axis.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn secondary_y_axis_label(mut self, label: impl ToString) -> Self {
+        self.axis_hints[2].label = Some(label.to_string());
+        self
+    }
+
+    /// Set the unit of the secondary Y axis, e.g. `"hPa"`.
+    ///
+    /// Like [`Self::secondary_y_axis_label`], this reserves a gutter for the secondary Y axis.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn secondary_y_axis_unit(mut self, unit: impl ToString) -> Self {
+        self.axis_hints[2].unit = Some(unit.to_string());
+        self
+    }
+
+    /// How many digits wide the tick labels in the Y axis gutter can be. Default: `5`.
+    ///
+    /// Only has an effect if the Y axis has a gutter, see [`Self::y_axis_label`].
+    pub fn y_axis_width(mut self, digits: usize) -> Self {
+        self.axis_hints[1].digits = digits;
+        self
+    }
+
+    /// How many digits wide the tick labels in the secondary Y axis gutter can be. Default: `5`.
+    pub fn secondary_y_axis_width(mut self, digits: usize) -> Self {
+        self.axis_hints[2].digits = digits;
+        self
+    }
+
+    /// How values along the X axis are mapped to the screen. Default: [`AxisScale::Linear`].
+    ///
+    /// For example, use [`AxisScale::Log10`] for the frequency axis of a Bode plot.
+    /// Items keep using the plain values; the plot takes care of the mapping.
+    ///
+    /// This also sets a grid spacer suited for the scale, so call [`Self::x_grid_spacer`]
+    /// after this if you want to use your own.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{AxisScale, Line, Plot, Values};
+    /// let line = Line::new(Values::from_explicit_callback(|f| 1.0 / (1.0 + f * f), 1e-3..1e3, 256));
+    /// Plot::new("bode")
+    ///     .x_axis_scale(AxisScale::Log10)
+    ///     .y_axis_scale(AxisScale::Log10)
+    ///     .show(ui, |plot_ui| plot_ui.line(line));
+    /// # });
+    /// ```
+    pub fn x_axis_scale(mut self, scale: AxisScale) -> Self {
+        self.axis_scales[0] = scale;
+        self.grid_spacers[0] = default_grid_spacer(scale);
+        self
+    }
+
+    /// How values along the Y axis are mapped to the screen. Default: [`AxisScale::Linear`].
+    ///
+    /// See [`Self::x_axis_scale`] for details.
+    pub fn y_axis_scale(mut self, scale: AxisScale) -> Self {
+        self.axis_scales[1] = scale;
+        self.grid_spacers[1] = default_grid_spacer(scale);
+        self
+    }
+
+    /// How values along the secondary Y axis are mapped to the screen. Default: [`AxisScale::Linear`].
+    ///
+    /// See [`Self::x_axis_scale`] for details.
+    pub fn secondary_y_axis_scale(mut self, scale: AxisScale) -> Self {
+        self.axis_scales[2] = scale;
+        self.grid_spacers[2] = default_grid_spacer(scale);
+        self
+    }
+
+    /// Show the plot in polar coordinates: the values of the items are `(angle, radius)`, with
+    /// the angle in degrees.
+    ///
+    /// Instead of the axes, rings are drawn at the radii given by the Y grid spacer and spokes
+    /// at the angles given by the [`PolarAxes`]. The X and Y axis formatters label the angles
+    /// and the radii. The axes are linear and the aspect ratio is one, unless set otherwise with
+    /// [`Self::data_aspect`].
+    ///
+    /// Items are drawn with straight segments between their values, so sample curves densely.
+    /// Selections and linked cursors are not available in polar plots.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Line, Plot, PolarAxes, Value, Values};
+    /// let pattern = (0..=360).map(|angle| {
+    ///     let gain = (angle as f64).to_radians().cos().abs().powi(3);
+    ///     Value::new(angle as f64, gain)
+    /// });
+    /// Plot::new("antenna")
+    ///     .polar(PolarAxes::compass())
+    ///     .show(ui, |plot_ui| plot_ui.line(Line::new(Values::from_values_iter(pattern))));
+    /// # });
+    /// ```
+    pub fn polar(mut self, polar: PolarAxes) -> Self {
+        self.polar = Some(polar);
+        self
+    }
+
     /// Configure how the grid in the background is spaced apart along the X axis.
     ///
     /// Default is a log-10 grid, i.e. every plot unit is divided into 10 other units.
@@ -453,7 +800,11 @@ impl Plot {
     /// # ()
     /// ```
     ///
-    /// There are helpers for common cases, see [`log_grid_spacer`] and [`uniform_grid_spacer`].
+    /// For non-linear axes (see [`Self::x_axis_scale`]) the spacer works in the space where the
+    /// axis is linear, e.g. in decades for [`AxisScale::Log10`].
+    ///
+    /// There are helpers for common cases, see [`log_grid_spacer`] and [`uniform_grid_spacer`],
+    /// and [`time_grid_spacer`] for axes showing dates and times.
     pub fn x_grid_spacer(mut self, spacer: impl Fn(GridInput) -> Vec<GridMark> + 'static) -> Self {
         self.grid_spacers[0] = Box::new(spacer);
         self
@@ -467,6 +818,18 @@ impl Plot {
         self
     }
 
+    /// Configure how the ticks of the secondary Y axis are spaced apart.
+    ///
+    /// The secondary Y axis doesn't draw grid lines across the plot, only short ticks at its edge.
+    /// See [`Self::x_grid_spacer`] for explanation.
+    pub fn secondary_y_grid_spacer(
+        mut self,
+        spacer: impl Fn(GridInput) -> Vec<GridMark> + 'static,
+    ) -> Self {
+        self.grid_spacers[2] = Box::new(spacer);
+        self
+    }
+
     /// Expand bounds to include the given x value.
     /// For instance, to always show the y axis, call `plot.include_x(0.0)`.
     pub fn include_x(mut self, x: impl Into<f64>) -> Self {
@@ -481,6 +844,25 @@ impl Plot {
         self
     }
 
+    /// Keep the visible bounds inside of `limits` when panning and zooming.
+    ///
+    /// The view is moved back inside of the limits, or shrunk to them if it is larger. Use
+    /// infinite values to leave a side unconstrained, e.g. to never show negative x values:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Plot, PlotBounds};
+    /// let limits = PlotBounds::from_min_max([0.0, f64::NEG_INFINITY], [f64::INFINITY; 2]);
+    /// Plot::new("my_plot").limit_bounds(limits).show(ui, |_plot_ui| ());
+    /// # });
+    /// ```
+    ///
+    /// The y limits only apply to the primary y axis.
+    pub fn limit_bounds(mut self, limits: PlotBounds) -> Self {
+        self.bounds_limits = Some(limits);
+        self
+    }
+
     /// Show a legend including all named items.
     pub fn legend(mut self, legend: Legend) -> Self {
         self.legend_config = Some(legend);
@@ -529,18 +911,24 @@ impl Plot {
             allow_drag,
             allow_boxed_zoom,
             boxed_zoom_pointer_button: boxed_zoom_pointer,
+            selection_mode,
+            selection_pointer_button,
             min_auto_bounds,
+            bounds_limits,
             margin_fraction,
             width,
             height,
             min_size,
-            data_aspect,
+            mut data_aspect,
             view_aspect,
             mut show_x,
             mut show_y,
             label_formatter,
             coordinates_formatter,
             axis_formatters,
+            axis_hints,
+            mut axis_scales,
+            polar,
             legend_config,
             show_background,
             show_axes,
@@ -548,6 +936,11 @@ impl Plot {
             grid_spacers,
         } = self;
 
+        if polar.is_some() {
+            axis_scales = [AxisScale::Linear; 3];
+            data_aspect = data_aspect.or(Some(1.0));
+        }
+
         // Determine the size of the plot in the UI
         let size = {
             let width = width
@@ -573,7 +966,10 @@ impl Plot {
         };
 
         // Allocate the space.
-        let (rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        let (complete_rect, mut response) = ui.allocate_exact_size(size, Sense::drag());
+
+        // Reserve the axis gutters, the rest is used for the data.
+        let (rect, gutters) = axis::layout(ui, complete_rect, &axis_hints);
 
         // Load or initialize the memory.
         let plot_id = ui.make_persistent_id(id_source);
@@ -588,8 +984,20 @@ impl Plot {
                 min_auto_bounds,
                 center_x_axis,
                 center_y_axis,
-            ),
+                [axis_scales[0], axis_scales[1]],
+            )
+            .with_polar(polar),
+            last_secondary_transform: ScreenTransform::new(
+                rect,
+                PlotBounds::NOTHING,
+                center_x_axis,
+                center_y_axis,
+                [axis_scales[0], axis_scales[2]],
+            )
+            .with_polar(polar),
             last_click_pos_for_zoom: None,
+            selection: None,
+            selection_drag: None,
         });
 
         // If the min bounds changed, recalculate everything.
@@ -608,7 +1016,10 @@ impl Plot {
             mut hovered_entry,
             mut hidden_items,
             last_screen_transform,
+            last_secondary_transform,
             mut last_click_pos_for_zoom,
+            mut selection,
+            mut selection_drag,
             ..
         } = memory;
 
@@ -616,17 +1027,42 @@ impl Plot {
         let mut plot_ui = PlotUi {
             items: Vec::new(),
             next_auto_color_idx: 0,
+            y_axis: YAxis::Primary,
+            bounds_modifications: Vec::new(),
             last_screen_transform,
-            response,
+            last_secondary_transform,
+            handle_dragged: false,
+            handle_hovered: false,
+            selection,
+            new_selection: SelectionChange::Unchanged,
+            hidden_items: hidden_items.clone(),
+            id: plot_id,
+            response: Some(response.clone()),
             ctx: ui.ctx().clone(),
         };
         let inner = build_fn(&mut plot_ui);
         let PlotUi {
             mut items,
-            mut response,
+            bounds_modifications,
             last_screen_transform,
+            last_secondary_transform,
+            handle_dragged,
+            handle_hovered,
+            new_selection,
             ..
         } = plot_ui;
+        match new_selection {
+            SelectionChange::Unchanged => {}
+            SelectionChange::Cleared => {
+                selection = None;
+                selection_drag = None;
+            }
+            SelectionChange::Set(new_selection) => {
+                selection = Some(new_selection);
+                selection_drag = None;
+            }
+        }
+        let has_secondary_items = items.iter().any(|(y_axis, _)| *y_axis == YAxis::Secondary);
 
         // Background
         if show_background {
@@ -639,27 +1075,30 @@ impl Plot {
         }
 
         // --- Legend ---
-        let legend = legend_config
-            .and_then(|config| LegendWidget::try_new(rect, config, &items, &hidden_items));
+        let legend = legend_config.and_then(|config| {
+            let items = items.iter().map(|(_, item)| &**item);
+            LegendWidget::try_new(rect, config, items, &hidden_items)
+        });
         // Don't show hover cursor when hovering over legend.
         if hovered_entry.is_some() {
             show_x = false;
             show_y = false;
         }
         // Remove the deselected items.
-        items.retain(|item| !hidden_items.contains(item.name()));
+        items.retain(|(_, item)| !hidden_items.contains(item.name()));
         // Highlight the hovered items.
         if let Some(hovered_name) = &hovered_entry {
             items
                 .iter_mut()
-                .filter(|entry| entry.name() == hovered_name)
-                .for_each(|entry| entry.highlight());
+                .filter(|(_, entry)| entry.name() == hovered_name)
+                .for_each(|(_, entry)| entry.highlight());
         }
         // Move highlighted items to front.
-        items.sort_by_key(|item| item.highlighted());
+        items.sort_by_key(|(_, item)| item.highlighted());
 
         // --- Bound computation ---
         let mut bounds = *last_screen_transform.bounds();
+        let mut secondary_bounds = *last_secondary_transform.bounds();
 
         // Transfer the bounds from a link group.
         if let Some(axes) = linked_axes.as_ref() {
@@ -677,42 +1116,48 @@ impl Plot {
             }
         };
 
+        // Apply the changes requested while building the plot. Zooming needs the screen
+        // transform, so it is done below.
+        let zooms = apply_bounds_modifications(
+            bounds_modifications,
+            &mut bounds,
+            &mut secondary_bounds,
+            &mut auto_bounds,
+        );
+
         // Allow double clicking to reset to automatic bounds.
         if response.double_clicked_by(PointerButton::Primary) {
             auto_bounds = true.into();
         }
 
         // Set bounds automatically based on content.
-        if auto_bounds.any() || !bounds.is_valid() {
-            if auto_bounds.x {
-                bounds.set_x(&min_auto_bounds);
-            }
-
-            if auto_bounds.y {
-                bounds.set_y(&min_auto_bounds);
-            }
-
-            for item in &items {
-                // bounds.merge(&item.get_bounds());
-
-                if auto_bounds.x {
-                    bounds.merge_x(&item.get_bounds());
-                }
-                if auto_bounds.y {
-                    bounds.merge_y(&item.get_bounds());
-                }
-            }
-
-            if auto_bounds.x {
-                bounds.add_relative_margin_x(margin_fraction);
-            }
-
-            if auto_bounds.y {
-                bounds.add_relative_margin_y(margin_fraction);
-            }
-        }
-
-        let mut transform = ScreenTransform::new(rect, bounds, center_x_axis, center_y_axis);
+        auto_fit_bounds(
+            &items,
+            &auto_bounds,
+            min_auto_bounds,
+            margin_fraction,
+            axis_scales,
+            polar.is_some(),
+            &mut bounds,
+            &mut secondary_bounds,
+        );
+
+        let mut transform = ScreenTransform::new(
+            rect,
+            bounds,
+            center_x_axis,
+            center_y_axis,
+            [axis_scales[0], axis_scales[1]],
+        )
+        .with_polar(polar);
+        let mut secondary_transform = ScreenTransform::new(
+            rect,
+            secondary_bounds,
+            center_x_axis,
+            center_y_axis,
+            [axis_scales[0], axis_scales[2]],
+        )
+        .with_polar(polar);
 
         // Enforce equal aspect ratio.
         if let Some(data_aspect) = data_aspect {
@@ -722,16 +1167,37 @@ impl Plot {
             transform.set_aspect(data_aspect as f64, preserve_y);
         }
 
+        apply_zooms(zooms, &mut transform, &mut secondary_transform);
+
+        // Selecting
+        let mut selection_cursor = None;
+        if let Some(mode) = selection_mode.filter(|_| !handle_dragged && polar.is_none()) {
+            selection_cursor = selection::interact(
+                mode,
+                selection_pointer_button,
+                &response,
+                &transform,
+                &mut selection,
+                &mut selection_drag,
+            );
+        }
+        let selecting = selection_drag.is_some();
+
         // Dragging
-        if allow_drag && response.dragged_by(PointerButton::Primary) {
+        if allow_drag
+            && !handle_dragged
+            && !selecting
+            && response.dragged_by(PointerButton::Primary)
+        {
             response = response.on_hover_cursor(CursorIcon::Grabbing);
             transform.translate_bounds(-response.drag_delta());
+            secondary_transform.translate_bounds(-response.drag_delta());
             auto_bounds = false.into();
         }
 
         // Zooming
         let mut boxed_zoom_rect = None;
-        if allow_boxed_zoom {
+        if allow_boxed_zoom && !handle_dragged && !selecting {
             // Save last click to allow boxed zooming
             if response.drag_started() && response.dragged_by(boxed_zoom_pointer) {
                 // it would be best for egui that input has a memory of the last click pos because it's a common pattern
@@ -759,14 +1225,18 @@ impl Plot {
                 }
                 // when the click is release perform the zoom
                 if response.drag_released() {
-                    let box_start_pos = transform.value_from_position(box_start_pos);
-                    let box_end_pos = transform.value_from_position(box_end_pos);
-                    let new_bounds = PlotBounds {
-                        min: [box_start_pos.x, box_end_pos.y],
-                        max: [box_end_pos.x, box_start_pos.y],
+                    let box_bounds = |transform: &ScreenTransform| {
+                        let box_start_pos = transform.point_from_position(box_start_pos);
+                        let box_end_pos = transform.point_from_position(box_end_pos);
+                        PlotBounds {
+                            min: [box_start_pos.x, box_end_pos.y],
+                            max: [box_end_pos.x, box_start_pos.y],
+                        }
                     };
+                    let new_bounds = box_bounds(&transform);
                     if new_bounds.is_valid() {
                         *transform.bounds_mut() = new_bounds;
+                        *secondary_transform.bounds_mut() = box_bounds(&secondary_transform);
                         auto_bounds = false.into();
                     } else {
                         auto_bounds = true.into();
@@ -786,6 +1256,7 @@ impl Plot {
                 };
                 if zoom_factor != Vec2::splat(1.0) {
                     transform.zoom(zoom_factor, hover_pos);
+                    secondary_transform.zoom(zoom_factor, hover_pos);
                     auto_bounds = false.into();
                 }
             }
@@ -793,16 +1264,36 @@ impl Plot {
                 let scroll_delta = ui.input().scroll_delta;
                 if scroll_delta != Vec2::ZERO {
                     transform.translate_bounds(-scroll_delta);
+                    secondary_transform.translate_bounds(-scroll_delta);
                     auto_bounds = false.into();
                 }
             }
         }
 
+        if let Some(limits) = &bounds_limits {
+            transform.clamp_bounds(limits);
+        }
+
+        // Both y axes share the x axis, which is controlled by the primary transform.
+        secondary_transform.bounds_mut().set_x(transform.bounds());
+
         // Initialize values from functions.
-        for item in &mut items {
-            item.initialize(transform.bounds().range_x());
+        let x_range = initialization_range(&transform);
+        for (_, item) in &mut items {
+            item.initialize(x_range.clone(), axis_scales[0]);
         }
 
+        let linked_cursor_x = linked_axes
+            .as_ref()
+            .filter(|group| group.link_cursor && polar.is_none())
+            .and_then(|group| {
+                let hovered_x = response
+                    .hover_pos()
+                    .filter(|pos| rect.contains(*pos))
+                    .map(|pos| transform.value_from_position(pos).x);
+                group.update_cursor(plot_id, hovered_x)
+            });
+
         let prepared = PreparedPlot {
             items,
             show_x,
@@ -811,9 +1302,16 @@ impl Plot {
             coordinates_formatter,
             axis_formatters,
             show_axes,
+            axis_hints,
+            gutters,
             transform: transform.clone(),
+            secondary_transform: has_secondary_items.then(|| secondary_transform.clone()),
             grid_spacers,
+            linked_cursor_x,
         };
+        if let Some(selection) = &selection {
+            selection::paint(ui, selection, &transform);
+        }
         prepared.ui(ui, &response);
 
         if let Some(boxed_zoom_rect) = boxed_zoom_rect {
@@ -837,11 +1335,20 @@ impl Plot {
             hidden_items,
             min_auto_bounds,
             last_screen_transform: transform,
+            last_secondary_transform: secondary_transform,
             last_click_pos_for_zoom,
+            selection,
+            selection_drag,
         };
         memory.store(ui.ctx(), plot_id);
 
-        let response = if show_x || show_y {
+        let response = if handle_dragged {
+            response.on_hover_cursor(CursorIcon::Grabbing)
+        } else if handle_hovered {
+            response.on_hover_cursor(CursorIcon::Grab)
+        } else if let Some(cursor) = selection_cursor {
+            response.on_hover_cursor(cursor)
+        } else if show_x || show_y {
             response.on_hover_cursor(CursorIcon::Crosshair)
         } else {
             response
@@ -849,71 +1356,567 @@ impl Plot {
 
         InnerResponse { inner, response }
     }
-}
 
-/// Provides methods to interact with a plot while building it. It is the single argument of the closure
-/// provided to [`Plot::show`]. See [`Plot`] for an example of how to use it.
-pub struct PlotUi {
-    items: Vec<Box<dyn PlotItem>>,
-    next_auto_color_idx: usize,
-    last_screen_transform: ScreenTransform,
-    response: Response,
-    ctx: Context,
-}
+    /// Draw the plot into an SVG document of the given `size` (in points), instead of showing it
+    /// in the [`Ui`], e.g. to put a vector version of it into a report.
+    ///
+    /// The document uses the same grid spacers, axis formatters, axis titles and legend as
+    /// [`Self::show`]. If a plot with the same id has been shown before, its current view and
+    /// hidden legend entries are used, so the export matches what is on screen. Otherwise the
+    /// bounds are fitted to the items.
+    ///
+    /// The `ui` is only used for the style and to lay out text, nothing is painted into it.
+    /// Images and other textured items are left out.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Line, Plot, Value, Values};
+    /// let sin = (0..100).map(|i| Value::new(i as f64 * 0.1, (i as f64 * 0.1).sin()));
+    /// let svg = Plot::new("my_plot").export_svg(ui, egui::vec2(400.0, 300.0), |plot_ui| {
+    ///     plot_ui.line(Line::new(Values::from_values_iter(sin)))
+    /// });
+    /// assert!(svg.starts_with("<svg"));
+    /// # });
+    /// ```
+    pub fn export_svg(self, ui: &mut Ui, size: Vec2, build_fn: impl FnOnce(&mut PlotUi)) -> String {
+        let Self {
+            id_source,
+            center_x_axis,
+            center_y_axis,
+            min_auto_bounds,
+            bounds_limits,
+            margin_fraction,
+            mut data_aspect,
+            axis_formatters,
+            axis_hints,
+            mut axis_scales,
+            polar,
+            legend_config,
+            show_background,
+            show_axes,
+            grid_spacers,
+            ..
+        } = self;
 
-impl PlotUi {
-    fn auto_color(&mut self) -> Color32 {
-        let i = self.next_auto_color_idx;
-        self.next_auto_color_idx += 1;
-        let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
-        let h = i as f32 * golden_ratio;
-        Hsva::new(h, 0.85, 0.5, 1.0).into() // TODO(emilk): OkLab or some other perspective color space
-    }
+        if polar.is_some() {
+            axis_scales = [AxisScale::Linear; 3];
+            data_aspect = data_aspect.or(Some(1.0));
+        }
 
-    pub fn ctx(&self) -> &Context {
-        &self.ctx
-    }
+        let complete_rect = Rect::from_min_size(Pos2::ZERO, size);
+        let (rect, gutters) = axis::layout(ui, complete_rect, &axis_hints);
 
-    /// The plot bounds as they were in the last frame. If called on the first frame and the bounds were not
-    /// further specified in the plot builder, this will return bounds centered on the origin. The bounds do
-    /// not change until the plot is drawn.
-    pub fn plot_bounds(&self) -> PlotBounds {
-        *self.last_screen_transform.bounds()
-    }
+        // Start from the view of the plot on screen, if there is one.
+        let plot_id = ui.make_persistent_id(id_source);
+        let (mut auto_bounds, mut bounds, mut secondary_bounds, hidden_items, selection) =
+            match PlotMemory::load(ui.ctx(), plot_id) {
+                Some(memory) => (
+                    memory.auto_bounds,
+                    *memory.last_screen_transform.bounds(),
+                    *memory.last_secondary_transform.bounds(),
+                    memory.hidden_items,
+                    memory.selection,
+                ),
+                None => (
+                    true.into(),
+                    PlotBounds::NOTHING,
+                    PlotBounds::NOTHING,
+                    Default::default(),
+                    None,
+                ),
+            };
 
-    /// Returns `true` if the plot area is currently hovered.
-    pub fn plot_hovered(&self) -> bool {
-        self.response.hovered()
-    }
+        let new_transform = |bounds, y_scale| {
+            ScreenTransform::new(
+                rect,
+                bounds,
+                center_x_axis,
+                center_y_axis,
+                [axis_scales[0], y_scale],
+            )
+            .with_polar(polar)
+        };
 
-    /// Returns `true` if the plot was clicked by the primary button.
-    pub fn plot_clicked(&self) -> bool {
-        self.response.clicked()
-    }
+        let mut plot_ui = PlotUi {
+            items: Vec::new(),
+            next_auto_color_idx: 0,
+            y_axis: YAxis::Primary,
+            bounds_modifications: Vec::new(),
+            last_screen_transform: new_transform(bounds, axis_scales[1]),
+            last_secondary_transform: new_transform(secondary_bounds, axis_scales[2]),
+            handle_dragged: false,
+            handle_hovered: false,
+            selection,
+            new_selection: SelectionChange::Unchanged,
+            hidden_items: hidden_items.clone(),
+            id: plot_id,
+            response: None,
+            ctx: ui.ctx().clone(),
+        };
+        build_fn(&mut plot_ui);
+        let PlotUi {
+            mut items,
+            bounds_modifications,
+            ..
+        } = plot_ui;
+        let has_secondary_items = items.iter().any(|(y_axis, _)| *y_axis == YAxis::Secondary);
 
-    /// The pointer position in plot coordinates. Independent of whether the pointer is in the plot area.
-    pub fn pointer_coordinate(&self) -> Option<Value> {
-        // We need to subtract the drag delta to keep in sync with the frame-delayed screen transform:
-        let last_pos = self.ctx().input().pointer.latest_pos()? - self.response.drag_delta();
-        let value = self.plot_from_screen(last_pos);
+        let legend = legend_config.and_then(|config| {
+            let items = items.iter().map(|(_, item)| &**item);
+            LegendWidget::try_new(rect, config, items, &hidden_items)
+        });
+        items.retain(|(_, item)| !hidden_items.contains(item.name()));
+
+        let zooms = apply_bounds_modifications(
+            bounds_modifications,
+            &mut bounds,
+            &mut secondary_bounds,
+            &mut auto_bounds,
+        );
+        if !bounds.is_valid() {
+            auto_bounds = true.into();
+        }
+        auto_fit_bounds(
+            &items,
+            &auto_bounds,
+            min_auto_bounds,
+            margin_fraction,
+            axis_scales,
+            polar.is_some(),
+            &mut bounds,
+            &mut secondary_bounds,
+        );
+
+        let mut transform = new_transform(bounds, axis_scales[1]);
+        let mut secondary_transform = new_transform(secondary_bounds, axis_scales[2]);
+        if let Some(data_aspect) = data_aspect {
+            transform.set_aspect(data_aspect as f64, false);
+        }
+        apply_zooms(zooms, &mut transform, &mut secondary_transform);
+        if let Some(limits) = &bounds_limits {
+            transform.clamp_bounds(limits);
+        }
+        secondary_transform.bounds_mut().set_x(transform.bounds());
+
+        let x_range = initialization_range(&transform);
+        for (_, item) in &mut items {
+            item.initialize(x_range.clone(), axis_scales[0]);
+        }
+
+        let prepared = PreparedPlot {
+            items,
+            show_x: false,
+            show_y: false,
+            label_formatter: None,
+            coordinates_formatter: None,
+            axis_formatters,
+            show_axes,
+            axis_hints,
+            gutters,
+            transform,
+            secondary_transform: has_secondary_items.then(|| secondary_transform),
+            grid_spacers,
+            linked_cursor_x: None,
+        };
+        let (mut frame_shapes, shapes) = prepared.shapes(ui);
+
+        if show_background {
+            frame_shapes.insert(
+                0,
+                Shape::Rect(epaint::RectShape {
+                    rect,
+                    rounding: Rounding::same(2.0),
+                    fill: ui.visuals().extreme_bg_color,
+                    stroke: ui.visuals().widgets.noninteractive.bg_stroke,
+                }),
+            );
+        }
+        if let Some(legend) = legend {
+            frame_shapes.extend(legend.shapes(ui));
+        }
+
+        svg::document(size, rect, &frame_shapes, &shapes)
+    }
+}
+
+/// Apply the bounds changes requested through [`PlotUi`]. Returns the zooms, which can only be
+/// applied once the screen transforms are known, see [`apply_zooms`].
+fn apply_bounds_modifications(
+    bounds_modifications: Vec<(YAxis, BoundsModification)>,
+    bounds: &mut PlotBounds,
+    secondary_bounds: &mut PlotBounds,
+    auto_bounds: &mut AutoBounds,
+) -> Vec<(YAxis, Vec2, Value)> {
+    let mut zooms = Vec::new();
+    for (y_axis, modification) in bounds_modifications {
+        let (mut x_bounds, mut y_bounds) = match y_axis {
+            YAxis::Primary => (*bounds, *bounds),
+            YAxis::Secondary => (*bounds, *secondary_bounds),
+        };
+        let (auto_x, auto_y) = match modification {
+            BoundsModification::Set(new_bounds) => {
+                x_bounds.set_x(&new_bounds);
+                y_bounds.set_y(&new_bounds);
+                (false, false)
+            }
+            BoundsModification::Translate(delta) => {
+                x_bounds.translate_x(delta.x as f64);
+                y_bounds.translate_y(delta.y as f64);
+                (false, false)
+            }
+            BoundsModification::Zoom(zoom_factor, center) => {
+                zooms.push((y_axis, zoom_factor, center));
+                (false, false)
+            }
+            BoundsModification::AutoBounds([auto_x, auto_y]) => (auto_x, auto_y),
+        };
+        bounds.set_x(&x_bounds);
+        auto_bounds.x = auto_x;
+        match y_axis {
+            YAxis::Primary => {
+                bounds.set_y(&y_bounds);
+                auto_bounds.y = auto_y;
+            }
+            YAxis::Secondary => {
+                secondary_bounds.set_y(&y_bounds);
+                auto_bounds.y2 = auto_y;
+            }
+        }
+    }
+    zooms
+}
+
+/// Zoom the transforms as requested through [`PlotUi::zoom_bounds`].
+fn apply_zooms(
+    zooms: Vec<(YAxis, Vec2, Value)>,
+    transform: &mut ScreenTransform,
+    secondary_transform: &mut ScreenTransform,
+) {
+    for (y_axis, zoom_factor, center) in zooms {
+        match y_axis {
+            YAxis::Primary => {
+                let center = transform.position_from_value(&center);
+                transform.zoom(zoom_factor, center);
+            }
+            YAxis::Secondary => {
+                let center = secondary_transform.position_from_value(&center);
+                transform.zoom(vec2(zoom_factor.x, 1.0), center);
+                secondary_transform.zoom(vec2(1.0, zoom_factor.y), center);
+            }
+        }
+    }
+}
+
+/// Fit the bounds of the axes with automatic bounds to the items, plus a margin.
+#[allow(clippy::too_many_arguments)]
+fn auto_fit_bounds(
+    items: &[(YAxis, Box<dyn PlotItem>)],
+    auto_bounds: &AutoBounds,
+    min_auto_bounds: PlotBounds,
+    margin_fraction: Vec2,
+    axis_scales: [AxisScale; 3],
+    polar: bool,
+    bounds: &mut PlotBounds,
+    secondary_bounds: &mut PlotBounds,
+) {
+    if !auto_bounds.any() {
+        return;
+    }
+
+    if auto_bounds.x {
+        bounds.set_x(&min_auto_bounds);
+    }
+
+    if auto_bounds.y {
+        bounds.set_y(&min_auto_bounds);
+    }
+
+    if auto_bounds.y2 {
+        secondary_bounds.set_y(&PlotBounds::NOTHING);
+    }
+
+    let [mut primary_items_bounds, mut secondary_items_bounds] = items_bounds(items, &axis_scales);
+    if polar {
+        // The bounds are in the plane, the items are given as (angle, radius).
+        primary_items_bounds = PolarAxes::point_bounds(&primary_items_bounds);
+        secondary_items_bounds = PolarAxes::point_bounds(&secondary_items_bounds);
+    }
+    if auto_bounds.x {
+        bounds.merge_x(&primary_items_bounds);
+        bounds.merge_x(&secondary_items_bounds);
+        bounds.add_relative_margin_x(margin_fraction, axis_scales[0]);
+    }
+
+    if auto_bounds.y {
+        bounds.merge_y(&primary_items_bounds);
+        bounds.add_relative_margin_y(margin_fraction, axis_scales[1]);
+    }
+
+    if auto_bounds.y2 {
+        secondary_bounds.merge_y(&secondary_items_bounds);
+        secondary_bounds.add_relative_margin_y(margin_fraction, axis_scales[2]);
+    }
+}
+
+/// The x range for which items generated from functions are evaluated: the visible x range, or
+/// all angles in a polar plot.
+fn initialization_range(transform: &ScreenTransform) -> RangeInclusive<f64> {
+    if transform.polar().is_some() {
+        0.0..=360.0
+    } else {
+        transform.bounds().range_x()
+    }
+}
+
+/// The bounds of the items on the primary and on the secondary y axis.
+///
+/// Values that can't be shown on a logarithmic axis are left out, so they don't stretch its bounds.
+fn items_bounds(
+    items: &[(YAxis, Box<dyn PlotItem>)],
+    axis_scales: &[AxisScale; 3],
+) -> [PlotBounds; 2] {
+    let mut bounds = [PlotBounds::NOTHING; 2];
+    for (y_axis, item) in items {
+        let mut item_bounds = item.get_bounds();
+        let (scales, index) = match y_axis {
+            YAxis::Primary => ([axis_scales[0], axis_scales[1]], 0),
+            YAxis::Secondary => ([axis_scales[0], axis_scales[2]], 1),
+        };
+
+        for (axis, scale) in scales.iter().enumerate() {
+            if *scale == AxisScale::Log10 && item_bounds.min[axis] <= 0.0 {
+                item_bounds.min[axis] = f64::INFINITY;
+            }
+        }
+
+        bounds[index].merge(&item_bounds);
+    }
+    bounds
+}
+
+/// Provides methods to interact with a plot while building it. It is the single argument of the closure
+/// provided to [`Plot::show`]. See [`Plot`] for an example of how to use it.
+pub struct PlotUi {
+    items: Vec<(YAxis, Box<dyn PlotItem>)>,
+    next_auto_color_idx: usize,
+    y_axis: YAxis,
+    bounds_modifications: Vec<(YAxis, BoundsModification)>,
+    last_screen_transform: ScreenTransform,
+    last_secondary_transform: ScreenTransform,
+    /// Whether one of the [`DragHandles`] is held, which stops the plot from panning.
+    handle_dragged: bool,
+    handle_hovered: bool,
+    /// The selection as it was in the last frame.
+    selection: Option<PlotSelection>,
+    new_selection: SelectionChange,
+    /// The names of the items hidden in the legend in the last frame.
+    hidden_items: AHashSet<String>,
+    id: Id,
+    /// `None` when exporting the plot, which doesn't interact with the user.
+    response: Option<Response>,
+    ctx: Context,
+}
+
+/// How [`PlotUi::set_selection`] changed the selection.
+enum SelectionChange {
+    Unchanged,
+    Cleared,
+    Set(PlotSelection),
+}
+
+impl PlotUi {
+    fn auto_color(&mut self) -> Color32 {
+        let i = self.next_auto_color_idx;
+        self.next_auto_color_idx += 1;
+        let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
+        let h = i as f32 * golden_ratio;
+        Hsva::new(h, 0.85, 0.5, 1.0).into() // TODO(emilk): OkLab or some other perspective color space
+    }
+
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The y axis that items are currently added to. Default: [`YAxis::Primary`].
+    pub fn y_axis(&self) -> YAxis {
+        self.y_axis
+    }
+
+    /// Choose the y axis that the items added after this call are plotted against.
+    ///
+    /// Items on the [`YAxis::Secondary`] axis have their own y bounds, which are fitted to them
+    /// independently of the other items, and their y axis is shown on the right side of the plot.
+    /// Both axes share the x axis.
+    ///
+    /// The bounds and coordinate conversions of this [`PlotUi`] (e.g. [`Self::plot_bounds`] and
+    /// [`Self::pointer_coordinate`]) refer to the chosen y axis as well.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Line, Plot, Values, YAxis};
+    /// let temperature = Line::new(Values::from_explicit_callback(|t| 20.0 + t.sin(), 0.0..10.0, 100));
+    /// let pressure = Line::new(Values::from_explicit_callback(|t| 1000.0 + 50.0 * t.cos(), 0.0..10.0, 100));
+    /// Plot::new("weather")
+    ///     .y_axis_label("Temperature")
+    ///     .secondary_y_axis_label("Pressure")
+    ///     .show(ui, |plot_ui| {
+    ///         plot_ui.line(temperature);
+    ///         plot_ui.set_y_axis(YAxis::Secondary);
+    ///         plot_ui.line(pressure);
+    ///     });
+    /// # });
+    /// ```
+    pub fn set_y_axis(&mut self, y_axis: YAxis) {
+        self.y_axis = y_axis;
+    }
+
+    /// The transform of the y axis that new items are added to.
+    fn transform(&self) -> &ScreenTransform {
+        self.transform_for(self.y_axis)
+    }
+
+    fn transform_for(&self, y_axis: YAxis) -> &ScreenTransform {
+        match y_axis {
+            YAxis::Primary => &self.last_screen_transform,
+            YAxis::Secondary => &self.last_secondary_transform,
+        }
+    }
+
+    /// The plot bounds as they were in the last frame. If called on the first frame and the bounds were not
+    /// further specified in the plot builder, this will return bounds centered on the origin. The bounds do
+    /// not change until the plot is drawn.
+    ///
+    /// The y bounds are those of the current [`Self::y_axis`].
+    pub fn plot_bounds(&self) -> PlotBounds {
+        *self.transform().bounds()
+    }
+
+    /// Show the given bounds, e.g. for a "reset view" button.
+    ///
+    /// This turns off automatic bounds, see [`Self::set_auto_bounds`]. Like the other methods
+    /// changing the view, it takes effect when the plot is shown at the end of this frame, so
+    /// [`Self::plot_bounds`] still returns the bounds of the last frame.
+    ///
+    /// The y bounds apply to the current [`Self::y_axis`].
+    pub fn set_plot_bounds(&mut self, plot_bounds: PlotBounds) {
+        self.bounds_modifications
+            .push((self.y_axis, BoundsModification::Set(plot_bounds)));
+    }
+
+    /// Move the view by `delta`, in plot coordinates.
+    ///
+    /// For instance, to follow the latest data of a time series, move the view by the time
+    /// that has passed since the last frame.
+    pub fn translate_bounds(&mut self, delta: Vec2) {
+        self.bounds_modifications
+            .push((self.y_axis, BoundsModification::Translate(delta)));
+    }
+
+    /// Zoom the view around `center`, given in plot coordinates.
+    ///
+    /// A `zoom_factor` larger than `1.0` zooms in, i.e. shows a smaller range of values.
+    pub fn zoom_bounds(&mut self, zoom_factor: Vec2, center: Value) {
+        self.bounds_modifications
+            .push((self.y_axis, BoundsModification::Zoom(zoom_factor, center)));
+    }
+
+    /// Turn automatic bounds on or off for the x axis and the current [`Self::y_axis`].
+    ///
+    /// Automatic bounds fit the view to the items in the plot. They are turned off when the
+    /// user moves the view, and turned on again when they double click it.
+    pub fn set_auto_bounds(&mut self, auto_bounds: [bool; 2]) {
+        self.bounds_modifications
+            .push((self.y_axis, BoundsModification::AutoBounds(auto_bounds)));
+    }
+
+    /// The part of the plot selected by the user, see [`Plot::selection`]. Like
+    /// [`Self::plot_bounds`], this is the selection as it was in the last frame.
+    pub fn selection(&self) -> Option<PlotSelection> {
+        self.selection
+    }
+
+    /// Replace the selection, or remove it with `None`. Can be used even if the user can't
+    /// select anything in the plot, to highlight a part of it.
+    pub fn set_selection(&mut self, selection: Option<PlotSelection>) {
+        self.new_selection = match selection {
+            Some(selection) => SelectionChange::Set(selection),
+            None => SelectionChange::Cleared,
+        };
+    }
+
+    /// The values of the series added so far that lie inside of the [`Self::selection`],
+    /// together with the name of their item. Empty if there is no selection.
+    ///
+    /// Only items made of points are included, such as [`Line`], [`Points`] and [`Polygon`],
+    /// and not those whose values are generated from a function.
+    pub fn selected_values(&self) -> Vec<(String, Vec<Value>)> {
+        let selection = match self.selection {
+            Some(selection) => selection,
+            None => return Vec::new(),
+        };
+        let secondary_selection =
+            selection.with_y_axis(&self.last_screen_transform, &self.last_secondary_transform);
+
+        self.items
+            .iter()
+            .filter_map(|(y_axis, item)| {
+                let values = match item.geometry() {
+                    PlotGeometry::Points(values) => values,
+                    PlotGeometry::None | PlotGeometry::Rects => return None,
+                };
+                let selection = match y_axis {
+                    YAxis::Primary => &selection,
+                    YAxis::Secondary => &secondary_selection,
+                };
+                let selected = values
+                    .iter()
+                    .filter(|value| selection.contains(value))
+                    .copied()
+                    .collect();
+                Some((item.name().to_owned(), selected))
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the plot area is currently hovered.
+    pub fn plot_hovered(&self) -> bool {
+        self.response.as_ref().map_or(false, Response::hovered)
+    }
+
+    /// Returns `true` if the plot was clicked by the primary button.
+    pub fn plot_clicked(&self) -> bool {
+        self.response.as_ref().map_or(false, Response::clicked)
+    }
+
+    /// The pointer position in plot coordinates. Independent of whether the pointer is in the plot area.
+    pub fn pointer_coordinate(&self) -> Option<Value> {
+        // We need to subtract the drag delta to keep in sync with the frame-delayed screen transform:
+        let last_pos = self.ctx().input().pointer.latest_pos()? - self.drag_delta();
+        let value = self.plot_from_screen(last_pos);
         Some(value)
     }
 
     /// The pointer drag delta in plot coordinates.
     pub fn pointer_coordinate_drag_delta(&self) -> Vec2 {
-        let delta = self.response.drag_delta();
-        let dp_dv = self.last_screen_transform.dpos_dvalue();
+        let delta = self.drag_delta();
+        let dp_dv = self.transform().dpos_dvalue();
         Vec2::new(delta.x / dp_dv[0] as f32, delta.y / dp_dv[1] as f32)
     }
 
+    fn drag_delta(&self) -> Vec2 {
+        self.response
+            .as_ref()
+            .map_or(Vec2::ZERO, Response::drag_delta)
+    }
+
     /// Transform the plot coordinates to screen coordinates.
     pub fn screen_from_plot(&self, position: Value) -> Pos2 {
-        self.last_screen_transform.position_from_value(&position)
+        self.transform().position_from_value(&position)
     }
 
     /// Transform the screen coordinates to plot coordinates.
     pub fn plot_from_screen(&self, position: Pos2) -> Value {
-        self.last_screen_transform.value_from_position(position)
+        self.transform().value_from_position(position)
     }
 
     /// Add a data line.
@@ -926,7 +1929,7 @@ impl PlotUi {
         if line.stroke.color == Color32::TRANSPARENT {
             line.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(line));
+        self.items.push((self.y_axis, Box::new(line)));
     }
 
     /// Add a polygon. The polygon has to be convex.
@@ -939,7 +1942,20 @@ impl PlotUi {
         if polygon.stroke.color == Color32::TRANSPARENT {
             polygon.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(polygon));
+        self.items.push((self.y_axis, Box::new(polygon)));
+    }
+
+    /// Add a band, filling the area between two series.
+    pub fn band(&mut self, mut band: Band) {
+        if band.lower.is_empty() && band.upper.is_empty() {
+            return;
+        };
+
+        // Give the band an automatic color if no color has been assigned.
+        if band.stroke.color == Color32::TRANSPARENT {
+            band.stroke.color = self.auto_color();
+        }
+        self.items.push((self.y_axis, Box::new(band)));
     }
 
     /// Add a text.
@@ -948,7 +1964,7 @@ impl PlotUi {
             return;
         };
 
-        self.items.push(Box::new(text));
+        self.items.push((self.y_axis, Box::new(text)));
     }
 
     /// Add data points.
@@ -961,7 +1977,111 @@ impl PlotUi {
         if points.color == Color32::TRANSPARENT {
             points.color = self.auto_color();
         }
-        self.items.push(Box::new(points));
+        self.items.push((self.y_axis, Box::new(points)));
+    }
+
+    /// Add handles that the user can drag around with the primary mouse button.
+    ///
+    /// Returns the handle that is being dragged and the value it was dragged to. The handles are
+    /// drawn at the values passed in, so the caller has to store the new value for the handle to
+    /// follow the pointer:
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{DragHandles, Plot, Value};
+    /// let mut envelope = vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0), Value::new(3.0, 0.0)];
+    /// Plot::new("envelope").show(ui, |plot_ui| {
+    ///     let handles = DragHandles::new("envelope", envelope.clone());
+    ///     if let Some(drag) = plot_ui.drag_handles(handles) {
+    ///         envelope[drag.index] = drag.value;
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn drag_handles(&mut self, handles: DragHandles) -> Option<HandleDrag> {
+        let DragHandles {
+            id,
+            mut values,
+            shape,
+            mut color,
+            radius,
+            name,
+        } = handles;
+        if values.is_empty() {
+            return None;
+        }
+
+        // Give the handles an automatic color if no color has been assigned.
+        if color == Color32::TRANSPARENT {
+            color = self.auto_color();
+        }
+
+        let id = self.id.with(id);
+        let transform = self.transform().clone();
+        let interact_radius_sq = radius.at_least(MIN_HANDLE_INTERACT_RADIUS).powi(2);
+        let closest_handle = |pos: Pos2| {
+            values
+                .iter()
+                .map(|value| transform.position_from_value(value).distance_sq(pos))
+                .enumerate()
+                .filter(|(_, dist_sq)| *dist_sq <= interact_radius_sq)
+                .min_by_key(|(_, dist_sq)| dist_sq.ord())
+                .map(|(index, _)| index)
+        };
+
+        // Pick up the handle closest to where the drag started, and hold it until released.
+        // Nothing can be held in an exported plot.
+        let (held, hovered, drag_delta) = match &self.response {
+            Some(response) => {
+                let mut held: Option<usize> = self.ctx.data().get_temp(id);
+                if response.drag_started() && response.dragged_by(PointerButton::Primary) {
+                    let press_origin = self.ctx.input().pointer.press_origin();
+                    held = press_origin.and_then(closest_handle);
+                }
+                if !response.dragged_by(PointerButton::Primary) {
+                    held = None;
+                }
+                let held = held.filter(|index| *index < values.len());
+                match held {
+                    Some(index) => self.ctx.data().insert_temp(id, index),
+                    None => self.ctx.data().remove::<usize>(id),
+                }
+
+                let hovered = held.or_else(|| response.hover_pos().and_then(closest_handle));
+                (held, hovered, response.drag_delta())
+            }
+            None => (None, None, Vec2::ZERO),
+        };
+        self.handle_dragged |= held.is_some();
+        self.handle_hovered |= hovered.is_some();
+
+        let drag = held.map(|index| {
+            let pos = transform.position_from_value(&values[index]) + drag_delta;
+            let value = transform.value_from_position(pos);
+            HandleDrag { index, value }
+        });
+        if let Some(drag) = drag {
+            values[drag.index] = drag.value;
+        }
+
+        // Mark the handle under the pointer with a ring.
+        if let Some(index) = hovered {
+            let ring = Points::new(Values::from_values(vec![values[index]]))
+                .shape(shape)
+                .color(color)
+                .filled(false)
+                .radius(1.6 * radius)
+                .name(&name);
+            self.items.push((self.y_axis, Box::new(ring)));
+        }
+        let points = Points::new(Values::from_values(values))
+            .shape(shape)
+            .color(color)
+            .radius(radius)
+            .name(name);
+        self.items.push((self.y_axis, Box::new(points)));
+
+        drag
     }
 
     /// Add arrows.
@@ -974,12 +2094,12 @@ impl PlotUi {
         if arrows.color == Color32::TRANSPARENT {
             arrows.color = self.auto_color();
         }
-        self.items.push(Box::new(arrows));
+        self.items.push((self.y_axis, Box::new(arrows)));
     }
 
     /// Add an image.
     pub fn image(&mut self, image: PlotImage) {
-        self.items.push(Box::new(image));
+        self.items.push((self.y_axis, Box::new(image)));
     }
 
     /// Add a horizontal line.
@@ -989,7 +2109,7 @@ impl PlotUi {
         if hline.stroke.color == Color32::TRANSPARENT {
             hline.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(hline));
+        self.items.push((self.y_axis, Box::new(hline)));
     }
 
     /// Add a vertical line.
@@ -999,7 +2119,7 @@ impl PlotUi {
         if vline.stroke.color == Color32::TRANSPARENT {
             vline.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(vline));
+        self.items.push((self.y_axis, Box::new(vline)));
     }
 
     /// Add a box plot diagram.
@@ -1012,7 +2132,15 @@ impl PlotUi {
         if box_plot.default_color == Color32::TRANSPARENT {
             box_plot = box_plot.color(self.auto_color());
         }
-        self.items.push(Box::new(box_plot));
+        self.items.push((self.y_axis, Box::new(box_plot)));
+    }
+
+    /// Add a candlestick chart.
+    pub fn candle_chart(&mut self, chart: CandleChart) {
+        if chart.candles.is_empty() {
+            return;
+        }
+        self.items.push((self.y_axis, Box::new(chart)));
     }
 
     /// Add a bar chart.
@@ -1025,7 +2153,116 @@ impl PlotUi {
         if chart.default_color == Color32::TRANSPARENT {
             chart = chart.color(self.auto_color());
         }
-        self.items.push(Box::new(chart));
+        self.items.push((self.y_axis, Box::new(chart)));
+    }
+
+    /// Add bar charts side by side, each with its own color and legend entry.
+    pub fn grouped_bar_chart(&mut self, group: GroupedBarChart) {
+        for chart in group.layout() {
+            self.bar_chart(chart);
+        }
+    }
+
+    /// Add a histogram. Its samples are binned when it is added.
+    pub fn histogram(&mut self, mut histogram: Histogram) {
+        if histogram.samples.is_empty() {
+            return;
+        }
+
+        // Give the bins an automatic color if no color has been assigned.
+        if histogram.chart.default_color == Color32::TRANSPARENT {
+            histogram = histogram.color(self.auto_color());
+        }
+        self.items.push((self.y_axis, Box::new(histogram.build())));
+    }
+
+    /// Add a pie chart, with one legend entry per named slice.
+    ///
+    /// Clicking a slice explodes it, i.e. moves it out of the pie, and clicking it again moves
+    /// it back. Returns the index of the slice clicked this frame.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Legend, PieChart, PieSlice, Plot};
+    /// let pie = PieChart::new(
+    ///     "fruit",
+    ///     vec![
+    ///         PieSlice::new(3.0).name("Apples"),
+    ///         PieSlice::new(2.0).name("Pears"),
+    ///         PieSlice::new(1.0).name("Plums"),
+    ///     ],
+    /// )
+    /// .inner_radius(0.5);
+    /// Plot::new("fruit")
+    ///     .data_aspect(1.0)
+    ///     .legend(Legend::default())
+    ///     .show(ui, |plot_ui| plot_ui.pie_chart(pie));
+    /// # });
+    /// ```
+    pub fn pie_chart(&mut self, mut pie: PieChart) -> Option<usize> {
+        if pie.slices.is_empty() {
+            return None;
+        }
+
+        // Give the slices an automatic color if no color has been assigned.
+        for slice in &mut pie.slices {
+            if slice.color == Color32::TRANSPARENT {
+                slice.color = self.auto_color();
+            }
+        }
+
+        let id = self.id.with(pie.id);
+        let mut exploded: Option<usize> = self.ctx.data().get_temp(id);
+        let mut items = pie.slice_items(&self.hidden_items, exploded);
+
+        // The slices are added to `self.y_axis`, so they have to be hit tested in its space:
+        let clicked = if self.plot_clicked() {
+            let transform = self.transform_for(self.y_axis);
+            self.response
+                .as_ref()
+                .and_then(Response::interact_pointer_pos)
+                .map(|pos| transform.value_from_position(pos))
+                .and_then(|value| items.iter().position(|item| item.contains(value)))
+        } else {
+            None
+        };
+        if let Some(index) = clicked {
+            exploded = (exploded != Some(index)).then(|| index);
+            match exploded {
+                Some(index) => self.ctx.data().insert_temp(id, index),
+                None => self.ctx.data().remove::<usize>(id),
+            }
+            for (index, item) in items.iter_mut().enumerate() {
+                item.exploded = exploded == Some(index);
+            }
+        }
+
+        for item in items {
+            self.items.push((self.y_axis, Box::new(item)));
+        }
+        clicked
+    }
+
+    /// Add a heatmap.
+    pub fn heatmap(&mut self, heatmap: Heatmap) {
+        if heatmap.rows() == 0 {
+            return;
+        }
+
+        self.items.push((self.y_axis, Box::new(heatmap)));
+    }
+
+    /// Add iso-lines of a grid of values. They are computed when they are added.
+    pub fn contour(&mut self, mut contour: Contour) {
+        if contour.rows() < 2 || contour.cols < 2 {
+            return;
+        }
+
+        // Give the lines an automatic color if no color has been assigned.
+        if contour.stroke.color == Color32::TRANSPARENT {
+            contour.stroke.color = self.auto_color();
+        }
+        self.items.push((self.y_axis, Box::new(contour.build())));
     }
 }
 
@@ -1038,6 +2275,9 @@ impl PlotUi {
 pub struct GridInput {
     /// Min/max of the visible data range (the values at the two edges of the plot,
     /// for the current axis).
+    ///
+    /// For non-linear axes, these are given in the space where the axis is linear
+    /// (e.g. `(-3.0, 6.0)` for `1e-3..=1e6` on a [`AxisScale::Log10`] axis).
     pub bounds: (f64, f64),
 
     /// Recommended (but not required) lower-bound on the step size returned by custom grid spacers.
@@ -1048,8 +2288,11 @@ pub struct GridInput {
 }
 
 /// One mark (horizontal or vertical line) in the background grid of a plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct GridMark {
     /// X or Y value in the plot.
+    ///
+    /// For non-linear axes, this is in the same space as [`GridInput::bounds`].
     pub value: f64,
 
     /// The (approximate) distance to the next value of same thickness.
@@ -1061,6 +2304,20 @@ pub struct GridMark {
     pub step_size: f64,
 }
 
+/// A labeled mark on an axis, as given to the functions of [`Plot::x_axis_tick_formatter`] and
+/// [`Plot::y_axis_tick_formatter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tick {
+    /// The value of the mark in plot coordinates.
+    pub value: f64,
+
+    /// The [`GridMark::step_size`] of the mark.
+    pub step_size: f64,
+
+    /// Whether this is one of the marks with the largest step size, i.e. the thickest lines.
+    pub major: bool,
+}
+
 /// Recursively splits the grid into `base` subdivisions (e.g. 100, 10, 1).
 ///
 /// The logarithmic base, expressing how many times each grid unit is subdivided.
@@ -1101,47 +2358,146 @@ pub fn uniform_grid_spacer(spacer: impl Fn(GridInput) -> [f64; 3] + 'static) ->
     Box::new(get_marks)
 }
 
+/// Marks at every power of ten, for axes using [`AxisScale::Log10`].
+///
+/// When there is enough room, each decade is subdivided at 2, 3, …, 9.
+/// When zoomed out far, only every 10th, 100th, … decade gets a mark.
+pub fn log_axis_grid_spacer() -> GridSpacer {
+    let get_marks = |input: GridInput| -> Vec<GridMark> {
+        let decade_step = next_power(input.base_step_size, 10.0).at_least(1.0);
+        let mut marks = generate_marks(
+            [decade_step, decade_step * 10.0, decade_step * 100.0],
+            input.bounds,
+        );
+
+        // Only subdivide decades which are at least ten grid lines wide.
+        if input.base_step_size.abs() < 0.1 {
+            let (min, max) = input.bounds;
+            for decade in (min.floor() as i64)..(max.ceil() as i64) {
+                for multiple in 2..=9 {
+                    let value = decade as f64 + (multiple as f64).log10();
+                    if min < value && value < max {
+                        marks.push(GridMark {
+                            value,
+                            step_size: 0.1,
+                        });
+                    }
+                }
+            }
+        }
+
+        marks
+    };
+
+    Box::new(get_marks)
+}
+
+/// Marks at zero and at plus and minus `linear_threshold` times every power of ten,
+/// for axes using [`AxisScale::Symlog`] with the same `linear_threshold`.
+pub fn symlog_axis_grid_spacer(linear_threshold: f64) -> GridSpacer {
+    let linear_threshold = transform::sanitized_threshold(linear_threshold);
+    let scale = AxisScale::Symlog { linear_threshold };
+    let get_marks = move |input: GridInput| -> Vec<GridMark> {
+        let decade_step = next_power(input.base_step_size, 10.0).at_least(1.0) as i64;
+        let (min, max) = input.bounds;
+        let last_decade = min.abs().max(max.abs()).ceil() as i64;
+
+        let mut marks = vec![];
+        for decade in (0..=last_decade).step_by(decade_step as usize) {
+            let step_size = if decade % (10 * decade_step) == 0 {
+                10.0 * decade_step as f64
+            } else {
+                decade_step as f64
+            };
+            let value = scale.scale(linear_threshold * 10_f64.powi(decade as i32));
+            for value in [-value, value] {
+                if min < value && value < max {
+                    marks.push(GridMark { value, step_size });
+                }
+            }
+        }
+        if min < 0.0 && 0.0 < max {
+            marks.push(GridMark {
+                value: 0.0,
+                step_size: 100.0 * decade_step as f64,
+            });
+        }
+
+        marks
+    };
+
+    Box::new(get_marks)
+}
+
+/// The grid spacer that [`Plot`] uses for an axis with the given scale.
+fn default_grid_spacer(scale: AxisScale) -> GridSpacer {
+    match scale {
+        AxisScale::Linear => log_grid_spacer(10),
+        AxisScale::Log10 => log_axis_grid_spacer(),
+        AxisScale::Symlog { linear_threshold } => symlog_axis_grid_spacer(linear_threshold),
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 struct PreparedPlot {
-    items: Vec<Box<dyn PlotItem>>,
+    items: Vec<(YAxis, Box<dyn PlotItem>)>,
     show_x: bool,
     show_y: bool,
     label_formatter: LabelFormatter,
     coordinates_formatter: Option<(Corner, CoordinatesFormatter)>,
-    axis_formatters: [AxisFormatter; 2],
+    axis_formatters: [AxisFormatter; 3],
     show_axes: [bool; 2],
+    axis_hints: [AxisHints; 3],
+    gutters: [Rect; 3],
     transform: ScreenTransform,
-    grid_spacers: [GridSpacer; 2],
+    /// Only set if there are items on the secondary y axis.
+    secondary_transform: Option<ScreenTransform>,
+    grid_spacers: [GridSpacer; 3],
+    /// The x value hovered in a plot of the same [`LinkedAxisGroup`], if the cursor is linked.
+    linked_cursor_x: Option<f64>,
 }
 
 impl PreparedPlot {
-    fn ui(self, ui: &mut Ui, response: &Response) {
-        let mut shapes = Vec::new();
-
-        for d in 0..2 {
-            if self.show_axes[d] {
-                self.paint_axis(ui, d, &mut shapes);
-            }
+    fn transform_for(&self, y_axis: YAxis) -> &ScreenTransform {
+        match (y_axis, &self.secondary_transform) {
+            (YAxis::Secondary, Some(secondary_transform)) => secondary_transform,
+            _ => &self.transform,
         }
+    }
+
+    fn ui(self, ui: &mut Ui, response: &Response) {
+        let (mut shapes, gutter_shapes) = self.shapes(ui);
 
         let transform = &self.transform;
 
-        let mut plot_ui = ui.child_ui(*transform.frame(), Layout::default());
-        plot_ui.set_clip_rect(*transform.frame());
-        for item in &self.items {
-            item.get_shapes(&mut plot_ui, transform, &mut shapes);
-        }
+        // Only show the hover readout while the pointer is above the data, not the gutters.
+        let hover_pos = response
+            .hover_pos()
+            .filter(|pos| transform.frame().contains(*pos));
 
-        if let Some(pointer) = response.hover_pos() {
+        if let Some(pointer) = hover_pos {
             self.hover(ui, pointer, &mut shapes);
         }
 
+        if let Some(x) = self.linked_cursor_x {
+            self.paint_linked_cursor(ui, x, hover_pos.is_none(), &mut shapes);
+        }
+
+        if !gutter_shapes.is_empty() {
+            let gutter_rect = self.gutters[0]
+                .union(self.gutters[1])
+                .union(self.gutters[2]);
+            ui.painter()
+                .with_clip_rect(gutter_rect.union(*transform.frame()))
+                .extend(gutter_shapes);
+        }
+
         let painter = ui.painter().with_clip_rect(*transform.frame());
         painter.extend(shapes);
 
         if let Some((corner, formatter)) = self.coordinates_formatter.as_ref() {
-            if let Some(pointer) = response.hover_pos() {
+            if let Some(pointer) = hover_pos {
                 let font_id = TextStyle::Monospace.resolve(ui.style());
                 let coordinate = transform.value_from_position(pointer);
                 let text = formatter.format(&coordinate, transform.bounds());
@@ -1157,44 +2513,111 @@ impl PreparedPlot {
         }
     }
 
-    fn paint_axis(&self, ui: &Ui, axis: usize, shapes: &mut Vec<Shape>) {
+    /// The shapes inside the frame, i.e. the grid and the items, and the shapes in the gutters.
+    fn shapes(&self, ui: &mut Ui) -> (Vec<Shape>, Vec<Shape>) {
+        let mut shapes = Vec::new();
+        let mut gutter_shapes = Vec::new();
+
+        if let Some(polar) = self.transform.polar() {
+            if self.show_axes.iter().any(|show| *show) {
+                polar::paint_grid(
+                    ui,
+                    polar,
+                    &self.transform,
+                    &self.grid_spacers[1],
+                    [
+                        self.axis_formatters[0].as_deref(),
+                        self.axis_formatters[1].as_deref(),
+                    ],
+                    &mut shapes,
+                );
+            }
+        }
+
+        // Axis `2` is the secondary y axis.
+        for d in 0..3 {
+            let show_axis = match d {
+                2 => self.show_axes[1] && self.secondary_transform.is_some(),
+                _ => self.show_axes[d],
+            };
+            if show_axis && self.transform.polar().is_none() {
+                self.paint_axis(ui, d, &mut shapes, &mut gutter_shapes);
+            }
+            if self.axis_hints[d].has_gutter() {
+                axis::paint_title(
+                    ui,
+                    &self.axis_hints[d],
+                    d,
+                    self.gutters[d],
+                    &mut gutter_shapes,
+                );
+            }
+        }
+
+        let frame = *self.transform.frame();
+        let mut plot_ui = ui.child_ui(frame, Layout::default());
+        plot_ui.set_clip_rect(frame);
+        for (y_axis, item) in &self.items {
+            item.get_shapes(&mut plot_ui, self.transform_for(*y_axis), &mut shapes);
+        }
+
+        (shapes, gutter_shapes)
+    }
+
+    fn paint_axis(
+        &self,
+        ui: &Ui,
+        axis: usize,
+        shapes: &mut Vec<Shape>,
+        gutter_shapes: &mut Vec<Shape>,
+    ) {
         let Self {
-            transform,
             axis_formatters,
+            axis_hints,
+            gutters,
             grid_spacers,
             ..
         } = self;
 
+        // The secondary y axis (`2`) runs along the same dimension as the primary one.
+        let (transform, dim) = match axis {
+            0 => (&self.transform, 0),
+            1 => (&self.transform, 1),
+            2 => (self.transform_for(YAxis::Secondary), 1),
+            _ => panic!("Axis {} does not exist.", axis),
+        };
+
         let bounds = transform.bounds();
-        let axis_range = match axis {
+        let axis_range = match dim {
             0 => bounds.range_x(),
-            1 => bounds.range_y(),
-            _ => panic!("Axis {} does not exist.", axis),
+            _ => bounds.range_y(),
         };
+        let scale = transform.scales()[dim];
 
         let font_id = TextStyle::Body.resolve(ui.style());
 
         // Where on the cross-dimension to show the label values
         let bounds = transform.bounds();
-        let value_cross = 0.0_f64.clamp(bounds.min[1 - axis], bounds.max[1 - axis]);
+        let value_cross = 0.0_f64.clamp(bounds.min[1 - dim], bounds.max[1 - dim]);
 
         let input = GridInput {
-            bounds: (bounds.min[axis], bounds.max[axis]),
-            base_step_size: transform.dvalue_dpos()[axis] * MIN_LINE_SPACING_IN_POINTS,
+            bounds: (scale.scale(bounds.min[dim]), scale.scale(bounds.max[dim])),
+            base_step_size: transform.dvalue_dpos()[dim] * MIN_LINE_SPACING_IN_POINTS,
         };
         let steps = (grid_spacers[axis])(input);
+        let major_step_size = steps.iter().map(|step| step.step_size).fold(0.0, f64::max);
 
         for step in steps {
-            let value_main = step.value;
+            let value_main = scale.unscale(step.value);
 
-            let value = if axis == 0 {
+            let value = if dim == 0 {
                 Value::new(value_main, value_cross)
             } else {
                 Value::new(value_cross, value_main)
             };
 
             let pos_in_gui = transform.position_from_value(&value);
-            let spacing_in_points = (transform.dpos_dvalue()[axis] * step.step_size).abs() as f32;
+            let spacing_in_points = (transform.dpos_dvalue()[dim] * step.step_size).abs() as f32;
 
             let line_alpha = remap_clamp(
                 spacing_in_points,
@@ -1207,8 +2630,15 @@ impl PreparedPlot {
 
                 let mut p0 = pos_in_gui;
                 let mut p1 = pos_in_gui;
-                p0[1 - axis] = transform.frame().min[1 - axis];
-                p1[1 - axis] = transform.frame().max[1 - axis];
+                if axis == 2 {
+                    // Only short ticks along the right edge, so they don't clash with the grid
+                    // of the primary y axis.
+                    p0.x = transform.frame().right() - SECONDARY_TICK_LENGTH;
+                    p1.x = transform.frame().right();
+                } else {
+                    p0[1 - dim] = transform.frame().min[1 - dim];
+                    p1[1 - dim] = transform.frame().max[1 - dim];
+                }
                 shapes.push(Shape::line_segment([p0, p1], Stroke::new(1.0, line_color)));
             }
 
@@ -1218,33 +2648,121 @@ impl PreparedPlot {
                 let color = color_from_alpha(ui, text_alpha);
 
                 let text: String = if let Some(formatter) = axis_formatters[axis].as_deref() {
-                    formatter(value_main, &axis_range)
-                } else {
+                    let tick = Tick {
+                        value: value_main,
+                        step_size: step.step_size,
+                        major: step.step_size == major_step_size,
+                    };
+                    formatter(tick, &axis_range)
+                } else if scale.is_linear() {
                     emath::round_to_decimals(value_main, 5).to_string() // hack
+                } else {
+                    format_scaled_tick(value_main)
                 };
 
                 // Custom formatters can return empty string to signal "no label at this resolution"
-                if !text.is_empty() {
+                if !text.is_empty() && axis_hints[axis].has_gutter() {
+                    let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
+                    let text_pos =
+                        axis::tick_label_pos(gutters[axis], axis, pos_in_gui, galley.size());
+                    gutter_shapes.push(Shape::galley(text_pos, galley));
+                } else if !text.is_empty() && axis == 2 {
+                    let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
+
+                    // Right-aligned against the right edge of the frame.
+                    let text_pos = pos2(
+                        transform.frame().right() - SECONDARY_TICK_LENGTH - galley.size().x,
+                        pos_in_gui.y - galley.size().y,
+                    );
+
+                    shapes.push(Shape::galley(text_pos, galley));
+                } else if !text.is_empty() {
                     let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
 
                     let mut text_pos = pos_in_gui + vec2(1.0, -galley.size().y);
 
                     // Make sure we see the labels, even if the axis is off-screen:
-                    text_pos[1 - axis] = text_pos[1 - axis]
-                        .at_most(transform.frame().max[1 - axis] - galley.size()[1 - axis] - 2.0)
-                        .at_least(transform.frame().min[1 - axis] + 1.0);
+                    text_pos[1 - dim] = text_pos[1 - dim]
+                        .at_most(transform.frame().max[1 - dim] - galley.size()[1 - dim] - 2.0)
+                        .at_least(transform.frame().min[1 - dim] + 1.0);
 
                     shapes.push(Shape::galley(text_pos, galley));
                 }
             }
         }
+    }
 
-        fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
-            if ui.visuals().dark_mode {
-                Rgba::from_white_alpha(alpha).into()
-            } else {
-                Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
-            }
+    /// A vertical line at the `x` value hovered in a linked plot, and a readout of the values of
+    /// the visible items at that `x`. The line is left out in the hovered plot itself, which
+    /// already shows the rulers of the regular hover.
+    fn paint_linked_cursor(&self, ui: &Ui, x: f64, show_line: bool, shapes: &mut Vec<Shape>) {
+        let transform = &self.transform;
+        let frame = *transform.frame();
+        let pos_x = transform.position_from_value(&Value::new(x, 0.0)).x;
+        if !frame.x_range().contains(&pos_x) {
+            return;
+        }
+
+        let line_color = if ui.visuals().dark_mode {
+            Color32::from_gray(100).additive()
+        } else {
+            Color32::from_black_alpha(180)
+        };
+        if show_line {
+            shapes.push(Shape::line_segment(
+                [pos2(pos_x, frame.top()), pos2(pos_x, frame.bottom())],
+                Stroke::new(1.0, line_color),
+            ));
+        }
+
+        // One row per item that has a value at `x`, in the color of the item.
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let rows: Vec<_> = self
+            .items
+            .iter()
+            .filter_map(|(y_axis, item)| {
+                let y = item.value_at_x(x)?;
+                let transform = self.transform_for(*y_axis);
+                let scale = transform.dvalue_dpos_at(&Value::new(x, y));
+                let decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+                let text = if item.name().is_empty() {
+                    format!("{:.*}", decimals, y)
+                } else {
+                    format!("{}: {:.*}", item.name(), decimals, y)
+                };
+                let marker = transform.position_from_value(&Value::new(x, y));
+                shapes.push(Shape::circle_filled(marker, 3.0, item.color()));
+                Some(
+                    ui.fonts()
+                        .layout_no_wrap(text, font_id.clone(), item.color()),
+                )
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+
+        let padding = vec2(4.0, 2.0);
+        let size = rows.iter().fold(Vec2::ZERO, |size, galley| {
+            vec2(size.x.max(galley.size().x), size.y + galley.size().y)
+        }) + 2.0 * padding;
+        let left = if pos_x + 4.0 + size.x <= frame.right() {
+            pos_x + 4.0
+        } else {
+            pos_x - 4.0 - size.x
+        };
+        let rect = Rect::from_min_size(pos2(left, frame.top() + 4.0), size);
+        shapes.push(Shape::Rect(epaint::RectShape {
+            rect,
+            rounding: Rounding::same(2.0),
+            fill: ui.visuals().extreme_bg_color.linear_multiply(0.8),
+            stroke: ui.visuals().widgets.noninteractive.bg_stroke,
+        }));
+        let mut pos = rect.min + padding;
+        for galley in rows {
+            let height = galley.size().y;
+            shapes.push(Shape::galley(pos, galley));
+            pos.y += height;
         }
     }
 
@@ -1264,33 +2782,68 @@ impl PreparedPlot {
 
         let interact_radius_sq: f32 = (16.0f32).powi(2);
 
-        let candidates = items.iter().filter_map(|item| {
+        let candidates = items.iter().filter_map(|(y_axis, item)| {
             let item = &**item;
+            let transform = self.transform_for(*y_axis);
             let closest = item.find_closest(pointer, transform);
 
-            Some(item).zip(closest)
+            Some((item, transform)).zip(closest)
         });
 
         let closest = candidates
             .min_by_key(|(_, elem)| elem.dist_sq.ord())
             .filter(|(_, elem)| elem.dist_sq <= interact_radius_sq);
 
-        let plot = items::PlotConfig {
-            ui,
-            transform,
-            show_x: *show_x,
-            show_y: *show_y,
-        };
-
-        if let Some((item, elem)) = closest {
+        if let Some(((item, transform), elem)) = closest {
+            // Report the values on the y axis the item is plotted against.
+            let plot = items::PlotConfig {
+                ui,
+                transform,
+                show_x: *show_x,
+                show_y: *show_y,
+            };
             item.on_hover(elem, shapes, &plot, label_formatter);
         } else {
+            let plot = items::PlotConfig {
+                ui,
+                transform,
+                show_x: *show_x,
+                show_y: *show_y,
+            };
             let value = transform.value_from_position(pointer);
             items::rulers_at_value(pointer, value, "", &plot, shapes, label_formatter);
         }
     }
 }
 
+/// The color of grid lines and labels with the given opacity.
+fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
+    if ui.visuals().dark_mode {
+        Rgba::from_white_alpha(alpha).into()
+    } else {
+        Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
+    }
+}
+
+/// Tick label for a non-linear axis, where values span many orders of magnitude.
+///
+/// Shows about four significant digits, switching to scientific notation for very large or
+/// small values, e.g. `0.002`, `150`, `1e6`.
+fn format_scaled_tick(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_owned();
+    }
+
+    let exponent = value.abs().log10().floor() as i32;
+    if (-4..5).contains(&exponent) {
+        let decimals = (3 - exponent).max(0) as usize;
+        emath::round_to_decimals(value, decimals).to_string()
+    } else {
+        let mantissa = emath::round_to_decimals(value / 10_f64.powi(exponent), 3);
+        format!("{}e{}", mantissa, exponent)
+    }
+}
+
 /// Returns next bigger power in given base
 /// e.g.
 /// ```ignore
@@ -1325,3 +2878,94 @@ fn fill_marks_between(out: &mut Vec<GridMark>, step_size: f64, (min, max): (f64,
     });
     out.extend(marks_iter);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a frame of a 400x400 screen with the `plot` in it, and return what `build` returns.
+    fn run_plot<R>(
+        ctx: &Context,
+        plot: Plot,
+        events: Vec<Event>,
+        build: impl FnOnce(&mut PlotUi) -> R,
+    ) -> R {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            events,
+            ..Default::default()
+        };
+        let mut result = None;
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                plot.show(ui, |plot_ui| result = Some(build(plot_ui)));
+            });
+        });
+        result.expect("the plot is shown")
+    }
+
+    fn button(pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn drag_handle_follows_pointer() {
+        let ctx = Context::default();
+        let run = |events| {
+            let plot = Plot::new("plot")
+                .include_x(-1.0)
+                .include_x(2.0)
+                .include_y(-1.0)
+                .include_y(2.0);
+            run_plot(&ctx, plot, events, |plot_ui| {
+                let values = vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)];
+                let drag = plot_ui.drag_handles(DragHandles::new("handles", values));
+                (drag, plot_ui.screen_from_plot(Value::new(1.0, 1.0)))
+            })
+        };
+
+        run(vec![]);
+        let (_, handle) = run(vec![]);
+        assert_eq!(run(vec![Event::PointerMoved(handle)]).0, None);
+
+        // Pick up the second handle and drag it to the right:
+        run(vec![button(handle, true)]);
+        let (drag, _) = run(vec![Event::PointerMoved(handle + vec2(40.0, 0.0))]);
+        let drag = drag.expect("the handle is dragged");
+        assert_eq!(drag.index, 1);
+        assert!(drag.value.x > 1.0);
+        assert!((drag.value.y - 1.0).abs() < 1e-6);
+
+        // Nothing is dragged once the button is released:
+        run(vec![button(handle + vec2(40.0, 0.0), false)]);
+        assert_eq!(run(vec![Event::PointerMoved(handle)]).0, None);
+    }
+
+    #[test]
+    fn pie_on_secondary_axis_is_clicked() {
+        let ctx = Context::default();
+        let run = |events| {
+            let plot = Plot::new("plot").include_y(-1.0).include_y(1.0);
+            run_plot(&ctx, plot, events, |plot_ui| {
+                plot_ui.set_y_axis(YAxis::Secondary);
+                let slices = vec![PieSlice::new(1.0)];
+                let pie = PieChart::new("pie", slices)
+                    .center(Value::new(0.0, 100.0))
+                    .radius(10.0);
+                let clicked = plot_ui.pie_chart(pie);
+                (clicked, plot_ui.screen_from_plot(Value::new(0.0, 105.0)))
+            })
+        };
+
+        run(vec![]);
+        let (_, slice) = run(vec![]);
+        run(vec![Event::PointerMoved(slice), button(slice, true)]);
+        let (clicked, _) = run(vec![button(slice, false)]);
+        assert_eq!(clicked, Some(0));
+    }
+}
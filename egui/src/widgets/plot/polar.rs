@@ -0,0 +1,315 @@
+//! Plots of values given in polar coordinates, as `(angle, radius)`.
+
+use crate::*;
+
+use super::{
+    color_from_alpha, AxisFormatterFn, GridInput, GridSpacer, PlotBounds, ScreenTransform, Tick,
+    Value, MIN_LINE_SPACING_IN_POINTS,
+};
+
+/// The smallest angle in degrees between the spokes of the grid, to keep their number in check.
+const MIN_SPOKE_STEP: f64 = 0.1;
+
+/// How a polar [`Plot`](super::Plot) maps angles to directions, see
+/// [`Plot::polar`](super::Plot::polar).
+///
+/// Angles are given in degrees. By default, `0°` points to the right and angles grow
+/// counterclockwise, as in mathematics.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolarAxes {
+    zero_angle: f64,
+    clockwise: bool,
+    spoke_step: f64,
+}
+
+impl Default for PolarAxes {
+    fn default() -> Self {
+        Self {
+            zero_angle: 0.0,
+            clockwise: false,
+            spoke_step: 30.0,
+        }
+    }
+}
+
+impl PolarAxes {
+    /// `0°` points up and angles grow clockwise, as on a compass.
+    pub fn compass() -> Self {
+        Self::default().zero_angle(90.0).clockwise(true)
+    }
+
+    /// The direction of `0°`, as an angle in degrees counterclockwise from the right.
+    pub fn zero_angle(mut self, degrees: f64) -> Self {
+        self.zero_angle = degrees;
+        self
+    }
+
+    /// Whether angles grow clockwise. Default: `false`.
+    pub fn clockwise(mut self, clockwise: bool) -> Self {
+        self.clockwise = clockwise;
+        self
+    }
+
+    /// Degrees between the spokes of the grid, at least `0.1`. Default: `30.0`.
+    pub fn spoke_step(mut self, degrees: f64) -> Self {
+        self.spoke_step = degrees.at_least(MIN_SPOKE_STEP);
+        self
+    }
+
+    /// The direction of `angle` in radians, counterclockwise from the right.
+    fn direction(&self, angle: f64) -> f64 {
+        let angle = if self.clockwise { -angle } else { angle };
+        (self.zero_angle + angle).to_radians()
+    }
+
+    /// The point in the plane of an `(angle, radius)` value.
+    pub(super) fn point_from_value(&self, value: &Value) -> Value {
+        let direction = self.direction(value.x);
+        Value::new(value.y * direction.cos(), value.y * direction.sin())
+    }
+
+    /// The `(angle, radius)` value of a point in the plane, with the angle in `0.0..360.0`.
+    pub(super) fn value_from_point(&self, point: &Value) -> Value {
+        let direction = point.y.atan2(point.x).to_degrees() - self.zero_angle;
+        let angle = if self.clockwise {
+            -direction
+        } else {
+            direction
+        };
+        Value::new(angle.rem_euclid(360.0), point.x.hypot(point.y))
+    }
+
+    /// Bounds in the plane around the origin, large enough for all radii within `bounds`
+    /// of `(angle, radius)` values.
+    pub(super) fn point_bounds(bounds: &PlotBounds) -> PlotBounds {
+        let radius = bounds.min[1].abs().max(bounds.max[1].abs());
+        if radius.is_finite() {
+            PlotBounds::new_symmetrical(radius)
+        } else {
+            PlotBounds::NOTHING
+        }
+    }
+}
+
+/// Paint rings at the radii given by the `spacer`, and spokes every [`PolarAxes::spoke_step`]
+/// degrees, labeled with the `formatters` for the angle and the radius.
+pub(super) fn paint_grid(
+    ui: &Ui,
+    polar: &PolarAxes,
+    transform: &ScreenTransform,
+    spacer: &GridSpacer,
+    formatters: [Option<&AxisFormatterFn>; 2],
+    shapes: &mut Vec<Shape>,
+) {
+    let frame = *transform.frame();
+    let center = transform.position_from_point(&Value::new(0.0, 0.0));
+    // Polar plots keep an aspect ratio of one, so both axes have the same scale.
+    let dpos_dvalue = transform.dpos_dvalue_x();
+
+    // The visible radii, from the closest to the farthest point of the frame.
+    let max_radius = [
+        frame.left_top(),
+        frame.right_top(),
+        frame.left_bottom(),
+        frame.right_bottom(),
+    ]
+    .iter()
+    .map(|corner| corner.distance(center))
+    .fold(0.0, f32::max) as f64
+        / dpos_dvalue;
+    let min_radius = (center - frame.clamp(center)).length() as f64 / dpos_dvalue;
+    if max_radius <= min_radius {
+        return;
+    }
+    let radius_range = min_radius..=max_radius;
+
+    let font_id = TextStyle::Body.resolve(ui.style());
+    let input = GridInput {
+        bounds: (min_radius, max_radius),
+        base_step_size: MIN_LINE_SPACING_IN_POINTS / dpos_dvalue,
+    };
+    let marks = spacer(input);
+    let major_step_size = marks.iter().map(|mark| mark.step_size).fold(0.0, f64::max);
+    for mark in marks {
+        if mark.value <= 0.0 {
+            continue;
+        }
+        let spacing_in_points = (dpos_dvalue * mark.step_size) as f32;
+        let line_alpha = remap_clamp(
+            spacing_in_points,
+            (MIN_LINE_SPACING_IN_POINTS as f32)..=300.0,
+            0.0..=0.15,
+        );
+        if line_alpha > 0.0 {
+            shapes.push(Shape::circle_stroke(
+                center,
+                (mark.value * dpos_dvalue) as f32,
+                Stroke::new(1.0, color_from_alpha(ui, line_alpha)),
+            ));
+        }
+
+        // The radii are labeled along the spoke of `0°`.
+        let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
+        if text_alpha > 0.0 {
+            let text = match formatters[1] {
+                Some(formatter) => formatter(
+                    Tick {
+                        value: mark.value,
+                        step_size: mark.step_size,
+                        major: mark.step_size == major_step_size,
+                    },
+                    &radius_range,
+                ),
+                None => emath::round_to_decimals(mark.value, 5).to_string(),
+            };
+            if !text.is_empty() {
+                let pos = transform.position_from_value(&Value::new(0.0, mark.value));
+                let galley = ui.painter().layout_no_wrap(
+                    text,
+                    font_id.clone(),
+                    color_from_alpha(ui, text_alpha),
+                );
+                shapes.push(Shape::galley(pos + vec2(2.0, -galley.size().y), galley));
+            }
+        }
+    }
+
+    // Deserialized axes can skip the clamping in `PolarAxes::spoke_step`:
+    let spoke_step = polar.spoke_step.at_least(MIN_SPOKE_STEP);
+    let spokes = (360.0 / spoke_step).ceil() as usize;
+    let angle_range = 0.0..=360.0;
+    for spoke in 0..spokes {
+        let angle = spoke as f64 * spoke_step;
+        let direction = polar.direction(angle);
+        let dir = vec2(direction.cos() as f32, -direction.sin() as f32);
+        let (entry, exit) = match ray_in_rect(center, dir, frame) {
+            Some(range) => range,
+            None => continue,
+        };
+        shapes.push(Shape::line_segment(
+            [center + entry * dir, center + exit * dir],
+            Stroke::new(1.0, color_from_alpha(ui, 0.15)),
+        ));
+
+        // The angles are labeled close to the edge of the frame.
+        let text = match formatters[0] {
+            Some(formatter) => formatter(
+                Tick {
+                    value: angle,
+                    step_size: spoke_step,
+                    major: true,
+                },
+                &angle_range,
+            ),
+            None => format!("{}°", emath::round_to_decimals(angle, 3)),
+        };
+        if text.is_empty() {
+            continue;
+        }
+        let galley = ui
+            .painter()
+            .layout_no_wrap(text, font_id.clone(), color_from_alpha(ui, 0.4));
+        let inset = galley.size().length() / 2.0 + 2.0;
+        if exit - inset >= entry {
+            let pos = center + (exit - inset) * dir - galley.size() / 2.0;
+            shapes.push(Shape::galley(pos, galley));
+        }
+    }
+}
+
+/// The distances along the ray from `origin` in the direction `dir` at which it enters and
+/// leaves the `rect`, if it crosses it.
+fn ray_in_rect(origin: Pos2, dir: Vec2, rect: Rect) -> Option<(f32, f32)> {
+    let mut entry = 0.0_f32;
+    let mut exit = f32::INFINITY;
+    for axis in 0..2 {
+        if dir[axis] == 0.0 {
+            if !(rect.min[axis]..=rect.max[axis]).contains(&origin[axis]) {
+                return None;
+            }
+            continue;
+        }
+        let to_min = (rect.min[axis] - origin[axis]) / dir[axis];
+        let to_max = (rect.max[axis] - origin[axis]) / dir[axis];
+        entry = entry.max(to_min.min(to_max));
+        exit = exit.min(to_min.max(to_max));
+    }
+    (entry < exit).then(|| (entry, exit))
+}
+
+/// A circle through the pointer and a spoke from the origin to the pointer.
+pub(super) fn rulers(
+    pointer: Pos2,
+    transform: &ScreenTransform,
+    show: [bool; 2],
+    line_color: Color32,
+    shapes: &mut Vec<Shape>,
+) {
+    let center = transform.position_from_point(&Value::new(0.0, 0.0));
+    if show[0] {
+        let dir = (pointer - center).normalized();
+        if let Some((entry, exit)) = ray_in_rect(center, dir, *transform.frame()) {
+            shapes.push(Shape::line_segment(
+                [center + entry * dir, center + exit * dir],
+                (1.0, line_color),
+            ));
+        }
+    }
+    if show[1] {
+        shapes.push(Shape::circle_stroke(
+            center,
+            pointer.distance(center),
+            (1.0, line_color),
+        ));
+    }
+}
+
+/// The hover readout of an `(angle, radius)` value, e.g. `θ = 45.0°` and `r = 1.25`.
+pub(super) fn format_value(value: &Value, transform: &ScreenTransform, show: [bool; 2]) -> String {
+    let dvalue_dpos = transform.dvalue_dpos()[0].abs();
+    let decimals = |step: f64| ((-step.log10()).ceil().at_least(0.0) as usize).at_most(6);
+    let mut lines = Vec::new();
+    if show[0] {
+        // The angle between neighboring points on the screen shrinks with the radius.
+        let angle_step = (dvalue_dpos / value.y.abs()).to_degrees();
+        lines.push(format!("θ = {:.*}°", decimals(angle_step), value.x));
+    }
+    if show[1] {
+        lines.push(format!("r = {:.*}", decimals(dvalue_dpos), value.y));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polar_round_trip() {
+        for polar in [PolarAxes::default(), PolarAxes::compass()] {
+            for value in [Value::new(30.0, 2.0), Value::new(300.0, 0.5)] {
+                let round_trip = polar.value_from_point(&polar.point_from_value(&value));
+                assert!((round_trip.x - value.x).abs() < 1e-9);
+                assert!((round_trip.y - value.y).abs() < 1e-9);
+            }
+        }
+
+        // On a compass, 90° points to the right.
+        let east = PolarAxes::compass().point_from_value(&Value::new(90.0, 1.0));
+        assert!((east.x - 1.0).abs() < 1e-9 && east.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn spoke_step_is_clamped() {
+        assert_eq!(
+            PolarAxes::default().spoke_step(1e-6).spoke_step,
+            MIN_SPOKE_STEP
+        );
+        assert_eq!(
+            PolarAxes::default().spoke_step(-5.0).spoke_step,
+            MIN_SPOKE_STEP
+        );
+        assert_eq!(PolarAxes::default().spoke_step(45.0).spoke_step, 45.0);
+    }
+}
@@ -0,0 +1,273 @@
+//! Selecting a part of a plot by dragging out a range or a rectangle.
+
+use std::ops::RangeInclusive;
+
+use crate::*;
+
+use super::{PlotBounds, ScreenTransform, Value};
+
+/// How far from an edge of the selection, in points, the pointer can grab it.
+const EDGE_GRAB_RADIUS: f32 = 5.0;
+
+/// What the user selects by dragging in a [`Plot`](super::Plot), see
+/// [`Plot::selection`](super::Plot::selection).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// A range of x values, over the full height of the plot.
+    XRange,
+
+    /// A rectangle of x and y values.
+    Rect,
+}
+
+/// A part of a plot selected by the user, see [`Plot::selection`](super::Plot::selection).
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlotSelection {
+    x: [f64; 2],
+    /// `None` for a selection of an x range.
+    y: Option<[f64; 2]>,
+}
+
+impl PlotSelection {
+    /// Select the x values in `range`, over the full height of the plot.
+    pub fn from_x_range(range: RangeInclusive<f64>) -> Self {
+        Self {
+            x: sorted(*range.start(), *range.end()),
+            y: None,
+        }
+    }
+
+    /// Select the values inside of `bounds`.
+    pub fn from_bounds(bounds: PlotBounds) -> Self {
+        Self {
+            x: [bounds.min[0], bounds.max[0]],
+            y: Some([bounds.min[1], bounds.max[1]]),
+        }
+    }
+
+    /// The selected x values.
+    pub fn x_range(&self) -> RangeInclusive<f64> {
+        self.x[0]..=self.x[1]
+    }
+
+    /// The selected y values, or `None` if all of them are selected.
+    pub fn y_range(&self) -> Option<RangeInclusive<f64>> {
+        self.y.map(|[min, max]| min..=max)
+    }
+
+    /// Whether the value lies inside of the selection, including its edges.
+    pub fn contains(&self, value: &Value) -> bool {
+        let in_range = |value: f64, [min, max]: [f64; 2]| min <= value && value <= max;
+        in_range(value.x, self.x) && self.y.map_or(true, |y| in_range(value.y, y))
+    }
+
+    /// The selection with its y range converted from one y axis to another, through the screen
+    /// positions of both.
+    pub(super) fn with_y_axis(&self, from: &ScreenTransform, to: &ScreenTransform) -> Self {
+        let y = self.y.map(|y| {
+            let convert = |y| {
+                let pos = from.position_from_value(&Value::new(self.x[0], y));
+                to.value_from_position(pos).y
+            };
+            sorted(convert(y[0]), convert(y[1]))
+        });
+        Self { x: self.x, y }
+    }
+
+    /// The selected area on the screen.
+    pub(super) fn screen_rect(&self, transform: &ScreenTransform) -> Rect {
+        let frame = transform.frame();
+        let [y_min, y_max] = self
+            .y
+            .unwrap_or([transform.bounds().min[1], transform.bounds().max[1]]);
+        let rect = Rect::from_two_pos(
+            transform.position_from_value(&Value::new(self.x[0], y_min)),
+            transform.position_from_value(&Value::new(self.x[1], y_max)),
+        );
+        if self.y.is_some() {
+            rect
+        } else {
+            Rect::from_x_y_ranges(rect.x_range(), frame.y_range())
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.x[0] >= self.x[1] || self.y.map_or(false, |y| y[0] >= y[1])
+    }
+}
+
+/// The part of the selection that is being dragged, remembered between frames.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug)]
+pub(super) struct SelectionDrag {
+    /// The corner of the selection that stays in place.
+    anchor: [f64; 2],
+
+    /// The grabbed edges when resizing the selection (see [`grabbed_edges`]), or `None` when
+    /// dragging out a new one.
+    edges: Option<[Option<usize>; 2]>,
+}
+
+/// Creates and resizes the selection with the `button`. A click clears it, as it drags out an
+/// empty selection. Returns the cursor to show, if the pointer is used for the selection.
+pub(super) fn interact(
+    mode: SelectionMode,
+    button: PointerButton,
+    response: &Response,
+    transform: &ScreenTransform,
+    selection: &mut Option<PlotSelection>,
+    drag: &mut Option<SelectionDrag>,
+) -> Option<CursorIcon> {
+    let pointer = response
+        .interact_pointer_pos()
+        .or_else(|| response.hover_pos());
+    let grabbed = match (*selection, pointer) {
+        (Some(selection), Some(pointer)) => grabbed_edges(&selection, transform, pointer),
+        _ => [None; 2],
+    };
+
+    if response.drag_started() && response.dragged_by(button) {
+        match (*selection, pointer) {
+            (Some(current), _) if grabbed != [None; 2] => {
+                // Keep the opposite edges in place.
+                let mut anchor = [0.0; 2];
+                for (axis, range) in [Some(current.x), current.y].into_iter().enumerate() {
+                    if let (Some(range), Some(edge)) = (range, grabbed[axis]) {
+                        anchor[axis] = range[1 - edge];
+                    }
+                }
+                *drag = Some(SelectionDrag {
+                    anchor,
+                    edges: Some(grabbed),
+                });
+            }
+            (_, Some(pointer)) => {
+                let value = transform.value_from_position(pointer);
+                *drag = Some(SelectionDrag {
+                    anchor: [value.x, value.y],
+                    edges: None,
+                });
+                *selection = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(SelectionDrag { anchor, edges }) = *drag {
+        if response.dragged_by(button) {
+            if let Some(pointer) = pointer {
+                let value = transform.value_from_position(pointer);
+                let current = selection.unwrap_or(PlotSelection {
+                    x: [anchor[0]; 2],
+                    y: (mode == SelectionMode::Rect).then(|| [anchor[1]; 2]),
+                });
+                let follows = edges.map_or([true; 2], |edges| edges.map(|edge| edge.is_some()));
+                *selection = Some(PlotSelection {
+                    x: if follows[0] {
+                        sorted(anchor[0], value.x)
+                    } else {
+                        current.x
+                    },
+                    y: match current.y {
+                        Some(_) if follows[1] => Some(sorted(anchor[1], value.y)),
+                        y => y,
+                    },
+                });
+            }
+        } else {
+            *drag = None;
+            if selection.map_or(false, |selection| selection.is_empty()) {
+                *selection = None;
+            }
+        }
+    }
+
+    match drag {
+        Some(SelectionDrag {
+            edges: Some(edges), ..
+        }) => edge_cursor(*edges),
+        Some(SelectionDrag { edges: None, .. }) => Some(CursorIcon::Crosshair),
+        None if response.hovered() => edge_cursor(grabbed),
+        None => None,
+    }
+}
+
+fn edge_cursor(edges: [Option<usize>; 2]) -> Option<CursorIcon> {
+    match edges {
+        // The lower left or the upper right corner.
+        [Some(x), Some(y)] if x == y => Some(CursorIcon::ResizeNeSw),
+        [Some(_), Some(_)] => Some(CursorIcon::ResizeNwSe),
+        [Some(_), None] => Some(CursorIcon::ResizeHorizontal),
+        [None, Some(_)] => Some(CursorIcon::ResizeVertical),
+        [None, None] => None,
+    }
+}
+
+/// Which x and y edges of the selection are under the pointer: `0` for the lower, `1` for the
+/// upper one.
+fn grabbed_edges(
+    selection: &PlotSelection,
+    transform: &ScreenTransform,
+    pointer: Pos2,
+) -> [Option<usize>; 2] {
+    let rect = selection.screen_rect(transform);
+    if !rect.expand(EDGE_GRAB_RADIUS).contains(pointer) {
+        return [None; 2];
+    }
+    let closest = |lower: f32, upper: f32, pos: f32| {
+        if (pos - lower).abs() <= EDGE_GRAB_RADIUS {
+            Some(lower)
+        } else if (pos - upper).abs() <= EDGE_GRAB_RADIUS {
+            Some(upper)
+        } else {
+            None
+        }
+    };
+
+    // On the screen, the lower x value is on the left, but the lower y value at the bottom.
+    let x_edge =
+        closest(rect.left(), rect.right(), pointer.x)
+            .map(|edge| if edge == rect.left() { 0 } else { 1 });
+    let y_edge = if selection.y.is_some() {
+        closest(rect.bottom(), rect.top(), pointer.y)
+            .map(|edge| if edge == rect.bottom() { 0 } else { 1 })
+    } else {
+        None
+    };
+    [x_edge, y_edge]
+}
+
+/// Paint the selection as a shaded area with a border.
+pub(super) fn paint(ui: &Ui, selection: &PlotSelection, transform: &ScreenTransform) {
+    let rect = selection.screen_rect(transform);
+    let visuals = &ui.visuals().selection;
+    ui.painter().with_clip_rect(*transform.frame()).rect(
+        rect,
+        0.0,
+        visuals.bg_fill.linear_multiply(0.3),
+        visuals.stroke,
+    );
+}
+
+fn sorted(a: f64, b: f64) -> [f64; 2] {
+    [a.min(b), a.max(b)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_contains_values() {
+        let range = PlotSelection::from_x_range(3.0..=1.0);
+        assert_eq!(range.x_range(), 1.0..=3.0);
+        assert_eq!(range.y_range(), None);
+        assert!(range.contains(&Value::new(2.0, 1e9)));
+        assert!(!range.contains(&Value::new(3.5, 0.0)));
+
+        let rect = PlotSelection::from_bounds(PlotBounds::from_min_max([0.0, 0.0], [1.0, 2.0]));
+        assert!(rect.contains(&Value::new(1.0, 2.0)));
+        assert!(!rect.contains(&Value::new(0.5, 3.0)));
+    }
+}
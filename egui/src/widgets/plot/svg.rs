@@ -0,0 +1,365 @@
+//! Writing the shapes of a plot as an SVG document.
+
+use std::fmt::Write as _;
+
+use epaint::{CircleShape, PathShape, RectShape, TextShape};
+
+use crate::*;
+
+/// An SVG document of the given size, with `frame_shapes` clipped to `frame` and `shapes`
+/// painted on top of them without clipping.
+pub(super) fn document(
+    size: Vec2,
+    frame: Rect,
+    frame_shapes: &[Shape],
+    shapes: &[Shape],
+) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = num(size.x),
+        h = num(size.y),
+    )
+    .ok();
+    writeln!(
+        svg,
+        r#"<defs><clipPath id="plot-frame"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath></defs>"#,
+        num(frame.left()),
+        num(frame.top()),
+        num(frame.width()),
+        num(frame.height()),
+    )
+    .ok();
+
+    svg.push_str("<g clip-path=\"url(#plot-frame)\">\n");
+    for shape in frame_shapes {
+        write_shape(&mut svg, shape);
+    }
+    svg.push_str("</g>\n");
+    for shape in shapes {
+        write_shape(&mut svg, shape);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Noop | Shape::Callback(_) => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                write_shape(svg, shape);
+            }
+        }
+        Shape::Circle(CircleShape {
+            center,
+            radius,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}"{}{}/>"#,
+                num(center.x),
+                num(center.y),
+                num(*radius),
+                fill_attributes(*fill),
+                stroke_attributes(*stroke),
+            )
+            .ok();
+        }
+        Shape::LineSegment { points, stroke } => {
+            writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{}/>"#,
+                num(points[0].x),
+                num(points[0].y),
+                num(points[1].x),
+                num(points[1].y),
+                stroke_attributes(*stroke),
+            )
+            .ok();
+        }
+        Shape::Path(PathShape {
+            points,
+            closed,
+            fill,
+            stroke,
+        }) => {
+            let element = if *closed { "polygon" } else { "polyline" };
+            let fill = if *closed { *fill } else { Color32::TRANSPARENT };
+            writeln!(
+                svg,
+                r#"<{} points="{}"{}{}/>"#,
+                element,
+                points_attribute(points),
+                fill_attributes(fill),
+                stroke_attributes(*stroke),
+            )
+            .ok();
+        }
+        Shape::Rect(RectShape {
+            rect,
+            rounding,
+            fill,
+            stroke,
+        }) => {
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}"{}{}/>"#,
+                num(rect.left()),
+                num(rect.top()),
+                num(rect.width()),
+                num(rect.height()),
+                num(rounding.nw),
+                fill_attributes(*fill),
+                stroke_attributes(*stroke),
+            )
+            .ok();
+        }
+        Shape::Text(text_shape) => write_text(svg, text_shape),
+        Shape::Mesh(mesh) => {
+            // Textured meshes, like images, can't be represented without the texture.
+            if mesh.texture_id != TextureId::default() {
+                return;
+            }
+            for triangle in mesh.indices.chunks_exact(3) {
+                let vertices = triangle.iter().map(|&index| &mesh.vertices[index as usize]);
+                let points: Vec<Pos2> = vertices.clone().map(|vertex| vertex.pos).collect();
+                let color = vertices
+                    .map(|vertex| vertex.color)
+                    .next()
+                    .unwrap_or_default();
+                writeln!(
+                    svg,
+                    r#"<polygon points="{}"{}/>"#,
+                    points_attribute(&points),
+                    fill_attributes(color),
+                )
+                .ok();
+            }
+        }
+        Shape::QuadraticBezier(bezier) => {
+            let [p0, p1, p2] = bezier.points;
+            write_path(
+                svg,
+                &format!(
+                    "M {} {} Q {} {} {} {}",
+                    num(p0.x),
+                    num(p0.y),
+                    num(p1.x),
+                    num(p1.y),
+                    num(p2.x),
+                    num(p2.y)
+                ),
+                bezier.closed,
+                bezier.fill,
+                bezier.stroke,
+            );
+        }
+        Shape::CubicBezier(bezier) => {
+            let [p0, p1, p2, p3] = bezier.points;
+            write_path(
+                svg,
+                &format!(
+                    "M {} {} C {} {} {} {} {} {}",
+                    num(p0.x),
+                    num(p0.y),
+                    num(p1.x),
+                    num(p1.y),
+                    num(p2.x),
+                    num(p2.y),
+                    num(p3.x),
+                    num(p3.y)
+                ),
+                bezier.closed,
+                bezier.fill,
+                bezier.stroke,
+            );
+        }
+    }
+}
+
+fn write_path(svg: &mut String, path: &str, closed: bool, fill: Color32, stroke: Stroke) {
+    let (path, fill) = if closed {
+        (format!("{} Z", path), fill)
+    } else {
+        (path.to_owned(), Color32::TRANSPARENT)
+    };
+    writeln!(
+        svg,
+        r#"<path d="{}"{}{}/>"#,
+        path,
+        fill_attributes(fill),
+        stroke_attributes(stroke),
+    )
+    .ok();
+}
+
+/// One `<text>` element per row of the galley, in the color of its first glyph.
+fn write_text(svg: &mut String, text_shape: &TextShape) {
+    let TextShape {
+        pos,
+        galley,
+        override_text_color,
+        angle,
+        ..
+    } = text_shape;
+
+    let rotation = if *angle == 0.0 {
+        String::new()
+    } else {
+        format!(
+            r#" transform="rotate({} {} {})""#,
+            num(angle.to_degrees()),
+            num(pos.x),
+            num(pos.y)
+        )
+    };
+
+    for row in &galley.rows {
+        let first_glyph = match row.glyphs.first() {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let format = &galley.job.sections[first_glyph.section_index as usize].format;
+        let color = override_text_color.unwrap_or(format.color);
+        let family = match format.font_id.family {
+            FontFamily::Monospace => "monospace",
+            _ => "sans-serif",
+        };
+        let text: String = row.glyphs.iter().map(|glyph| glyph.chr).collect();
+
+        // The glyph positions are the top of the row, the baseline is one font size further down.
+        let baseline = pos2(
+            pos.x + first_glyph.pos.x,
+            pos.y + first_glyph.pos.y + format.font_id.size,
+        );
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" xml:space="preserve"{}{}>{}</text>"#,
+            num(baseline.x),
+            num(baseline.y),
+            family,
+            num(format.font_id.size),
+            fill_attributes(color),
+            rotation,
+            escape(&text),
+        )
+        .ok();
+    }
+}
+
+fn points_attribute(points: &[Pos2]) -> String {
+    let mut attribute = String::new();
+    for point in points {
+        if !attribute.is_empty() {
+            attribute.push(' ');
+        }
+        write!(attribute, "{},{}", num(point.x), num(point.y)).ok();
+    }
+    attribute
+}
+
+fn fill_attributes(color: Color32) -> String {
+    match svg_color(color) {
+        Some((rgb, 255)) => format!(r#" fill="{}""#, rgb),
+        Some((rgb, alpha)) => format!(r#" fill="{}" fill-opacity="{}""#, rgb, opacity(alpha)),
+        None => r#" fill="none""#.to_owned(),
+    }
+}
+
+fn stroke_attributes(stroke: Stroke) -> String {
+    if stroke.width <= 0.0 {
+        return String::new();
+    }
+    let width = num(stroke.width);
+    match svg_color(stroke.color) {
+        Some((rgb, 255)) => format!(r#" stroke="{}" stroke-width="{}""#, rgb, width),
+        Some((rgb, alpha)) => format!(
+            r#" stroke="{}" stroke-opacity="{}" stroke-width="{}""#,
+            rgb,
+            opacity(alpha),
+            width
+        ),
+        None => String::new(),
+    }
+}
+
+/// The color as `#rrggbb` and its alpha, or `None` if it is fully transparent.
+///
+/// Additive colors (zero alpha, but some color) are shown as opaque.
+fn svg_color(color: Color32) -> Option<(String, u8)> {
+    if color == Color32::TRANSPARENT {
+        return None;
+    }
+    let [r, g, b, a] = if color.a() == 0 {
+        [color.r(), color.g(), color.b(), 255]
+    } else {
+        color.to_srgba_unmultiplied()
+    };
+    Some((format!("#{:02x}{:02x}{:02x}", r, g, b), a))
+}
+
+fn opacity(alpha: u8) -> String {
+    num(alpha as f32 / 255.0)
+}
+
+/// A coordinate with at most two decimals.
+fn num(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_owned()
+    } else {
+        text.to_owned()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for chr in text.chars() {
+        match chr {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(chr),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_shapes() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(-0.001), "0");
+        assert_eq!(num(2.345), "2.35");
+
+        let shapes = vec![
+            Shape::line_segment(
+                [pos2(0.0, 0.0), pos2(10.0, 5.5)],
+                Stroke::new(1.0, Color32::RED),
+            ),
+            Shape::circle_filled(pos2(1.0, 2.0), 3.0, Color32::from_white_alpha(128)),
+        ];
+        let svg = document(
+            vec2(100.0, 50.0),
+            Rect::from_min_size(Pos2::ZERO, vec2(100.0, 50.0)),
+            &shapes,
+            &[],
+        );
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(
+            r##"<line x1="0" y1="0" x2="10" y2="5.5" stroke="#ff0000" stroke-width="1"/>"##
+        ));
+        assert!(
+            svg.contains(r##"<circle cx="1" cy="2" r="3" fill="#ffffff" fill-opacity="0.5"/>"##)
+        );
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Grid spacing and tick labels for axes showing dates and times.
+
+use std::ops::RangeInclusive;
+
+use epaint::util::FloatOrd;
+
+use super::{fill_marks_between, GridInput, GridMark, GridSpacer, Tick};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const WEEK: f64 = 7.0 * DAY;
+
+/// The average length of a year.
+const YEAR: f64 = 365.2425 * DAY;
+
+/// The average length of a month, used as the step size of monthly marks.
+const MONTH: f64 = YEAR / 12.0;
+
+/// The Unix epoch was on a Thursday, so the first Monday is four days later.
+const FIRST_MONDAY: f64 = 4.0 * DAY;
+
+#[derive(Clone, Copy)]
+enum TimeStep {
+    Seconds(f64),
+    Weeks,
+    Months(i64),
+}
+
+impl TimeStep {
+    /// Steps in increasing order. Grid lines of one step coincide with many lines of the
+    /// steps two and four places before it in this list.
+    const ALL: [TimeStep; 42] = [
+        Self::Seconds(0.001),
+        Self::Seconds(0.002),
+        Self::Seconds(0.005),
+        Self::Seconds(0.01),
+        Self::Seconds(0.02),
+        Self::Seconds(0.05),
+        Self::Seconds(0.1),
+        Self::Seconds(0.2),
+        Self::Seconds(0.5),
+        Self::Seconds(1.0),
+        Self::Seconds(2.0),
+        Self::Seconds(5.0),
+        Self::Seconds(10.0),
+        Self::Seconds(15.0),
+        Self::Seconds(30.0),
+        Self::Seconds(MINUTE),
+        Self::Seconds(2.0 * MINUTE),
+        Self::Seconds(5.0 * MINUTE),
+        Self::Seconds(10.0 * MINUTE),
+        Self::Seconds(15.0 * MINUTE),
+        Self::Seconds(30.0 * MINUTE),
+        Self::Seconds(HOUR),
+        Self::Seconds(2.0 * HOUR),
+        Self::Seconds(3.0 * HOUR),
+        Self::Seconds(6.0 * HOUR),
+        Self::Seconds(12.0 * HOUR),
+        Self::Seconds(DAY),
+        Self::Seconds(2.0 * DAY),
+        Self::Weeks,
+        Self::Months(1),
+        Self::Months(3),
+        Self::Months(6),
+        Self::Months(12),
+        Self::Months(2 * 12),
+        Self::Months(5 * 12),
+        Self::Months(10 * 12),
+        Self::Months(20 * 12),
+        Self::Months(50 * 12),
+        Self::Months(100 * 12),
+        Self::Months(200 * 12),
+        Self::Months(500 * 12),
+        Self::Months(1000 * 12),
+    ];
+
+    /// The (approximate) length of the step in seconds.
+    fn seconds(self) -> f64 {
+        match self {
+            Self::Seconds(seconds) => seconds,
+            Self::Weeks => WEEK,
+            Self::Months(months) => months as f64 * MONTH,
+        }
+    }
+
+    fn fill_marks(self, out: &mut Vec<GridMark>, (min, max): (f64, f64)) {
+        match self {
+            Self::Seconds(seconds) => fill_marks_between(out, seconds, (min, max)),
+            Self::Weeks => {
+                let start = ((min - FIRST_MONDAY) / WEEK).ceil() as i64;
+                let end = ((max - FIRST_MONDAY) / WEEK).ceil() as i64;
+                out.extend((start..end).map(|week| GridMark {
+                    value: FIRST_MONDAY + week as f64 * WEEK,
+                    step_size: WEEK,
+                }));
+            }
+            Self::Months(months) => {
+                let (year, month, _) = civil_from_days((min / DAY).floor() as i64);
+                let mut index = (year * 12 + month as i64 - 1).div_euclid(months) * months;
+                loop {
+                    let value =
+                        days_from_civil(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+                            as f64
+                            * DAY;
+                    if value >= max {
+                        break;
+                    }
+                    if value >= min {
+                        out.push(GridMark {
+                            value,
+                            step_size: self.seconds(),
+                        });
+                    }
+                    index += months;
+                }
+            }
+        }
+    }
+}
+
+/// Marks at human-friendly steps for axes showing time, e.g. every 15 seconds, every hour,
+/// every day or at the start of each month, depending on the zoom level.
+///
+/// The values along the axis are the seconds since the Unix epoch (1970-01-01 00:00:00 UTC).
+/// Marks are placed in UTC; to show local time, add the UTC offset to the values.
+///
+/// Each mark is only given once, with the largest step it falls on.
+///
+/// Use with [`Plot::x_grid_spacer`](super::Plot::x_grid_spacer) and a linear axis, and
+/// [`time_axis_formatter`] for matching labels:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{time_axis_formatter, time_grid_spacer, Line, Plot, Value, Values};
+/// let start = 1_655_000_000.0; // Seconds since the Unix epoch
+/// let temperature = (0..48).map(|hour| Value::new(start + hour as f64 * 3600.0, hour % 24));
+/// Plot::new("temperature")
+///     .x_grid_spacer(time_grid_spacer())
+///     .x_axis_tick_formatter(time_axis_formatter())
+///     .show(ui, |plot_ui| plot_ui.line(Line::new(Values::from_values_iter(temperature))));
+/// # });
+/// ```
+pub fn time_grid_spacer() -> GridSpacer {
+    let get_marks = |input: GridInput| -> Vec<GridMark> {
+        let base_step_size = input.base_step_size.abs();
+        let steps = match TimeStep::ALL
+            .iter()
+            .position(|step| step.seconds() >= base_step_size)
+        {
+            Some(smallest) => [0, 2, 4]
+                .map(|offset| TimeStep::ALL[(smallest + offset).min(TimeStep::ALL.len() - 1)]),
+            None => {
+                // Beyond the longest step, use powers of ten of years, like the default spacer:
+                let years = 10_f64.powf((base_step_size / YEAR).log10().ceil());
+                [1.0, 10.0, 100.0].map(|factor| TimeStep::Seconds(factor * years * YEAR))
+            }
+        };
+
+        // From the largest step down, so the formatter sees each value with its largest step.
+        let mut marks: Vec<GridMark> = vec![];
+        for step in steps.iter().rev() {
+            step.fill_marks(&mut marks, input.bounds);
+        }
+        // The sort is stable, so the first of equal values is the one with the largest step:
+        marks.sort_by_key(|mark| mark.value.ord());
+        marks.dedup_by(|mark, kept| mark.value == kept.value);
+        marks
+    };
+
+    Box::new(get_marks)
+}
+
+/// Tick labels for axes showing time, to be used with [`time_grid_spacer`] and
+/// [`Plot::x_axis_tick_formatter`](super::Plot::x_axis_tick_formatter).
+///
+/// The detail of a label follows the step of its mark: `2022`, `2022-06`, `2022-06-14`,
+/// `13:45`, `13:45:30` or `13:45:30.250`. Major marks that are closer than a day apart
+/// also show the date, e.g. `2022-06-14 13:00`, while the minor marks between them only
+/// show the time of day.
+///
+/// The values are the seconds since the Unix epoch, shown in UTC.
+pub fn time_axis_formatter() -> impl Fn(Tick, &RangeInclusive<f64>) -> String {
+    |tick, _range| format_time(tick)
+}
+
+/// Format seconds since the Unix epoch, with as much detail as the step of the tick needs.
+fn format_time(tick: Tick) -> String {
+    let millis = (tick.value * 1000.0).round() as i64;
+    let days = millis.div_euclid(1000 * DAY as i64);
+    let millis_of_day = millis.rem_euclid(1000 * DAY as i64);
+
+    let (year, month, day) = civil_from_days(days);
+    let date = format!("{}-{:02}-{:02}", year, month, day);
+    if tick.step_size >= 12.0 * MONTH {
+        return format!("{}", year);
+    } else if tick.step_size >= MONTH {
+        return format!("{}-{:02}", year, month);
+    } else if tick.step_size >= DAY {
+        return date;
+    }
+
+    let seconds_of_day = millis_of_day / 1000;
+    let (hours, minutes, seconds) = (
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+    );
+    let time = if tick.step_size < 1.0 {
+        format!(
+            "{:02}:{:02}:{:02}.{:03}",
+            hours,
+            minutes,
+            seconds,
+            millis_of_day % 1000
+        )
+    } else if tick.step_size < MINUTE {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", hours, minutes)
+    };
+    if tick.major {
+        format!("{} {}", date, time)
+    } else {
+        time
+    }
+}
+
+/// The number of days since the Unix epoch for a date in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`], returning `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_labels() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        for days in [-1_000_000, -1, 59, 11_016, 19_157, 1_000_000] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+
+        let june_14 = days_from_civil(2022, 6, 14) as f64 * DAY;
+        let tick = |value, step_size, major| Tick {
+            value,
+            step_size,
+            major,
+        };
+        assert_eq!(format_time(tick(june_14, DAY, false)), "2022-06-14");
+        assert_eq!(
+            format_time(tick(june_14 + 13.75 * HOUR, 900.0, false)),
+            "13:45"
+        );
+        assert_eq!(
+            format_time(tick(june_14 + 13.0 * HOUR, HOUR, true)),
+            "2022-06-14 13:00"
+        );
+        assert_eq!(
+            format_time(tick(june_14 + 30.25, 0.25, false)),
+            "00:00:30.250"
+        );
+        assert_eq!(
+            format_time(tick(june_14 - 13.0 * DAY, MONTH, true)),
+            "2022-06"
+        );
+    }
+
+    #[test]
+    fn time_marks_within_a_day() {
+        // Two hours of a day, with about a pixel per ten seconds.
+        let start = days_from_civil(2022, 6, 14) as f64 * DAY + 12.0 * HOUR;
+        let marks = time_grid_spacer()(GridInput {
+            bounds: (start, start + 2.0 * HOUR),
+            base_step_size: 60.0,
+        });
+
+        let mut values: Vec<_> = marks.iter().map(|mark| mark.value).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.dedup();
+        assert_eq!(values.len(), marks.len(), "each mark is only given once");
+
+        let major_step_size = marks.iter().map(|mark| mark.step_size).fold(0.0, f64::max);
+        let labels: Vec<_> = marks
+            .iter()
+            .map(|mark| {
+                format_time(Tick {
+                    value: mark.value,
+                    step_size: mark.step_size,
+                    major: mark.step_size == major_step_size,
+                })
+            })
+            .collect();
+        assert!(labels.contains(&"2022-06-14 13:00".to_owned()));
+        assert!(labels.contains(&"12:05".to_owned()));
+    }
+
+    #[test]
+    fn time_marks_far_zoomed_out() {
+        // A few billion years, beyond the longest step:
+        let marks = time_grid_spacer()(GridInput {
+            bounds: (-1e17, 1e17),
+            base_step_size: 1e15,
+        });
+        assert!(
+            !marks.is_empty() && marks.len() < 1000,
+            "{} marks",
+            marks.len()
+        );
+        assert!(marks.iter().all(|mark| mark.step_size >= 1e15));
+        assert!(marks.windows(2).all(|pair| pair[0].value < pair[1].value));
+    }
+}
@@ -1,8 +1,113 @@
 use std::ops::RangeInclusive;
 
 use super::items::Value;
+use super::polar::PolarAxes;
 use crate::*;
 
+/// Smallest value that is shown on a [`AxisScale::Log10`] axis.
+/// Anything at or below zero is clamped to this.
+const MIN_LOG_VALUE: f64 = 1e-300;
+
+/// How the values along an axis are mapped to the screen.
+///
+/// Set with [`super::Plot::x_axis_scale`] and [`super::Plot::y_axis_scale`].
+/// All plot items, the grid, zooming, dragging and the hover readout follow the scale,
+/// so values are always given as they are, without any pre-transformation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum AxisScale {
+    /// Equal steps in value are equal steps on screen.
+    Linear,
+
+    /// Every power of ten takes the same space on screen.
+    ///
+    /// Only positive values can be shown, anything else ends up far outside the plot.
+    Log10,
+
+    /// Logarithmic for large magnitudes, but close to linear around zero,
+    /// so that values of both signs can be shown.
+    ///
+    /// A value `v` is mapped to `sign(v) * log10(1 + |v| / linear_threshold)`.
+    ///
+    /// `linear_threshold` should be positive and finite. Other values are replaced by
+    /// the nearest usable one, `f64::EPSILON` for anything below it and `1.0` for NaN and infinity.
+    Symlog { linear_threshold: f64 },
+}
+
+impl Default for AxisScale {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl AxisScale {
+    /// A [`Self::Symlog`] scale that is about linear in `-linear_threshold..=linear_threshold`.
+    ///
+    /// `linear_threshold` should be positive and finite, see [`Self::Symlog`].
+    pub fn symlog(linear_threshold: f64) -> Self {
+        crate::egui_assert!(
+            linear_threshold > 0.0 && linear_threshold.is_finite(),
+            "symlog linear_threshold should be positive and finite, got {}",
+            linear_threshold
+        );
+        Self::Symlog {
+            linear_threshold: sanitized_threshold(linear_threshold),
+        }
+    }
+
+    pub fn is_linear(&self) -> bool {
+        *self == Self::Linear
+    }
+
+    /// Map a plot value to the space in which the axis is linear.
+    pub(crate) fn scale(&self, value: f64) -> f64 {
+        match *self {
+            Self::Linear => value,
+            Self::Log10 => value.max(MIN_LOG_VALUE).log10(),
+            Self::Symlog { linear_threshold } => {
+                let linear_threshold = sanitized_threshold(linear_threshold);
+                value.signum() * (value.abs() / linear_threshold).ln_1p() / std::f64::consts::LN_10
+            }
+        }
+    }
+
+    /// The inverse of [`Self::scale`].
+    pub(crate) fn unscale(&self, scaled: f64) -> f64 {
+        match *self {
+            Self::Linear => scaled,
+            Self::Log10 => 10_f64.powf(scaled),
+            Self::Symlog { linear_threshold } => {
+                let linear_threshold = sanitized_threshold(linear_threshold);
+                scaled.signum()
+                    * linear_threshold
+                    * (scaled.abs() * std::f64::consts::LN_10).exp_m1()
+            }
+        }
+    }
+
+    /// The derivative of [`Self::scale`] at the given value, i.e. how fast the scaled value
+    /// changes with the plot value.
+    pub(crate) fn derivative(&self, value: f64) -> f64 {
+        match *self {
+            Self::Linear => 1.0,
+            Self::Log10 => 1.0 / (value.max(MIN_LOG_VALUE) * std::f64::consts::LN_10),
+            Self::Symlog { linear_threshold } => {
+                let linear_threshold = sanitized_threshold(linear_threshold);
+                1.0 / ((linear_threshold + value.abs()) * std::f64::consts::LN_10)
+            }
+        }
+    }
+}
+
+/// The `linear_threshold` of a [`AxisScale::Symlog`] scale, made positive and finite.
+pub(crate) fn sanitized_threshold(linear_threshold: f64) -> f64 {
+    if linear_threshold.is_finite() {
+        linear_threshold.max(f64::EPSILON)
+    } else {
+        1.0
+    }
+}
+
 /// 2D bounding box of f64 precision.
 /// The range of data values we show.
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -18,6 +123,12 @@ impl PlotBounds {
         max: [-f64::INFINITY; 2],
     };
 
+    /// Create bounds from the lower left and upper right corner, e.g. for
+    /// [`PlotUi::set_plot_bounds`](super::PlotUi::set_plot_bounds).
+    pub fn from_min_max(min: [f64; 2], max: [f64; 2]) -> Self {
+        Self { min, max }
+    }
+
     pub fn min(&self) -> [f64; 2] {
         self.min
     }
@@ -77,16 +188,6 @@ impl PlotBounds {
         self.max[1] = self.max[1].max(y);
     }
 
-    pub(crate) fn expand_x(&mut self, pad: f64) {
-        self.min[0] -= pad;
-        self.max[0] += pad;
-    }
-
-    pub(crate) fn expand_y(&mut self, pad: f64) {
-        self.min[1] -= pad;
-        self.max[1] += pad;
-    }
-
     pub(crate) fn merge_x(&mut self, other: &PlotBounds) {
         self.min[0] = self.min[0].min(other.min[0]);
         self.max[0] = self.max[0].max(other.max[0]);
@@ -107,13 +208,6 @@ impl PlotBounds {
         self.max[1] = other.max[1];
     }
 
-    pub(crate) fn merge(&mut self, other: &PlotBounds) {
-        self.min[0] = self.min[0].min(other.min[0]);
-        self.min[1] = self.min[1].min(other.min[1]);
-        self.max[0] = self.max[0].max(other.max[0]);
-        self.max[1] = self.max[1].max(other.max[1]);
-    }
-
     pub(crate) fn translate_x(&mut self, delta: f64) {
         self.min[0] += delta;
         self.max[0] += delta;
@@ -124,19 +218,29 @@ impl PlotBounds {
         self.max[1] += delta;
     }
 
-    pub(crate) fn translate(&mut self, delta: Vec2) {
-        self.translate_x(delta.x as f64);
-        self.translate_y(delta.y as f64);
+    pub(crate) fn merge(&mut self, other: &PlotBounds) {
+        self.min[0] = self.min[0].min(other.min[0]);
+        self.min[1] = self.min[1].min(other.min[1]);
+        self.max[0] = self.max[0].max(other.max[0]);
+        self.max[1] = self.max[1].max(other.max[1]);
+    }
+
+    /// Add a margin relative to the width, measured in the space where the `scale` is linear.
+    pub(crate) fn add_relative_margin_x(&mut self, margin_fraction: Vec2, scale: AxisScale) {
+        self.add_relative_margin(0, margin_fraction.x as f64, scale);
     }
 
-    pub(crate) fn add_relative_margin_x(&mut self, margin_fraction: Vec2) {
-        let width = self.width().max(0.0);
-        self.expand_x(margin_fraction.x as f64 * width);
+    /// Add a margin relative to the height, measured in the space where the `scale` is linear.
+    pub(crate) fn add_relative_margin_y(&mut self, margin_fraction: Vec2, scale: AxisScale) {
+        self.add_relative_margin(1, margin_fraction.y as f64, scale);
     }
 
-    pub(crate) fn add_relative_margin_y(&mut self, margin_fraction: Vec2) {
-        let height = self.height().max(0.0);
-        self.expand_y(margin_fraction.y as f64 * height);
+    fn add_relative_margin(&mut self, axis: usize, margin_fraction: f64, scale: AxisScale) {
+        let min = scale.scale(self.min[axis]);
+        let max = scale.scale(self.max[axis]);
+        let pad = margin_fraction * (max - min).max(0.0);
+        self.min[axis] = scale.unscale(min - pad);
+        self.max[axis] = scale.unscale(max + pad);
     }
 
     pub(crate) fn range_x(&self) -> RangeInclusive<f64> {
@@ -172,10 +276,21 @@ pub(crate) struct ScreenTransform {
     x_centered: bool,
     /// Whether to always center the y-range of the bounds.
     y_centered: bool,
+    /// How values are mapped to the screen along the x and y axis.
+    scales: [AxisScale; 2],
+    /// Set for a polar plot, whose values are `(angle, radius)`. The bounds are then given in the
+    /// plane of the points the values are mapped to.
+    polar: Option<PolarAxes>,
 }
 
 impl ScreenTransform {
-    pub fn new(frame: Rect, mut bounds: PlotBounds, x_centered: bool, y_centered: bool) -> Self {
+    pub fn new(
+        frame: Rect,
+        mut bounds: PlotBounds,
+        x_centered: bool,
+        y_centered: bool,
+        scales: [AxisScale; 2],
+    ) -> Self {
         // Make sure they are not empty.
         if !bounds.is_valid() {
             bounds = PlotBounds::new_symmetrical(1.0);
@@ -189,14 +304,41 @@ impl ScreenTransform {
             bounds.make_y_symmetrical();
         };
 
+        // Logarithmic axes can only show positive values.
+        for (axis, scale) in scales.iter().enumerate() {
+            if *scale == AxisScale::Log10 && bounds.min[axis] <= 0.0 {
+                if bounds.max[axis] > 0.0 {
+                    bounds.min[axis] = bounds.max[axis] / 1000.0;
+                } else {
+                    bounds.min[axis] = 0.1;
+                    bounds.max[axis] = 10.0;
+                }
+            }
+        }
+
         Self {
             frame,
             bounds,
             x_centered,
             y_centered,
+            scales,
+            polar: None,
         }
     }
 
+    /// Make this the transform of a polar plot, with linear axes.
+    pub fn with_polar(mut self, polar: Option<PolarAxes>) -> Self {
+        if polar.is_some() {
+            self.scales = [AxisScale::Linear; 2];
+        }
+        self.polar = polar;
+        self
+    }
+
+    pub fn polar(&self) -> Option<&PolarAxes> {
+        self.polar.as_ref()
+    }
+
     pub fn frame(&self) -> &Rect {
         &self.frame
     }
@@ -209,6 +351,37 @@ impl ScreenTransform {
         &mut self.bounds
     }
 
+    pub fn scales(&self) -> [AxisScale; 2] {
+        self.scales
+    }
+
+    /// The bounds of the given axis, in the space where that axis is linear.
+    fn scaled_range(&self, axis: usize) -> RangeInclusive<f64> {
+        let scale = self.scales[axis];
+        scale.scale(self.bounds.min[axis])..=scale.scale(self.bounds.max[axis])
+    }
+
+    /// The screen range of the given axis, from the position of the minimum to the maximum.
+    fn frame_range(&self, axis: usize) -> RangeInclusive<f64> {
+        if axis == 0 {
+            (self.frame.left() as f64)..=(self.frame.right() as f64)
+        } else {
+            (self.frame.bottom() as f64)..=(self.frame.top() as f64) // negated y axis!
+        }
+    }
+
+    /// Set the bounds of the given axis from a range in the space where that axis is linear.
+    fn set_scaled_range(
+        bounds: &mut PlotBounds,
+        scale: AxisScale,
+        axis: usize,
+        min: f64,
+        max: f64,
+    ) {
+        bounds.min[axis] = scale.unscale(min);
+        bounds.max[axis] = scale.unscale(max);
+    }
+
     pub fn translate_bounds(&mut self, mut delta_pos: Vec2) {
         if self.x_centered {
             delta_pos.x = 0.;
@@ -216,52 +389,102 @@ impl ScreenTransform {
         if self.y_centered {
             delta_pos.y = 0.;
         }
-        delta_pos.x *= self.dvalue_dpos()[0] as f32;
-        delta_pos.y *= self.dvalue_dpos()[1] as f32;
-        self.bounds.translate(delta_pos);
+        let dvalue_dpos = self.dvalue_dpos();
+        for axis in 0..2 {
+            let delta = delta_pos[axis] as f64 * dvalue_dpos[axis];
+            let range = self.scaled_range(axis);
+            Self::set_scaled_range(
+                &mut self.bounds,
+                self.scales[axis],
+                axis,
+                range.start() + delta,
+                range.end() + delta,
+            );
+        }
     }
 
     /// Zoom by a relative factor with the given screen position as center.
     pub fn zoom(&mut self, zoom_factor: Vec2, center: Pos2) {
-        let center = self.value_from_position(center);
-
         let mut new_bounds = self.bounds;
-        new_bounds.min[0] = center.x + (new_bounds.min[0] - center.x) / (zoom_factor.x as f64);
-        new_bounds.max[0] = center.x + (new_bounds.max[0] - center.x) / (zoom_factor.x as f64);
-        new_bounds.min[1] = center.y + (new_bounds.min[1] - center.y) / (zoom_factor.y as f64);
-        new_bounds.max[1] = center.y + (new_bounds.max[1] - center.y) / (zoom_factor.y as f64);
+        for axis in 0..2 {
+            let range = self.scaled_range(axis);
+            let center = remap(center[axis] as f64, self.frame_range(axis), range.clone());
+            let zoom_factor = zoom_factor[axis] as f64;
+            Self::set_scaled_range(
+                &mut new_bounds,
+                self.scales[axis],
+                axis,
+                center + (range.start() - center) / zoom_factor,
+                center + (range.end() - center) / zoom_factor,
+            );
+        }
 
         if new_bounds.is_valid() {
             self.bounds = new_bounds;
         }
     }
 
+    /// Move the bounds back inside of `limits`, and shrink them if they are larger.
+    ///
+    /// Works in the space where each axis is linear. Infinite limits leave that side unconstrained.
+    pub fn clamp_bounds(&mut self, limits: &PlotBounds) {
+        for axis in 0..2 {
+            let scale = self.scales[axis];
+            let lower = scale.scale(limits.min[axis]);
+            let upper = scale.scale(limits.max[axis]);
+
+            let range = self.scaled_range(axis);
+            let (mut min, mut max) = (*range.start(), *range.end());
+            if upper - lower <= max - min {
+                min = lower;
+                max = upper;
+            } else if min < lower {
+                max += lower - min;
+                min = lower;
+            } else if max > upper {
+                min -= max - upper;
+                max = upper;
+            }
+            Self::set_scaled_range(&mut self.bounds, scale, axis, min, max);
+        }
+    }
+
     pub fn position_from_value(&self, value: &Value) -> Pos2 {
-        let x = remap(
-            value.x,
-            self.bounds.min[0]..=self.bounds.max[0],
-            (self.frame.left() as f64)..=(self.frame.right() as f64),
-        );
-        let y = remap(
-            value.y,
-            self.bounds.min[1]..=self.bounds.max[1],
-            (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
-        );
-        pos2(x as f32, y as f32)
+        match &self.polar {
+            Some(polar) => self.position_from_point(&polar.point_from_value(value)),
+            None => self.position_from_point(value),
+        }
     }
 
     pub fn value_from_position(&self, pos: Pos2) -> Value {
+        let point = self.point_from_position(pos);
+        match &self.polar {
+            Some(polar) => polar.value_from_point(&point),
+            None => point,
+        }
+    }
+
+    /// The screen position of a point in the plane of the bounds. This is the same as
+    /// [`Self::position_from_value`], except for polar plots.
+    pub fn position_from_point(&self, point: &Value) -> Pos2 {
         let x = remap(
-            pos.x as f64,
-            (self.frame.left() as f64)..=(self.frame.right() as f64),
-            self.bounds.min[0]..=self.bounds.max[0],
+            self.scales[0].scale(point.x),
+            self.scaled_range(0),
+            self.frame_range(0),
         );
         let y = remap(
-            pos.y as f64,
-            (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
-            self.bounds.min[1]..=self.bounds.max[1],
+            self.scales[1].scale(point.y),
+            self.scaled_range(1),
+            self.frame_range(1),
         );
-        Value::new(x, y)
+        pos2(x as f32, y as f32)
+    }
+
+    /// The inverse of [`Self::position_from_point`].
+    pub fn point_from_position(&self, pos: Pos2) -> Value {
+        let x = remap(pos.x as f64, self.frame_range(0), self.scaled_range(0));
+        let y = remap(pos.y as f64, self.frame_range(1), self.scaled_range(1));
+        Value::new(self.scales[0].unscale(x), self.scales[1].unscale(y))
     }
 
     /// Transform a rectangle of plot values to a screen-coordinate rectangle.
@@ -278,14 +501,24 @@ impl ScreenTransform {
         rect
     }
 
+    /// Width of the bounds of the given axis, in the space where that axis is linear.
+    fn scaled_width(&self, axis: usize) -> f64 {
+        let range = self.scaled_range(axis);
+        range.end() - range.start()
+    }
+
     /// delta position / delta value
+    ///
+    /// For non-linear axes, this is per unit of the scaled value (e.g. per decade).
     pub fn dpos_dvalue_x(&self) -> f64 {
-        self.frame.width() as f64 / self.bounds.width()
+        self.frame.width() as f64 / self.scaled_width(0)
     }
 
     /// delta position / delta value
+    ///
+    /// For non-linear axes, this is per unit of the scaled value (e.g. per decade).
     pub fn dpos_dvalue_y(&self) -> f64 {
-        -self.frame.height() as f64 / self.bounds.height() // negated y axis!
+        -self.frame.height() as f64 / self.scaled_width(1) // negated y axis!
     }
 
     /// delta position / delta value
@@ -298,10 +531,21 @@ impl ScreenTransform {
         [1.0 / self.dpos_dvalue_x(), 1.0 / self.dpos_dvalue_y()]
     }
 
+    /// delta value / delta position, in plot values around the given value.
+    ///
+    /// This is the same as [`Self::dvalue_dpos`] for linear axes.
+    pub fn dvalue_dpos_at(&self, value: &Value) -> [f64; 2] {
+        let dvalue_dpos = self.dvalue_dpos();
+        [
+            dvalue_dpos[0] / self.scales[0].derivative(value.x),
+            dvalue_dpos[1] / self.scales[1].derivative(value.y),
+        ]
+    }
+
     pub fn get_aspect(&self) -> f64 {
         let rw = self.frame.width() as f64;
         let rh = self.frame.height() as f64;
-        (self.bounds.width() / rw) / (self.bounds.height() / rh)
+        (self.scaled_width(0) / rw) / (self.scaled_width(1) / rh)
     }
 
     /// Sets the aspect ratio by either expanding the x-axis or contracting the y-axis.
@@ -314,12 +558,50 @@ impl ScreenTransform {
             return;
         }
 
-        if preserve_y {
-            self.bounds
-                .expand_x((aspect / current_aspect - 1.0) * self.bounds.width() * 0.5);
+        let (axis, factor) = if preserve_y {
+            (0, aspect / current_aspect)
         } else {
-            self.bounds
-                .expand_y((current_aspect / aspect - 1.0) * self.bounds.height() * 0.5);
+            (1, current_aspect / aspect)
+        };
+        let range = self.scaled_range(axis);
+        let pad = (factor - 1.0) * (range.end() - range.start()) * 0.5;
+        Self::set_scaled_range(
+            &mut self.bounds,
+            self.scales[axis],
+            axis,
+            range.start() - pad,
+            range.end() + pad,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_scale_round_trip() {
+        let scales = [AxisScale::Linear, AxisScale::Log10, AxisScale::symlog(2.0)];
+        for scale in scales {
+            for value in [1e-3, 0.5, 1.0, 42.0, 1e6] {
+                let round_trip = scale.unscale(scale.scale(value));
+                assert!((round_trip - value).abs() <= 1e-9 * value, "{:?}", scale);
+            }
+        }
+
+        let symlog = AxisScale::symlog(2.0);
+        assert_eq!(symlog.scale(0.0), 0.0);
+        assert_eq!(symlog.scale(-42.0), -symlog.scale(42.0));
+        assert_eq!(AxisScale::Log10.scale(1000.0), 3.0);
+    }
+
+    #[test]
+    fn symlog_sanitizes_threshold() {
+        for linear_threshold in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let scale = AxisScale::Symlog { linear_threshold };
+            assert!(scale.scale(42.0).is_finite() && scale.scale(42.0) > 0.0);
+            assert!(scale.derivative(42.0).is_finite());
+            assert!((scale.unscale(scale.scale(42.0)) - 42.0).abs() < 1e-6);
         }
     }
 }
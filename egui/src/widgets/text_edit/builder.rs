@@ -890,6 +890,21 @@ fn paint_cursor_selection(
 
     for ri in min.row..=max.row {
         let row = &galley.rows[ri];
+        if row.has_rtl() {
+            // With right-to-left text the selected characters need not be next to each other.
+            let start = if ri == min.row { min.column } else { 0 };
+            let end = if ri == max.row {
+                max.column
+            } else {
+                row.glyphs.len()
+            };
+            for glyph in row.glyphs.iter().take(end).skip(start) {
+                let rect =
+                    Rect::from_x_y_ranges(glyph.pos.x..=glyph.max_x(), row.min_y()..=row.max_y());
+                painter.rect_filled(rect.translate(pos.to_vec2()), 0.0, color);
+            }
+            continue;
+        }
         let left = if ri == min.row {
             row.x_offset(min.column)
         } else {
@@ -4,9 +4,10 @@ use std::ops::RangeInclusive;
 use egui::plot::{GridInput, GridMark};
 use egui::*;
 use plot::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, CoordinatesFormatter, Corner, HLine,
-    Legend, Line, LineStyle, MarkerShape, Plot, PlotImage, Points, Polygon, Text, VLine, Value,
-    Values,
+    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, CandleChart, CandleElem, Colormap, Contour,
+    CoordinatesFormatter, Corner, DragHandles, GroupedBarChart, HLine, Heatmap, Histogram, Legend,
+    Line, LineStyle, MarkerShape, Normalization, Ohlc, PieChart, PieSlice, Plot, PlotImage, Points,
+    PolarAxes, Polygon, SelectionMode, Text, VLine, Value, Values,
 };
 
 // ----------------------------------------------------------------------------
@@ -427,6 +428,8 @@ impl CustomAxisDemo {
             .x_axis_formatter(x_fmt)
             .y_axis_formatter(y_fmt)
             .x_grid_spacer(CustomAxisDemo::x_grid)
+            .x_axis_label("Time")
+            .y_axis_label("Growth")
             .label_formatter(label_fmt)
             .show(ui, |plot_ui| {
                 plot_ui.line(CustomAxisDemo::logistic_fn());
@@ -441,6 +444,7 @@ impl CustomAxisDemo {
 struct LinkedAxisDemo {
     link_x: bool,
     link_y: bool,
+    link_cursor: bool,
     group: plot::LinkedAxisGroup,
 }
 
@@ -448,10 +452,12 @@ impl Default for LinkedAxisDemo {
     fn default() -> Self {
         let link_x = true;
         let link_y = false;
+        let link_cursor = true;
         Self {
             link_x,
             link_y,
-            group: plot::LinkedAxisGroup::new(link_x, link_y),
+            link_cursor,
+            group: plot::LinkedAxisGroup::new(link_x, link_y).link_cursor(link_cursor),
         }
     }
 }
@@ -468,11 +474,11 @@ impl LinkedAxisDemo {
     }
 
     fn configure_plot(plot_ui: &mut plot::PlotUi) {
-        plot_ui.line(LinkedAxisDemo::line_with_slope(0.5));
-        plot_ui.line(LinkedAxisDemo::line_with_slope(1.0));
-        plot_ui.line(LinkedAxisDemo::line_with_slope(2.0));
-        plot_ui.line(LinkedAxisDemo::sin());
-        plot_ui.line(LinkedAxisDemo::cos());
+        plot_ui.line(LinkedAxisDemo::line_with_slope(0.5).name("0.5 x"));
+        plot_ui.line(LinkedAxisDemo::line_with_slope(1.0).name("x"));
+        plot_ui.line(LinkedAxisDemo::line_with_slope(2.0).name("2 x"));
+        plot_ui.line(LinkedAxisDemo::sin().name("sin(x)"));
+        plot_ui.line(LinkedAxisDemo::cos().name("cos(x)"));
     }
 
     fn ui(&mut self, ui: &mut Ui) -> Response {
@@ -480,9 +486,11 @@ impl LinkedAxisDemo {
             ui.label("Linked axes:");
             ui.checkbox(&mut self.link_x, "X");
             ui.checkbox(&mut self.link_y, "Y");
+            ui.checkbox(&mut self.link_cursor, "Cursor");
         });
         self.group.set_link_x(self.link_x);
         self.group.set_link_y(self.link_y);
+        self.group.set_link_cursor(self.link_cursor);
         ui.horizontal(|ui| {
             Plot::new("linked_axis_1")
                 .data_aspect(1.0)
@@ -509,6 +517,64 @@ impl LinkedAxisDemo {
 
 // ----------------------------------------------------------------------------
 
+#[derive(PartialEq, Default)]
+struct PolarDemo {
+    compass: bool,
+}
+
+impl PolarDemo {
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        ui.checkbox(&mut self.compass, "Compass orientation")
+            .on_hover_text("0° points up and angles grow clockwise.");
+
+        // The gain of an array of four antennas, in decibels above -30 dB.
+        let pattern = Values::from_values_iter((0..=720).map(|i| {
+            let angle = i as f64 * 0.5;
+            let half_phase = std::f64::consts::FRAC_PI_2 * angle.to_radians().cos();
+            let array_factor = if half_phase.sin().abs() < 1e-9 {
+                1.0
+            } else {
+                ((4.0 * half_phase).sin() / (4.0 * half_phase.sin())).abs()
+            };
+            let gain_db = 20.0 * array_factor.max(1e-3).log10();
+            Value::new(angle, (gain_db + 30.0).max(0.0))
+        }));
+        let detections = Values::from_values(vec![
+            Value::new(15.0, 22.0),
+            Value::new(170.0, 12.0),
+            Value::new(200.0, 27.0),
+            Value::new(290.0, 8.0),
+        ]);
+        let sector = Values::from_values(
+            std::iter::once(Value::new(0.0, 0.0))
+                .chain(
+                    (60..=120)
+                        .step_by(5)
+                        .map(|angle| Value::new(angle as f64, 30.0)),
+                )
+                .collect(),
+        );
+
+        let polar = if self.compass {
+            PolarAxes::compass()
+        } else {
+            PolarAxes::default()
+        };
+        Plot::new("polar_demo")
+            .polar(polar)
+            .legend(Legend::default())
+            .y_axis_formatter(|radius, _| format!("{} dB", radius - 30.0))
+            .show(ui, |plot_ui| {
+                plot_ui.polygon(Polygon::new(sector).name("Sector"));
+                plot_ui.line(Line::new(pattern).name("Antenna pattern"));
+                plot_ui.points(Points::new(detections).radius(4.0).name("Detections"));
+            })
+            .response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(PartialEq, Default)]
 struct ItemsDemo {
     texture: Option<egui::TextureHandle>,
@@ -587,24 +653,76 @@ impl ItemsDemo {
 
 // ----------------------------------------------------------------------------
 
-#[derive(Default, PartialEq)]
-struct InteractionDemo {}
+#[derive(PartialEq)]
+struct InteractionDemo {
+    envelope: Vec<Value>,
+    select: bool,
+}
+
+impl Default for InteractionDemo {
+    fn default() -> Self {
+        Self {
+            envelope: vec![
+                Value::new(-2.0, 0.0),
+                Value::new(-1.0, 1.5),
+                Value::new(0.5, 0.8),
+                Value::new(2.0, 0.8),
+                Value::new(3.0, 0.0),
+            ],
+            select: false,
+        }
+    }
+}
 
 impl InteractionDemo {
-    #[allow(clippy::unused_self)]
     fn ui(&mut self, ui: &mut Ui) -> Response {
-        let plot = Plot::new("interaction_demo").height(300.0);
+        let (reset_view, zoom_in) = ui
+            .horizontal(|ui| {
+                let buttons = (
+                    ui.button("Reset view").clicked(),
+                    ui.button("Zoom in").clicked(),
+                );
+                ui.checkbox(&mut self.select, "Select")
+                    .on_hover_text("Drag to select values instead of moving the view");
+                buttons
+            })
+            .inner;
+
+        let mut plot = Plot::new("interaction_demo").height(300.0);
+        if self.select {
+            plot = plot.selection(SelectionMode::Rect);
+        }
 
         let InnerResponse {
             response,
-            inner: (screen_pos, pointer_coordinate, pointer_coordinate_drag_delta, bounds, hovered),
+            inner:
+                (
+                    screen_pos,
+                    pointer_coordinate,
+                    pointer_coordinate_drag_delta,
+                    bounds,
+                    hovered,
+                    selected,
+                ),
         } = plot.show(ui, |plot_ui| {
+            if reset_view {
+                plot_ui.set_auto_bounds([true, true]);
+            }
+            if zoom_in {
+                plot_ui.zoom_bounds(Vec2::splat(1.25), plot_ui.plot_bounds().center());
+            }
+            let handles = DragHandles::new("envelope", self.envelope.clone());
+            if let Some(drag) = plot_ui.drag_handles(handles) {
+                self.envelope[drag.index] = drag.value;
+            }
+            plot_ui.line(Line::new(Values::from_values(self.envelope.clone())).name("Envelope"));
             (
                 plot_ui.screen_from_plot(Value::new(0.0, 0.0)),
                 plot_ui.pointer_coordinate(),
                 plot_ui.pointer_coordinate_drag_delta(),
                 plot_ui.plot_bounds(),
                 plot_ui.plot_hovered(),
+                plot_ui.selected_values(),
             )
         });
 
@@ -632,6 +750,9 @@ impl InteractionDemo {
             "pointer coordinate drag delta: {}",
             coordinate_text
         ));
+        for (name, values) in selected {
+            ui.label(format!("selected values of {}: {}", name, values.len()));
+        }
 
         response
     }
@@ -642,8 +763,14 @@ impl InteractionDemo {
 #[derive(PartialEq, Eq)]
 enum Chart {
     GaussBars,
+    Samples,
     StackedBars,
+    GroupedBars,
     BoxPlot,
+    Candles,
+    Pie,
+    Heatmap,
+    Contour,
 }
 
 impl Default for Chart {
@@ -672,8 +799,14 @@ impl ChartsDemo {
         ui.label("Type:");
         ui.horizontal(|ui| {
             ui.selectable_value(&mut self.chart, Chart::GaussBars, "Histogram");
+            ui.selectable_value(&mut self.chart, Chart::Samples, "Binned Samples");
             ui.selectable_value(&mut self.chart, Chart::StackedBars, "Stacked Bar Chart");
+            ui.selectable_value(&mut self.chart, Chart::GroupedBars, "Grouped Bar Chart");
             ui.selectable_value(&mut self.chart, Chart::BoxPlot, "Box Plot");
+            ui.selectable_value(&mut self.chart, Chart::Candles, "Candlesticks");
+            ui.selectable_value(&mut self.chart, Chart::Pie, "Pie Chart");
+            ui.selectable_value(&mut self.chart, Chart::Heatmap, "Heatmap");
+            ui.selectable_value(&mut self.chart, Chart::Contour, "Contour Lines");
         });
         ui.label("Orientation:");
         ui.horizontal(|ui| {
@@ -682,8 +815,14 @@ impl ChartsDemo {
         });
         match self.chart {
             Chart::GaussBars => self.bar_gauss(ui),
+            Chart::Samples => self.samples(ui),
             Chart::StackedBars => self.bar_stacked(ui),
+            Chart::GroupedBars => self.bar_grouped(ui),
             Chart::BoxPlot => self.box_plot(ui),
+            Chart::Candles => self.candles(ui),
+            Chart::Pie => self.pie(ui),
+            Chart::Heatmap => self.heatmap(ui),
+            Chart::Contour => self.contour(ui),
         }
     }
 
@@ -715,6 +854,72 @@ impl ChartsDemo {
             .response
     }
 
+    fn samples(&self, ui: &mut Ui) -> Response {
+        // Approximately normally distributed samples: the sum of twelve uniform random numbers.
+        let mut state: u64 = 42;
+        let mut uniform = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1_u64 << 53) as f64
+        };
+        let samples: Vec<f64> = (0..2000)
+            .map(|_| (0..12).map(|_| uniform()).sum::<f64>() - 6.0)
+            .collect();
+
+        let mut density = Histogram::new(samples.clone())
+            .normalization(Normalization::Density)
+            .name("Density");
+        let mut cumulative = Histogram::new(samples)
+            .normalization(Normalization::Cumulative)
+            .step(true)
+            .name("Cumulative");
+        if !self.vertical {
+            density = density.horizontal();
+            cumulative = cumulative.horizontal();
+        }
+
+        Plot::new("Binned Samples Demo")
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.histogram(density);
+                plot_ui.histogram(cumulative);
+            })
+            .response
+    }
+
+    fn bar_grouped(&self, ui: &mut Ui) -> Response {
+        let fruits = ["Apples", "Bananas", "Cherries", "Dates"];
+        let series = [
+            ("Monday", [4.0, 2.5, 1.0, 3.0]),
+            ("Tuesday", [3.0, 3.5, 2.0, 1.5]),
+            ("Wednesday", [5.0, 1.0, 2.5, 2.0]),
+        ];
+        let charts = series
+            .iter()
+            .map(|(day, values)| {
+                let chart =
+                    BarChart::new(values.iter().map(|value| Bar::new(0.0, *value)).collect())
+                        .name(day);
+                if self.vertical {
+                    chart
+                } else {
+                    chart.horizontal()
+                }
+            })
+            .collect();
+        let group = GroupedBarChart::new(charts).categories(fruits);
+
+        let plot = Plot::new("Grouped Bar Chart Demo").legend(Legend::default());
+        let plot = if self.vertical {
+            plot.x_axis_categories(fruits)
+        } else {
+            plot.y_axis_categories(fruits)
+        };
+        plot.show(ui, |plot_ui| plot_ui.grouped_bar_chart(group))
+            .response
+    }
+
     fn bar_stacked(&self, ui: &mut Ui) -> Response {
         let mut chart1 = BarChart::new(vec![
             Bar::new(0.5, 1.0).name("Day 1"),
@@ -819,6 +1024,118 @@ impl ChartsDemo {
             })
             .response
     }
+
+    fn candles(&self, ui: &mut Ui) -> Response {
+        let mut close = 20.0;
+        let candles = (0..60)
+            .map(|day| {
+                let t = day as f64;
+                let open = close;
+                close = open + 1.5 * (0.7 * t).sin() + 0.5 * (2.3 * t).cos();
+                let high = open.max(close) + 0.4 + 0.3 * (1.7 * t).sin().abs();
+                let low = open.min(close) - 0.4 - 0.3 * (1.1 * t).cos().abs();
+                let volume = 1000.0 + 800.0 * (0.4 * t).sin().abs();
+                CandleElem::new(t, Ohlc::new(open, high, low, close))
+                    .name(format!("Day {}", day + 1))
+                    .volume(volume.round())
+            })
+            .collect();
+
+        let mut chart = CandleChart::new(candles)
+            .volume_area(0.0, 8.0)
+            .name("Price");
+        if !self.vertical {
+            chart = chart.horizontal();
+        }
+
+        Plot::new("Candlestick Demo")
+            .legend(Legend::default())
+            .show(ui, |plot_ui| plot_ui.candle_chart(chart))
+            .response
+    }
+
+    #[allow(clippy::unused_self)]
+    fn pie(&self, ui: &mut Ui) -> Response {
+        let pie = PieChart::new(
+            "browsers",
+            vec![
+                PieSlice::new(64.0).name("Chrome"),
+                PieSlice::new(19.0).name("Safari"),
+                PieSlice::new(4.0).name("Edge"),
+                PieSlice::new(3.5).name("Firefox"),
+                PieSlice::new(9.5).name("Other"),
+            ],
+        )
+        .inner_radius(0.45);
+
+        Plot::new("Pie Chart Demo")
+            .legend(Legend::default())
+            .data_aspect(1.0)
+            .show_axes([false; 2])
+            .show(ui, |plot_ui| {
+                plot_ui.pie_chart(pie);
+            })
+            .response
+    }
+
+    #[allow(clippy::unused_self)]
+    fn heatmap(&self, ui: &mut Ui) -> Response {
+        // A damped wave, sampled on a 40 x 30 grid.
+        let (cols, rows) = (40, 30);
+        let values = (0..rows)
+            .flat_map(|row| {
+                (0..cols).map(move |col| {
+                    let x = col as f64 / 4.0 - 5.0;
+                    let y = row as f64 / 4.0 - 3.75;
+                    let r = x.hypot(y);
+                    (2.0 * r).cos() * (-0.2 * r).exp()
+                })
+            })
+            .collect();
+        let heatmap = Heatmap::new(values, cols)
+            .origin(Value::new(-5.0, -3.75))
+            .cell_size(0.25, 0.25)
+            .colormap(Colormap::Coolwarm)
+            .value_range(-1.0..=1.0)
+            .name("Damped wave");
+
+        Plot::new("Heatmap Demo")
+            .legend(Legend::default())
+            .data_aspect(1.0)
+            .show(ui, |plot_ui| plot_ui.heatmap(heatmap))
+            .response
+    }
+
+    #[allow(clippy::unused_self)]
+    fn contour(&self, ui: &mut Ui) -> Response {
+        // A peak and a valley, sampled on a 60 x 40 grid.
+        let (cols, rows) = (60, 40);
+        let values = (0..rows)
+            .flat_map(|row| {
+                (0..cols).map(move |col| {
+                    let x = col as f64 / 10.0 - 3.0;
+                    let y = row as f64 / 10.0 - 2.0;
+                    let peak = (-((x + 1.0).powi(2) + y * y)).exp();
+                    let valley = (-((x - 1.2).powi(2) + (y - 0.5).powi(2)) / 0.5).exp();
+                    peak - 0.8 * valley
+                })
+            })
+            .collect();
+        let contour = Contour::new(values, cols)
+            .origin(Value::new(-3.0, -2.0))
+            .spacing(0.1, 0.1)
+            .levels((-7..=9).map(|level| level as f64 / 10.0).collect())
+            .fill_bands(Colormap::Viridis)
+            .color(Color32::from_gray(40))
+            .labels(true)
+            .name("Peak and valley");
+
+        Plot::new("Contour Demo")
+            .legend(Legend::default())
+            .data_aspect(1.0)
+            .show(ui, |plot_ui| plot_ui.contour(contour))
+            .response
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -833,6 +1150,7 @@ enum Panel {
     Interaction,
     CustomAxes,
     LinkedAxes,
+    Polar,
 }
 
 impl Default for Panel {
@@ -853,6 +1171,7 @@ pub struct PlotDemo {
     interaction_demo: InteractionDemo,
     custom_axes_demo: CustomAxisDemo,
     linked_axes_demo: LinkedAxisDemo,
+    polar_demo: PolarDemo,
     open_panel: Panel,
 }
 
@@ -899,6 +1218,7 @@ impl super::View for PlotDemo {
             ui.selectable_value(&mut self.open_panel, Panel::Interaction, "Interaction");
             ui.selectable_value(&mut self.open_panel, Panel::CustomAxes, "Custom Axes");
             ui.selectable_value(&mut self.open_panel, Panel::LinkedAxes, "Linked Axes");
+            ui.selectable_value(&mut self.open_panel, Panel::Polar, "Polar");
         });
         ui.separator();
 
@@ -927,6 +1247,9 @@ impl super::View for PlotDemo {
             Panel::LinkedAxes => {
                 self.linked_axes_demo.ui(ui);
             }
+            Panel::Polar => {
+                self.polar_demo.ui(ui);
+            }
         }
     }
 }
@@ -161,6 +161,8 @@ impl From<ColorImage> for ImageData {
 /// Each value represents "coverage", i.e. how much a texel is covered by a character.
 ///
 /// This is roughly interpreted as the opacity of a white image.
+///
+/// Glyphs with their own colors, like emojis, are stored in [`Self::colors`].
 #[derive(Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontImage {
@@ -171,6 +173,14 @@ pub struct FontImage {
     ///
     /// Often you want to use [`Self::srgba_pixels`] instead.
     pub pixels: Vec<f32>,
+
+    /// Premultiplied colors, used instead of the coverage where they are not transparent.
+    ///
+    /// Either empty, or one for each pixel.
+    /// It stays empty until the first color glyph is added, but from then on it costs
+    /// four bytes for every pixel of the whole image, on top of [`Self::pixels`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Vec<Color32>,
 }
 
 impl FontImage {
@@ -178,6 +188,7 @@ impl FontImage {
         Self {
             size,
             pixels: vec![0.0; size[0] * size[1]],
+            colors: vec![],
         }
     }
 
@@ -197,7 +208,12 @@ impl FontImage {
     /// If you are having problems with text looking skinny and pixelated, try
     /// setting a lower gamma, e.g. `0.5`.
     pub fn srgba_pixels(&'_ self, gamma: f32) -> impl ExactSizeIterator<Item = Color32> + '_ {
-        self.pixels.iter().map(move |coverage| {
+        self.pixels.iter().enumerate().map(move |(i, coverage)| {
+            if let Some(&color) = self.colors.get(i) {
+                if color != Color32::TRANSPARENT {
+                    return color;
+                }
+            }
             // This is arbitrarily chosen to make text look as good as possible.
             // In particular, it looks good with gamma=1 and the default eframe backend,
             // which uses linear blending.
@@ -207,20 +223,45 @@ impl FontImage {
         })
     }
 
+    /// Give a pixel its own color, which is used instead of its coverage.
+    pub fn set_color(&mut self, (x, y): (usize, usize), color: Color32) {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        if self.colors.is_empty() {
+            self.colors = vec![Color32::TRANSPARENT; w * h];
+        }
+        self.colors[y * w + x] = color;
+    }
+
+    /// Change the height, keeping the pixels of the rows that remain.
+    pub(crate) fn resize_height(&mut self, height: usize) {
+        self.size[1] = height;
+        let len = self.width() * height;
+        self.pixels.resize(len, 0.0);
+        if !self.colors.is_empty() {
+            self.colors.resize(len, Color32::TRANSPARENT);
+        }
+    }
+
     /// Clone a sub-region as a new image.
     pub fn region(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> FontImage {
         assert!(x + w <= self.width());
         assert!(y + h <= self.height());
 
         let mut pixels = Vec::with_capacity(w * h);
+        let mut colors = Vec::with_capacity(if self.colors.is_empty() { 0 } else { w * h });
         for y in y..y + h {
             let offset = y * self.width() + x;
             pixels.extend(&self.pixels[offset..(offset + w)]);
+            if !self.colors.is_empty() {
+                colors.extend(&self.colors[offset..(offset + w)]);
+            }
         }
         assert_eq!(pixels.len(), w * h);
         FontImage {
             size: [w, h],
             pixels,
+            colors,
         }
     }
 }
@@ -1,6 +1,9 @@
+use super::FontData;
+#[cfg(feature = "color_glyphs")]
+use crate::Color32;
 use crate::{
     mutex::{Mutex, RwLock},
-    TextureAtlas,
+    FontImage, TextureAtlas,
 };
 use ahash::AHashMap;
 use emath::{vec2, Vec2};
@@ -24,6 +27,12 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// The glyph has its own colors (e.g. an emoji), and is painted without the text color.
+    pub colored: bool,
+
+    /// The glyph is from an italic or slanted face, so it isn't slanted for [`super::TextFormat::italics`].
+    pub italic: bool,
 }
 
 impl UvRect {
@@ -68,6 +77,56 @@ pub struct FontImpl {
     pixels_per_point: f32,
     glyph_info_cache: RwLock<AHashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
     atlas: Arc<Mutex<TextureAtlas>>,
+    /// The font file, for text shaping and variable fonts.
+    font_data: Option<Arc<FontData>>,
+    style: FaceStyle,
+    /// Glyphs picked by text shaping, which need not correspond to a single `char`.
+    #[cfg(feature = "shaping")]
+    shaped_glyph_cache: RwLock<ShapedGlyphCache>,
+}
+
+/// How a [`FontImpl`] differs from the face in its font file.
+///
+/// See [`super::FontDefinitions::faces`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct FaceStyle {
+    /// Coordinates on the axes of a variable font, e.g. `wght`.
+    pub variations: Vec<(ttf_parser::Tag, f32)>,
+
+    /// Synthetic bold: make the glyphs this many pixels wider.
+    pub embolden: f32,
+
+    /// The glyphs are italic or slanted already, so shouldn't be slanted again.
+    pub italic: bool,
+}
+
+impl Eq for FaceStyle {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for FaceStyle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            variations,
+            embolden,
+            italic,
+        } = self;
+        for (tag, value) in variations {
+            tag.hash(state);
+            crate::f32_hash(state, *value);
+        }
+        crate::f32_hash(state, *embolden);
+        italic.hash(state);
+    }
+}
+
+#[cfg(feature = "shaping")]
+#[derive(Default)]
+struct ShapedGlyphCache {
+    /// Frame counter, used to find the least recently used glyphs.
+    generation: u32,
+
+    /// Each glyph, with the generation it was last used.
+    glyphs: AHashMap<ab_glyph::GlyphId, (GlyphInfo, u32)>,
 }
 
 impl FontImpl {
@@ -103,7 +162,36 @@ impl FontImpl {
             pixels_per_point,
             glyph_info_cache: Default::default(),
             atlas,
+            font_data: None,
+            style: Default::default(),
+            #[cfg(feature = "shaping")]
+            shaped_glyph_cache: Default::default(),
+        }
+    }
+
+    /// The font file, needed for text shaping and for the [`FaceStyle::variations`].
+    pub(crate) fn with_font_data(mut self, font_data: Arc<FontData>) -> Self {
+        self.font_data = Some(font_data);
+        self
+    }
+
+    /// Render the face with a different weight or slant than in its font file.
+    pub(crate) fn with_style(mut self, style: FaceStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The font file with the [`FaceStyle::variations`] applied, if there are any.
+    fn variable_face(&self) -> Option<ttf_parser::Face<'_>> {
+        if self.style.variations.is_empty() {
+            return None;
         }
+        let font_data = self.font_data.as_ref()?;
+        let mut face = ttf_parser::Face::from_slice(&font_data.font, font_data.index).ok()?;
+        for &(tag, value) in &self.style.variations {
+            face.set_variation(tag, value);
+        }
+        Some(face)
     }
 
     fn ignore_character(&self, chr: char) -> bool {
@@ -171,20 +259,48 @@ impl FontImpl {
                 None // unsupported character
             }
         } else {
-            let glyph_info = allocate_glyph(
-                &mut self.atlas.lock(),
-                &self.ab_glyph_font,
-                glyph_id,
-                self.scale_in_pixels as f32,
-                self.y_offset,
-                self.pixels_per_point,
-            );
+            let glyph_info = allocate_glyph(&mut self.atlas.lock(), self, glyph_id);
 
             self.glyph_info_cache.write().insert(c, glyph_info);
             Some(glyph_info)
         }
     }
 
+    /// Update the glyphs that were moved when repacking the atlas, from their old `min` to a new one,
+    /// and forget the others.
+    ///
+    /// Returns how many glyphs were evicted.
+    pub(crate) fn repack_glyphs(&self, moved: &AHashMap<[u16; 2], [u16; 2]>) -> usize {
+        let mut num_evicted = 0;
+        let mut repack = |glyph_info: &mut GlyphInfo| {
+            let uv_rect = &mut glyph_info.uv_rect;
+            if uv_rect.is_nothing() {
+                true // takes no space
+            } else if let Some(&min) = moved.get(&uv_rect.min) {
+                uv_rect.max = [
+                    min[0] + (uv_rect.max[0] - uv_rect.min[0]),
+                    min[1] + (uv_rect.max[1] - uv_rect.min[1]),
+                ];
+                uv_rect.min = min;
+                true
+            } else {
+                num_evicted += 1;
+                false
+            }
+        };
+
+        self.glyph_info_cache
+            .write()
+            .retain(|_, glyph_info| repack(glyph_info));
+        #[cfg(feature = "shaping")]
+        self.shaped_glyph_cache
+            .write()
+            .glyphs
+            .retain(|_, (glyph_info, _)| repack(glyph_info));
+
+        num_evicted
+    }
+
     #[inline]
     pub fn pair_kerning(
         &self,
@@ -210,8 +326,157 @@ impl FontImpl {
     }
 }
 
+/// The glyphs of a cluster of characters that were shaped together, see [`FontImpl::shape`].
+#[cfg(feature = "shaping")]
+#[derive(Default)]
+pub(crate) struct ShapedCluster {
+    /// Number of `char`s in the cluster.
+    pub chars: usize,
+
+    /// Unit: points.
+    pub advance_width: f32,
+
+    /// The glyphs, with their offsets from the left of the cluster in points.
+    pub glyphs: Vec<(GlyphInfo, Vec2)>,
+}
+
+#[cfg(feature = "shaping")]
+impl FontImpl {
+    /// Can `c` be shaped with this font?
+    pub(crate) fn can_shape(&self, c: char) -> bool {
+        use ab_glyph::Font as _;
+        self.font_data.is_some()
+            && !c.is_control()
+            && !self.ignore_character(c)
+            && self.ab_glyph_font.glyph_id(c).0 != 0
+    }
+
+    /// Shape a run of text in a single script with [`rustybuzz`],
+    /// returning the clusters in logical order.
+    ///
+    /// All characters of the `text` must be supported by this font, see [`Self::can_shape`].
+    pub(crate) fn shape(&self, text: &str) -> Vec<ShapedCluster> {
+        let face = self
+            .font_data
+            .as_ref()
+            .and_then(|data| rustybuzz::Face::from_slice(&data.font, data.index));
+        let mut face = match face {
+            Some(face) => face,
+            None => return self.unshaped_clusters(text),
+        };
+        let variations: Vec<_> = (self.style.variations.iter())
+            .map(|&(tag, value)| rustybuzz::Variation { tag, value })
+            .collect();
+        face.set_variations(&variations);
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        let rtl = buffer.direction() == rustybuzz::Direction::RightToLeft;
+        let output = rustybuzz::shape(&face, &[], buffer);
+
+        use ab_glyph::{Font as _, ScaleFont as _};
+        let scaled = self.ab_glyph_font.as_scaled(self.scale_in_pixels as f32);
+        let h_scale = scaled.h_scale_factor() / self.pixels_per_point;
+        let v_scale = scaled.v_scale_factor() / self.pixels_per_point;
+
+        // The glyphs are in visual order, with the byte offset of their cluster:
+        let mut clusters: Vec<(usize, ShapedCluster)> = vec![];
+        for (info, pos) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+            let start = info.cluster as usize;
+            if clusters.last().map_or(true, |(last, _)| *last != start) {
+                clusters.push((start, ShapedCluster::default()));
+            }
+            let (_, cluster) = clusters.last_mut().unwrap();
+            let offset = vec2(
+                cluster.advance_width + pos.x_offset as f32 * h_scale,
+                -pos.y_offset as f32 * v_scale,
+            );
+            let glyph_info = self.shaped_glyph_info(ab_glyph::GlyphId(info.glyph_id as u16));
+            cluster.glyphs.push((glyph_info, offset));
+            cluster.advance_width +=
+                pos.x_advance as f32 * h_scale + self.style.embolden / self.pixels_per_point;
+        }
+        if rtl {
+            clusters.reverse();
+        }
+
+        let in_order = clusters.windows(2).all(|pair| pair[0].0 < pair[1].0);
+        if clusters.is_empty() || !in_order {
+            return self.unshaped_clusters(text);
+        }
+        clusters[0].0 = 0;
+
+        let mut end = text.len();
+        for (start, cluster) in clusters.iter_mut().rev() {
+            cluster.chars = text[*start..end].chars().count();
+            end = *start;
+        }
+        clusters.into_iter().map(|(_, cluster)| cluster).collect()
+    }
+
+    /// One cluster per character, in case shaping fails.
+    fn unshaped_clusters(&self, text: &str) -> Vec<ShapedCluster> {
+        text.chars()
+            .map(|c| {
+                let glyph_info = self.glyph_info(c).unwrap_or_default();
+                ShapedCluster {
+                    chars: 1,
+                    advance_width: glyph_info.advance_width,
+                    glyphs: vec![(glyph_info, Vec2::ZERO)],
+                }
+            })
+            .collect()
+    }
+
+    fn shaped_glyph_info(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
+        {
+            let mut cache = self.shaped_glyph_cache.write();
+            let generation = cache.generation;
+            if let Some((glyph_info, last_used)) = cache.glyphs.get_mut(&glyph_id) {
+                *last_used = generation;
+                return *glyph_info;
+            }
+        }
+
+        let glyph_info = if glyph_id.0 == 0 {
+            GlyphInfo::default()
+        } else {
+            allocate_glyph(&mut self.atlas.lock(), self, glyph_id)
+        };
+        let mut cache = self.shaped_glyph_cache.write();
+        let generation = cache.generation;
+        cache.glyphs.insert(glyph_id, (glyph_info, generation));
+        glyph_info
+    }
+
+    /// Shaped glyphs looked up from now on are considered used in this frame.
+    pub(crate) fn set_generation(&self, generation: u32) {
+        self.shaped_glyph_cache.write().generation = generation;
+    }
+
+    /// Where in the texture atlas the shaped glyphs are, and the generation they were last used.
+    pub(crate) fn shaped_glyphs_in_atlas(&self) -> Vec<(UvRect, u32)> {
+        self.shaped_glyph_cache
+            .read()
+            .glyphs
+            .values()
+            .filter(|(glyph_info, _)| !glyph_info.uv_rect.is_nothing())
+            .map(|(glyph_info, last_used)| (glyph_info.uv_rect, *last_used))
+            .collect()
+    }
+}
+
 type FontIndex = usize;
 
+#[derive(Clone, Copy)]
+struct CachedGlyph {
+    font_index: FontIndex,
+    glyph_info: GlyphInfo,
+    /// The `Font::generation` when this was last looked up.
+    last_used: u32,
+}
+
 // TODO(emilk): rename?
 /// Wrapper over multiple [`FontImpl`] (e.g. a primary + fallbacks for emojis)
 pub struct Font {
@@ -221,7 +486,11 @@ pub struct Font {
     replacement_glyph: (FontIndex, GlyphInfo),
     pixels_per_point: f32,
     row_height: f32,
-    glyph_info_cache: AHashMap<char, (FontIndex, GlyphInfo)>,
+    glyph_info_cache: AHashMap<char, CachedGlyph>,
+    /// Frame counter, used to find the least recently used glyphs.
+    generation: u32,
+    cache_hits: u64,
+    cache_misses: u64,
 }
 
 impl Font {
@@ -234,6 +503,9 @@ impl Font {
                 pixels_per_point: 1.0,
                 row_height: 0.0,
                 glyph_info_cache: Default::default(),
+                generation: 0,
+                cache_hits: 0,
+                cache_misses: 0,
             };
         }
 
@@ -247,23 +519,53 @@ impl Font {
             pixels_per_point,
             row_height,
             glyph_info_cache: Default::default(),
+            generation: 0,
+            cache_hits: 0,
+            cache_misses: 0,
         };
+        slf.replacement_glyph = slf.find_replacement_glyph();
+        slf
+    }
 
+    fn find_replacement_glyph(&mut self) -> (FontIndex, GlyphInfo) {
         const PRIMARY_REPLACEMENT_CHAR: char = '◻'; // white medium square
         const FALLBACK_REPLACEMENT_CHAR: char = '?'; // fallback for the fallback
 
-        let replacement_glyph = slf
-            .glyph_info_no_cache_or_fallback(PRIMARY_REPLACEMENT_CHAR)
-            .or_else(|| slf.glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR))
+        self.glyph_info_no_cache_or_fallback(PRIMARY_REPLACEMENT_CHAR)
+            .or_else(|| self.glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR))
             .unwrap_or_else(|| {
                 panic!(
                     "Failed to find replacement characters {:?} or {:?}",
                     PRIMARY_REPLACEMENT_CHAR, FALLBACK_REPLACEMENT_CHAR
                 )
-            });
-        slf.replacement_glyph = replacement_glyph;
+            })
+    }
 
-        slf
+    /// Glyphs looked up from now on are considered used in this frame.
+    pub(crate) fn set_generation(&mut self, generation: u32) {
+        self.generation = generation;
+    }
+
+    /// Where in the texture atlas the glyphs looked up through this font are,
+    /// and the generation they were last used.
+    pub(crate) fn glyphs_in_atlas(&self) -> impl Iterator<Item = (UvRect, u32)> + '_ {
+        self.glyph_info_cache
+            .values()
+            .filter(|cached| !cached.glyph_info.uv_rect.is_nothing())
+            .map(|cached| (cached.glyph_info.uv_rect, cached.last_used))
+    }
+
+    /// Forget the cached glyphs, since the texture atlas has been repacked.
+    pub(crate) fn clear_glyph_cache(&mut self) {
+        self.glyph_info_cache.clear();
+        if !self.fonts.is_empty() {
+            self.replacement_glyph = self.find_replacement_glyph();
+        }
+    }
+
+    /// How many glyph lookups were cached, and how many were not.
+    pub(crate) fn cache_hits_and_misses(&self) -> (u64, u64) {
+        (self.cache_hits, self.cache_misses)
     }
 
     pub fn preload_common_characters(&mut self) {
@@ -302,7 +604,7 @@ impl Font {
     pub fn uv_rect(&self, c: char) -> UvRect {
         self.glyph_info_cache
             .get(&c)
-            .map(|gi| gi.1.uv_rect)
+            .map(|cached| cached.glyph_info.uv_rect)
             .unwrap_or_default()
     }
 
@@ -313,14 +615,25 @@ impl Font {
 
     /// `\n` will (intentionally) show up as the replacement character.
     fn glyph_info(&mut self, c: char) -> (FontIndex, GlyphInfo) {
-        if let Some(font_index_glyph_info) = self.glyph_info_cache.get(&c) {
-            return *font_index_glyph_info;
+        if let Some(cached) = self.glyph_info_cache.get_mut(&c) {
+            cached.last_used = self.generation;
+            self.cache_hits += 1;
+            return (cached.font_index, cached.glyph_info);
         }
+        self.cache_misses += 1;
 
-        let font_index_glyph_info = self.glyph_info_no_cache_or_fallback(c);
-        let font_index_glyph_info = font_index_glyph_info.unwrap_or(self.replacement_glyph);
-        self.glyph_info_cache.insert(c, font_index_glyph_info);
-        font_index_glyph_info
+        let (font_index, glyph_info) = self
+            .glyph_info_no_cache_or_fallback(c)
+            .unwrap_or(self.replacement_glyph);
+        self.glyph_info_cache.insert(
+            c,
+            CachedGlyph {
+                font_index,
+                glyph_info,
+                last_used: self.generation,
+            },
+        );
+        (font_index, glyph_info)
     }
 
     #[inline]
@@ -333,10 +646,28 @@ impl Font {
         (Some(font_impl), glyph_info)
     }
 
+    /// The font to shape `c` with, if any.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shaping_font_impl(&mut self, c: char) -> Option<Arc<FontImpl>> {
+        if self.fonts.is_empty() {
+            return None;
+        }
+        let (font_index, _) = self.glyph_info(c);
+        let font_impl = &self.fonts[font_index];
+        font_impl.can_shape(c).then(|| font_impl.clone())
+    }
+
     fn glyph_info_no_cache_or_fallback(&mut self, c: char) -> Option<(FontIndex, GlyphInfo)> {
         for (font_index, font_impl) in self.fonts.iter().enumerate() {
             if let Some(glyph_info) = font_impl.glyph_info(c) {
-                self.glyph_info_cache.insert(c, (font_index, glyph_info));
+                self.glyph_info_cache.insert(
+                    c,
+                    CachedGlyph {
+                        font_index,
+                        glyph_info,
+                        last_used: self.generation,
+                    },
+                );
                 return Some((font_index, glyph_info));
             }
         }
@@ -354,51 +685,79 @@ fn invisible_char(c: char) -> bool {
 
 fn allocate_glyph(
     atlas: &mut TextureAtlas,
-    font: &ab_glyph::FontArc,
+    font_impl: &FontImpl,
     glyph_id: ab_glyph::GlyphId,
-    scale_in_pixels: f32,
-    y_offset: f32,
-    pixels_per_point: f32,
 ) -> GlyphInfo {
     assert!(glyph_id.0 != 0);
     use ab_glyph::{Font as _, ScaleFont};
 
+    let font = &font_impl.ab_glyph_font;
+    let scale_in_pixels = font_impl.scale_in_pixels as f32;
+    let y_offset = font_impl.y_offset;
+    let pixels_per_point = font_impl.pixels_per_point;
+    let embolden = font_impl.style.embolden;
+
     let glyph =
         glyph_id.with_scale_and_position(scale_in_pixels, ab_glyph::Point { x: 0.0, y: 0.0 });
+    let scaled = font.as_scaled(scale_in_pixels);
+    let variable_face = font_impl.variable_face();
 
-    let uv_rect = font.outline_glyph(glyph).map(|glyph| {
-        let bb = glyph.px_bounds();
-        let glyph_width = bb.width() as usize;
-        let glyph_height = bb.height() as usize;
-        if glyph_width == 0 || glyph_height == 0 {
-            UvRect::default()
-        } else {
-            let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
-            glyph.draw(|x, y, v| {
-                if v > 0.0 {
-                    let px = glyph_pos.0 + x as usize;
-                    let py = glyph_pos.1 + y as usize;
-                    image[(px, py)] = v;
+    #[cfg(feature = "color_glyphs")]
+    let color_uv_rect = allocate_color_glyph(atlas, font_impl, glyph_id);
+    #[cfg(not(feature = "color_glyphs"))]
+    let color_uv_rect = None;
+
+    let outlined_glyph = match &variable_face {
+        Some(face) => outline_variable_glyph(face, glyph, scaled.scale_factor()),
+        None => font.outline_glyph(glyph),
+    };
+
+    let uv_rect = color_uv_rect.or_else(|| {
+        outlined_glyph.map(|glyph| {
+            let bb = glyph.px_bounds();
+            let glyph_width = bb.width() as usize;
+            let glyph_height = bb.height() as usize;
+            if glyph_width == 0 || glyph_height == 0 {
+                UvRect::default()
+            } else {
+                let glyph_width = glyph_width + embolden.ceil() as usize;
+                let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+                glyph.draw(|x, y, v| {
+                    if v > 0.0 {
+                        let px = glyph_pos.0 + x as usize;
+                        let py = glyph_pos.1 + y as usize;
+                        image[(px, py)] = v;
+                    }
+                });
+                if embolden > 0.0 {
+                    embolden_glyph(image, glyph_pos, (glyph_width, glyph_height), embolden);
+                }
+
+                let offset_in_pixels = vec2(bb.min.x as f32, scale_in_pixels + bb.min.y as f32);
+                let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
+                UvRect {
+                    offset,
+                    size: vec2(glyph_width as f32, glyph_height as f32) / pixels_per_point,
+                    min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+                    max: [
+                        (glyph_pos.0 + glyph_width) as u16,
+                        (glyph_pos.1 + glyph_height) as u16,
+                    ],
+                    colored: false,
+                    italic: font_impl.style.italic,
                 }
-            });
-
-            let offset_in_pixels = vec2(bb.min.x as f32, scale_in_pixels + bb.min.y as f32);
-            let offset = offset_in_pixels / pixels_per_point + y_offset * Vec2::Y;
-            UvRect {
-                offset,
-                size: vec2(glyph_width as f32, glyph_height as f32) / pixels_per_point,
-                min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
-                max: [
-                    (glyph_pos.0 + glyph_width) as u16,
-                    (glyph_pos.1 + glyph_height) as u16,
-                ],
             }
-        }
+        })
     });
     let uv_rect = uv_rect.unwrap_or_default();
 
-    let advance_width_in_points =
-        font.as_scaled(scale_in_pixels).h_advance(glyph_id) / pixels_per_point;
+    let advance_width_in_pixels = variable_face
+        .and_then(|face| face.glyph_hor_advance(ttf_parser::GlyphId(glyph_id.0)))
+        .map_or_else(
+            || scaled.h_advance(glyph_id),
+            |advance| advance as f32 * scaled.h_scale_factor(),
+        );
+    let advance_width_in_points = (advance_width_in_pixels + embolden) / pixels_per_point;
 
     GlyphInfo {
         id: glyph_id,
@@ -406,3 +765,469 @@ fn allocate_glyph(
         uv_rect,
     }
 }
+
+/// Outline a glyph of a variable font, which [`ab_glyph`] can't do.
+fn outline_variable_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph: ab_glyph::Glyph,
+    scale_factor: ab_glyph::PxScaleFactor,
+) -> Option<ab_glyph::OutlinedGlyph> {
+    let mut curves = OutlineCurves::default();
+    let bounds = face
+        .outline_glyph(ttf_parser::GlyphId(glyph.id.0), &mut curves)
+        .filter(|b| b.x_min < b.x_max && b.y_min < b.y_max)?;
+    let outline = ab_glyph::Outline {
+        bounds: ab_glyph::Rect {
+            min: ab_glyph::point(bounds.x_min.into(), bounds.y_max.into()),
+            max: ab_glyph::point(bounds.x_max.into(), bounds.y_min.into()),
+        },
+        curves: curves.curves,
+    };
+    Some(ab_glyph::OutlinedGlyph::new(glyph, outline, scale_factor))
+}
+
+/// Collects the outline of a glyph from [`ttf_parser`], for [`ab_glyph`] to rasterize.
+#[derive(Default)]
+struct OutlineCurves {
+    curves: Vec<ab_glyph::OutlineCurve>,
+    start: ab_glyph::Point,
+    last: ab_glyph::Point,
+}
+
+impl ttf_parser::OutlineBuilder for OutlineCurves {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = ab_glyph::point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = ab_glyph::point(x, y);
+        self.curves
+            .push(ab_glyph::OutlineCurve::Line(self.last, to));
+        self.last = to;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let to = ab_glyph::point(x, y);
+        let control = ab_glyph::point(x1, y1);
+        self.curves
+            .push(ab_glyph::OutlineCurve::Quad(self.last, control, to));
+        self.last = to;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let to = ab_glyph::point(x, y);
+        let control1 = ab_glyph::point(x1, y1);
+        let control2 = ab_glyph::point(x2, y2);
+        self.curves.push(ab_glyph::OutlineCurve::Cubic(
+            self.last, control1, control2, to,
+        ));
+        self.last = to;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.curves
+                .push(ab_glyph::OutlineCurve::Line(self.last, self.start));
+        }
+        self.last = self.start;
+    }
+}
+
+/// Synthetic bold: smear the glyph at `pos` in the `image` `strength` pixels to the right.
+///
+/// The glyph must already have room for this within its `w`.
+fn embolden_glyph(
+    image: &mut FontImage,
+    pos: (usize, usize),
+    (w, h): (usize, usize),
+    strength: f32,
+) {
+    let whole = strength.floor() as usize;
+    let fraction = strength - whole as f32;
+    for y in pos.1..pos.1 + h {
+        // Right to left, so that we read the pixels before smearing them:
+        for x in (0..w).rev() {
+            let mut coverage = image[(pos.0 + x, y)];
+            for dx in 1..=whole.min(x) {
+                coverage = coverage.max(image[(pos.0 + x - dx, y)]);
+            }
+            if fraction > 0.0 && whole < x {
+                coverage = coverage.max(fraction * image[(pos.0 + x - whole - 1, y)]);
+            }
+            image[(pos.0 + x, y)] = coverage;
+        }
+    }
+}
+
+/// Rasterize a glyph that has its own colors, like an emoji from a `CBDT`, `sbix` or `COLR` font table.
+///
+/// Returns `None` for glyphs that only have an outline.
+#[cfg(feature = "color_glyphs")]
+fn allocate_color_glyph(
+    atlas: &mut TextureAtlas,
+    font_impl: &FontImpl,
+    glyph_id: ab_glyph::GlyphId,
+) -> Option<UvRect> {
+    let scale_in_pixels = font_impl.scale_in_pixels as f32;
+    let pixels_per_point = font_impl.pixels_per_point;
+    let (source, offset_in_pixels) =
+        bitmap_color_glyph(&font_impl.ab_glyph_font, glyph_id, scale_in_pixels)
+            .or_else(|| layered_color_glyph(font_impl, glyph_id, scale_in_pixels))?;
+
+    let [glyph_width, glyph_height] = source.size;
+    let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+    for y in 0..glyph_height {
+        for x in 0..glyph_width {
+            let color = source[(x, y)];
+            let pos = (glyph_pos.0 + x, glyph_pos.1 + y);
+            image[pos] = color.a() as f32 / 255.0;
+            image.set_color(pos, color);
+        }
+    }
+
+    Some(UvRect {
+        offset: offset_in_pixels / pixels_per_point + font_impl.y_offset * Vec2::Y,
+        size: vec2(glyph_width as f32, glyph_height as f32) / pixels_per_point,
+        min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+        max: [
+            (glyph_pos.0 + glyph_width) as u16,
+            (glyph_pos.1 + glyph_height) as u16,
+        ],
+        colored: true,
+        italic: false,
+    })
+}
+
+/// A `CBDT` or `sbix` PNG bitmap, scaled to the font size,
+/// and the offset of its top left corner in pixels.
+#[cfg(feature = "color_glyphs")]
+fn bitmap_color_glyph(
+    font: &ab_glyph::FontArc,
+    glyph_id: ab_glyph::GlyphId,
+    scale_in_pixels: f32,
+) -> Option<(crate::ColorImage, Vec2)> {
+    use ab_glyph::{Font as _, ScaleFont as _};
+
+    let pixels_per_em = font.as_scaled(scale_in_pixels).h_scale_factor() * font.units_per_em()?;
+    let raster = font.glyph_raster_image(glyph_id, pixels_per_em.round() as u16)?;
+    let source = match raster.format {
+        ab_glyph::GlyphImageFormat::Png => decode_png(raster.data)?,
+        _ => return None,
+    };
+
+    // The bitmaps come in a few sizes, so we need to scale them:
+    let scale = pixels_per_em / raster.scale;
+    let glyph_width = ((source.width() as f32 * scale).round() as usize).max(1);
+    let glyph_height = ((source.height() as f32 * scale).round() as usize).max(1);
+    let mut image = crate::ColorImage::new([glyph_width, glyph_height], Color32::TRANSPARENT);
+    for y in 0..glyph_height {
+        for x in 0..glyph_width {
+            image[(x, y)] = downsampled_pixel(&source, [x, y], 1.0 / scale);
+        }
+    }
+
+    // The origin is the bottom left corner of the bitmap, relative to the baseline, with y up.
+    let left = raster.origin.x * scale;
+    let top = -raster.origin.y * scale - glyph_height as f32;
+    Some((image, vec2(left, scale_in_pixels + top)))
+}
+
+/// A `COLR` glyph: outlines of other glyphs stacked on top of each other,
+/// each filled with a color from the `CPAL` palette.
+/// Also returns the offset of the top left corner in pixels.
+#[cfg(feature = "color_glyphs")]
+fn layered_color_glyph(
+    font_impl: &FontImpl,
+    glyph_id: ab_glyph::GlyphId,
+    scale_in_pixels: f32,
+) -> Option<(crate::ColorImage, Vec2)> {
+    use ab_glyph::Font as _;
+
+    let font_data = font_impl.font_data.as_ref()?;
+    let face = ttf_parser::Face::from_slice(&font_data.font, font_data.index).ok()?;
+    let colr = face.table_data(ttf_parser::Tag::from_bytes(b"COLR"))?;
+    let cpal = face.table_data(ttf_parser::Tag::from_bytes(b"CPAL"))?;
+    let layers: Vec<_> = colr_layers(colr, cpal, glyph_id.0)?
+        .into_iter()
+        .filter_map(|(layer_id, color)| {
+            let glyph = ab_glyph::GlyphId(layer_id)
+                .with_scale_and_position(scale_in_pixels, ab_glyph::point(0.0, 0.0));
+            Some((font_impl.ab_glyph_font.outline_glyph(glyph)?, color))
+        })
+        .collect();
+
+    let mut min = ab_glyph::point(f32::INFINITY, f32::INFINITY);
+    let mut max = ab_glyph::point(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for (outline, _) in &layers {
+        let bb = outline.px_bounds();
+        min = ab_glyph::point(min.x.min(bb.min.x), min.y.min(bb.min.y));
+        max = ab_glyph::point(max.x.max(bb.max.x), max.y.max(bb.max.y));
+    }
+    if !(min.x < max.x && min.y < max.y) {
+        return None;
+    }
+
+    let size = [(max.x - min.x) as usize, (max.y - min.y) as usize];
+    let mut image = crate::ColorImage::new(size, Color32::TRANSPARENT);
+    for (outline, color) in layers {
+        // The pixel bounds are whole pixels, so the layers line up:
+        let bb = outline.px_bounds();
+        let (dx, dy) = ((bb.min.x - min.x) as usize, (bb.min.y - min.y) as usize);
+        outline.draw(|x, y, v| {
+            let pixel = &mut image[(dx + x as usize, dy + y as usize)];
+            let src = color.linear_multiply(v.min(1.0));
+            let keep = 1.0 - src.a() as f32 / 255.0;
+            let [r, g, b, a] = src.to_array();
+            let [dst_r, dst_g, dst_b, dst_a] = pixel.linear_multiply(keep).to_array();
+            *pixel = Color32::from_rgba_premultiplied(
+                r.saturating_add(dst_r),
+                g.saturating_add(dst_g),
+                b.saturating_add(dst_b),
+                a.saturating_add(dst_a),
+            );
+        });
+    }
+
+    Some((image, vec2(min.x, scale_in_pixels + min.y)))
+}
+
+/// The layers of a glyph in a version 0 `COLR` table, bottom to top,
+/// with their colors from the first palette of the `CPAL` table.
+///
+/// Returns `None` if the glyph has no layers, or if a layer is in the text color.
+/// Such glyphs are painted as plain outlines instead, so that they follow the text color.
+#[cfg(feature = "color_glyphs")]
+fn colr_layers(colr: &[u8], cpal: &[u8], glyph_id: u16) -> Option<Vec<(u16, Color32)>> {
+    let u16_at = |data: &[u8], offset: usize| -> Option<u16> {
+        Some(u16::from_be_bytes(
+            data.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    };
+    let u32_at = |data: &[u8], offset: usize| -> Option<usize> {
+        Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize)
+    };
+
+    // Only the base glyphs of version 0 are read. Version 1 tables start the same way.
+    let num_base_glyphs = u16_at(colr, 2)? as usize;
+    let base_glyphs = u32_at(colr, 4)?;
+    let layer_records = u32_at(colr, 8)?;
+    let num_layers = u16_at(colr, 12)? as usize;
+    let base_glyph = (0..num_base_glyphs)
+        .map(|i| base_glyphs + 6 * i)
+        .find(|&record| u16_at(colr, record) == Some(glyph_id))?;
+    let first_layer = u16_at(colr, base_glyph + 2)? as usize;
+    let layer_count = u16_at(colr, base_glyph + 4)? as usize;
+    if layer_count == 0 || first_layer + layer_count > num_layers {
+        return None;
+    }
+
+    let num_palette_entries = u16_at(cpal, 2)?;
+    let color_records = u32_at(cpal, 8)?;
+    let first_color = u16_at(cpal, 12)? as usize;
+
+    (first_layer..first_layer + layer_count)
+        .map(|layer| {
+            let record = layer_records + 4 * layer;
+            let layer_id = u16_at(colr, record)?;
+            let palette_index = u16_at(colr, record + 2)?;
+            if palette_index >= num_palette_entries {
+                return None; // 0xFFFF is the text color
+            }
+            let color = color_records + 4 * (first_color + palette_index as usize);
+            let [b, g, r, a]: [u8; 4] = cpal.get(color..color + 4)?.try_into().ok()?;
+            Some((layer_id, Color32::from_rgba_unmultiplied(r, g, b, a)))
+        })
+        .collect()
+}
+
+#[cfg(feature = "color_glyphs")]
+fn decode_png(data: &[u8]) -> Option<crate::ColorImage> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    let buffer = &buffer[..info.buffer_size()];
+
+    let rgba: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buffer.to_vec(),
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|la| [la[0], la[0], la[0], la[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        png::ColorType::Indexed => return None, // expanded by `normalize_to_color8`
+    };
+    let size = [info.width as usize, info.height as usize];
+    Some(crate::ColorImage::from_rgba_unmultiplied(size, &rgba))
+}
+
+/// The average color of the `source` pixels covered by the pixel `[x, y]`
+/// of an image that is `step` times smaller.
+#[cfg(feature = "color_glyphs")]
+fn downsampled_pixel(source: &crate::ColorImage, [x, y]: [usize; 2], step: f32) -> Color32 {
+    let range = |i: usize, len: usize| {
+        let min = ((i as f32 * step) as usize).min(len - 1);
+        let max = (((i + 1) as f32 * step).ceil() as usize).clamp(min + 1, len);
+        min..max
+    };
+    let mut sum = [0_u32; 4];
+    let mut count = 0;
+    for sy in range(y, source.height()) {
+        for sx in range(x, source.width()) {
+            let color = source[(sx, sy)];
+            for (sum, value) in sum.iter_mut().zip(color.to_array()) {
+                *sum += value as u32;
+            }
+            count += 1;
+        }
+    }
+    let [r, g, b, a] = sum.map(|sum| (sum / count) as u8);
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+#[cfg(all(test, feature = "color_glyphs"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_glyph_pixels() {
+        // A 2x2 image: red, green, blue and transparent.
+        let rgba = [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0];
+        let mut png_data = vec![];
+        {
+            let mut encoder = png::Encoder::new(&mut png_data, 2, 2);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&rgba).unwrap();
+        }
+
+        let image = decode_png(&png_data).unwrap();
+        assert_eq!(image.size, [2, 2]);
+        assert_eq!(image[(1, 0)], Color32::from_rgb(0, 255, 0));
+
+        // Half the size averages all four pixels:
+        let average = downsampled_pixel(&image, [0, 0], 2.0);
+        assert_eq!(average, Color32::from_rgba_premultiplied(63, 63, 63, 191));
+
+        let mut font_image = crate::FontImage::new([2, 1]);
+        font_image[(1, 0)] = 1.0;
+        font_image.set_color((0, 0), Color32::RED);
+        let pixels: Vec<Color32> = font_image.srgba_pixels(1.0).collect();
+        assert_eq!(pixels, vec![Color32::RED, Color32::WHITE]);
+    }
+
+    #[test]
+    fn colr_glyph_layers() {
+        let be =
+            |values: &[u16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+
+        // Glyph 5 is glyph 7 in palette color 0 under glyph 8 in palette color 1.
+        // Glyph 6 is glyph 7 in the text color.
+        let colr = be(&[
+            0, 2, 0, 14, 0, 26, 3, // header
+            5, 0, 2, 6, 2, 1, // base glyphs
+            7, 0, 8, 1, 7, 0xFFFF, // layers
+        ]);
+        let mut cpal = be(&[0, 2, 1, 2, 0, 14, 0]);
+        cpal.extend([0, 0, 255, 255, 255, 0, 0, 128]); // BGRA
+
+        let layers = colr_layers(&colr, &cpal, 5).unwrap();
+        let blue = Color32::from_rgba_unmultiplied(0, 0, 255, 128);
+        assert_eq!(layers, vec![(7, Color32::RED), (8, blue)]);
+        assert_eq!(colr_layers(&colr, &cpal, 6), None);
+        assert_eq!(colr_layers(&colr, &cpal, 7), None);
+    }
+
+    /// A copy of the `font` file with extra tables.
+    fn with_tables(font: &[u8], extra: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+        let u32_at =
+            |offset: usize| u32::from_be_bytes(font[offset..offset + 4].try_into().unwrap());
+        let num_tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        let mut tables: Vec<([u8; 4], Vec<u8>)> = (0..num_tables)
+            .map(|i| {
+                let record = 12 + 16 * i;
+                let tag = font[record..record + 4].try_into().unwrap();
+                let (offset, length) = (u32_at(record + 8) as usize, u32_at(record + 12) as usize);
+                (tag, font[offset..offset + length].to_vec())
+            })
+            .chain(extra)
+            .collect();
+        tables.sort_by_key(|(tag, _)| *tag); // looked up with a binary search
+
+        let mut out = font[0..4].to_vec();
+        out.extend((tables.len() as u16).to_be_bytes());
+        out.extend([0; 6]); // search hints, unused
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            out.extend(tag);
+            out.extend([0; 4]); // checksum, unused
+            out.extend((offset as u32).to_be_bytes());
+            out.extend((data.len() as u32).to_be_bytes());
+            offset += (data.len() + 3) / 4 * 4;
+        }
+        for (_, data) in tables {
+            out.resize((out.len() + 3) / 4 * 4, 0);
+            out.extend(data);
+        }
+        out
+    }
+
+    #[test]
+    fn colr_glyph_in_atlas() {
+        use crate::text::{FontData, FontDefinitions, FontFamily, FontId, Fonts};
+        use ab_glyph::Font as _;
+
+        // Paint the `A` of Hack as a single red layer:
+        let hack = include_bytes!("../../fonts/Hack-Regular.ttf");
+        let a = ab_glyph::FontRef::try_from_slice(hack)
+            .unwrap()
+            .glyph_id('A')
+            .0;
+        let be =
+            |values: &[u16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+        let colr = be(&[0, 1, 0, 14, 0, 20, 1, a, 0, 1, a, 0]);
+        let mut cpal = be(&[0, 1, 1, 1, 0, 14, 0]);
+        cpal.extend([0, 0, 255, 255]); // BGRA
+        let font = with_tables(hack, vec![(*b"COLR", colr), (*b"CPAL", cpal)]);
+
+        let mut definitions = FontDefinitions::empty();
+        let name = "colr".to_owned();
+        definitions
+            .font_data
+            .insert(name.clone(), FontData::from_owned(font));
+        definitions
+            .families
+            .insert(FontFamily::Proportional, vec![name]);
+        let fonts = Fonts::new(1.0, 1024, definitions);
+
+        let galley =
+            fonts.layout_no_wrap("A".to_owned(), FontId::proportional(32.0), Color32::WHITE);
+        // With `shaping`, the glyph is drawn from the shaped run instead:
+        let row = &galley.rows[0];
+        let uv_rect = row
+            .shaped
+            .first()
+            .map_or(row.glyphs[0].uv_rect, |glyph| glyph.uv_rect);
+        assert!(uv_rect.colored);
+
+        let image = match fonts.font_image_delta().unwrap().image {
+            crate::ImageData::Font(image) => image,
+            crate::ImageData::Color(_) => panic!("the font atlas is a font image"),
+        };
+        let [min_x, min_y] = uv_rect.min.map(usize::from);
+        let [max_x, max_y] = uv_rect.max.map(usize::from);
+        let colors: Vec<Color32> = (min_y..max_y)
+            .flat_map(|y| (min_x..max_x).map(move |x| (x, y)))
+            .map(|(x, y)| image.colors[y * image.width() + x])
+            .collect();
+        assert!(colors.contains(&Color32::RED));
+        assert!(colors.iter().all(|color| color.g() == 0 && color.b() == 0));
+    }
+}
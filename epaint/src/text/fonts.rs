@@ -4,7 +4,7 @@ use std::sync::Arc;
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
-        font::{Font, FontImpl},
+        font::{FaceStyle, Font, FontImpl, UvRect},
         Galley, LayoutJob,
     },
     TextureAtlas,
@@ -22,7 +22,12 @@ pub struct FontId {
 
     /// What font family to use.
     pub family: FontFamily,
-    // TODO(emilk): weight (bold), italics, …
+
+    /// How bold the text should be.
+    ///
+    /// Italics is selected with [`crate::text::TextFormat::italics`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weight: FontWeight,
 }
 
 impl Default for FontId {
@@ -31,6 +36,7 @@ impl Default for FontId {
         Self {
             size: 14.0,
             family: FontFamily::Proportional,
+            weight: FontWeight::REGULAR,
         }
     }
 }
@@ -38,7 +44,11 @@ impl Default for FontId {
 impl FontId {
     #[inline]
     pub const fn new(size: f32, family: FontFamily) -> Self {
-        Self { size, family }
+        Self {
+            size,
+            family,
+            weight: FontWeight::REGULAR,
+        }
     }
 
     #[inline]
@@ -50,15 +60,56 @@ impl FontId {
     pub const fn monospace(size: f32) -> Self {
         Self::new(size, FontFamily::Monospace)
     }
+
+    /// Select how bold the text should be.
+    #[inline]
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
 impl std::hash::Hash for FontId {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { size, family } = self;
+        let Self {
+            size,
+            family,
+            weight,
+        } = self;
         crate::f32_hash(state, *size);
         family.hash(state);
+        weight.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How thick the strokes of a font are, from 100 (thin) to 900 (black), like in CSS.
+///
+/// If there is no face of a font for the weight, [`Fonts`] uses the `wght` axis of a variable font,
+/// or else makes the glyphs bolder itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const REGULAR: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        Self::REGULAR
     }
 }
 
@@ -148,6 +199,30 @@ impl FontData {
     }
 }
 
+/// Another face of a font, e.g. its bold or italic variant.
+///
+/// See [`FontDefinitions::faces`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontFace {
+    /// The name of the face, a key into [`FontDefinitions::font_data`].
+    pub name: String,
+
+    pub weight: FontWeight,
+
+    pub italic: bool,
+}
+
+impl FontFace {
+    pub fn new(name: impl Into<String>, weight: FontWeight, italic: bool) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            italic,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Extra scale and vertical tweak to apply to all text of a certain font.
@@ -185,6 +260,14 @@ impl Default for FontTweak {
 
 // ----------------------------------------------------------------------------
 
+/// Does the font have a `COLR` table? Its glyphs are painted from the font file.
+fn has_color_layers(font_data: &FontData) -> bool {
+    ttf_parser::Face::from_slice(&font_data.font, font_data.index).map_or(false, |face| {
+        face.table_data(ttf_parser::Tag::from_bytes(b"COLR"))
+            .is_some()
+    })
+}
+
 fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontArc {
     match &data.font {
         std::borrow::Cow::Borrowed(bytes) => {
@@ -241,6 +324,15 @@ pub struct FontDefinitions {
     /// the first font and then move to the second, and so on.
     /// So the first font is the primary, and then comes a list of fallbacks in order of priority.
     pub families: BTreeMap<FontFamily, Vec<String>>,
+
+    /// The other faces of the fonts in [`Self::families`], e.g. bold and italic.
+    ///
+    /// Maps the name of a font to its other faces. The font itself is the regular, upright face.
+    ///
+    /// For a [`FontWeight`] or italics that a font has no face for,
+    /// `epaint` uses the `wght`, `ital` and `slnt` axes of variable fonts.
+    /// Failing that, it makes the glyphs bolder itself, and slants them.
+    pub faces: BTreeMap<String, Vec<FontFace>>,
 }
 
 impl Default for FontDefinitions {
@@ -307,6 +399,7 @@ impl Default for FontDefinitions {
         Self {
             font_data,
             families,
+            faces: Default::default(),
         }
     }
 }
@@ -321,6 +414,7 @@ impl FontDefinitions {
         Self {
             font_data: Default::default(),
             families,
+            faces: Default::default(),
         }
     }
 }
@@ -369,9 +463,7 @@ impl Fonts {
         let pixels_per_point_changed =
             (fonts_and_cache.fonts.pixels_per_point - pixels_per_point).abs() > 1e-3;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
-        let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
-        let needs_recreate =
-            pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
+        let needs_recreate = pixels_per_point_changed || max_texture_side_changed;
 
         if needs_recreate {
             let definitions = fonts_and_cache.fonts.definitions.clone();
@@ -380,6 +472,9 @@ impl Fonts {
                 fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
                 galley_cache: Default::default(),
             };
+        } else if fonts_and_cache.fonts.begin_frame() {
+            // The cached galleys refer to glyphs that have been evicted or moved:
+            fonts_and_cache.galley_cache = Default::default();
         }
 
         fonts_and_cache.galley_cache.flush_cache();
@@ -466,6 +561,12 @@ impl Fonts {
         self.lock().fonts.atlas.lock().fill_ratio()
     }
 
+    /// Glyph cache hits and atlas evictions since the fonts were created,
+    /// or since `pixels_per_point` last changed.
+    pub fn font_atlas_stats(&self) -> FontAtlasStats {
+        self.lock().fonts.atlas_stats()
+    }
+
     /// Will wrap text at the given width and line break at `\n`.
     ///
     /// The implementation uses memoization so repeated calls are cheap.
@@ -535,7 +636,12 @@ pub struct FontsImpl {
     definitions: FontDefinitions,
     atlas: Arc<Mutex<TextureAtlas>>,
     font_impl_cache: FontImplCache,
-    sized_family: ahash::AHashMap<(u32, FontFamily), Font>,
+    sized_family: ahash::AHashMap<(u32, FontFamily, FontWeight, bool), Font>,
+
+    /// Frame counter, used to find the least recently used glyphs.
+    generation: u32,
+    num_evictions: u64,
+    num_repacks: u64,
 }
 
 impl FontsImpl {
@@ -558,8 +664,7 @@ impl FontsImpl {
 
         let atlas = Arc::new(Mutex::new(atlas));
 
-        let font_impl_cache =
-            FontImplCache::new(atlas.clone(), pixels_per_point, &definitions.font_data);
+        let font_impl_cache = FontImplCache::new(atlas.clone(), pixels_per_point, &definitions);
 
         Self {
             pixels_per_point,
@@ -568,6 +673,9 @@ impl FontsImpl {
             atlas,
             font_impl_cache,
             sized_family: Default::default(),
+            generation: 0,
+            num_evictions: 0,
+            num_repacks: 0,
         }
     }
 
@@ -581,13 +689,24 @@ impl FontsImpl {
         &self.definitions
     }
 
-    /// Get the right font implementation from size and [`FontFamily`].
+    /// Get the right font implementation from size, [`FontFamily`] and [`FontWeight`].
     pub fn font(&mut self, font_id: &FontId) -> &mut Font {
-        let FontId { size, family } = font_id;
+        self.styled_font(font_id, false)
+    }
+
+    /// Get the right font implementation from size, [`FontFamily`] and [`FontWeight`],
+    /// and whether it should be italic.
+    pub fn styled_font(&mut self, font_id: &FontId, italics: bool) -> &mut Font {
+        let FontId {
+            size,
+            family,
+            weight,
+        } = font_id;
         let scale_in_pixels = self.font_impl_cache.scale_as_pixels(*size);
 
-        self.sized_family
-            .entry((scale_in_pixels, family.clone()))
+        let font = self
+            .sized_family
+            .entry((scale_in_pixels, family.clone(), *weight, italics))
             .or_insert_with(|| {
                 let fonts = &self.definitions.families.get(family);
                 let fonts = fonts.unwrap_or_else(|| {
@@ -596,11 +715,20 @@ impl FontsImpl {
 
                 let fonts: Vec<Arc<FontImpl>> = fonts
                     .iter()
-                    .map(|font_name| self.font_impl_cache.font_impl(scale_in_pixels, font_name))
+                    .map(|font_name| {
+                        self.font_impl_cache
+                            .font_impl(scale_in_pixels, font_name, *weight, italics)
+                    })
                     .collect();
+                #[cfg(feature = "shaping")]
+                for font_impl in &fonts {
+                    font_impl.set_generation(self.generation);
+                }
 
                 Font::new(fonts)
-            })
+            });
+        font.set_generation(self.generation);
+        font
     }
 
     /// Width of this character in points.
@@ -612,6 +740,132 @@ impl FontsImpl {
     fn row_height(&mut self, font_id: &FontId) -> f32 {
         self.font(font_id).row_height()
     }
+
+    /// Call at the start of each frame.
+    ///
+    /// Returns `true` if glyphs were evicted from the font atlas,
+    /// in which case all [`Galley`]s laid out before are out of date.
+    fn begin_frame(&mut self) -> bool {
+        self.generation = self.generation.wrapping_add(1);
+        #[cfg(feature = "shaping")]
+        for font_impl in self.font_impl_cache.cache.values() {
+            font_impl.set_generation(self.generation);
+        }
+
+        let font_atlas_almost_full = self.atlas.lock().fill_ratio() > 0.8;
+        if font_atlas_almost_full {
+            self.evict_glyphs();
+        }
+        font_atlas_almost_full
+    }
+
+    /// Evict the least recently used glyphs from the font atlas,
+    /// and pack the others tightly at the top of it.
+    fn evict_glyphs(&mut self) {
+        let mut atlas = self.atlas.lock();
+
+        // Only keep this many texels, so that the atlas doesn't fill up again right away.
+        // After an overflow some glyphs have been painted over, so we throw them all away.
+        let texel_budget = if atlas.overflowed() {
+            0
+        } else {
+            atlas.glyph_capacity() * 2 / 5
+        };
+
+        // When each glyph was last used, by where it is in the atlas:
+        let mut glyphs: ahash::AHashMap<[u16; 2], (u32, UvRect)> = Default::default();
+        let mut add_glyph = |uv_rect: UvRect, last_used: u32| {
+            let entry = glyphs.entry(uv_rect.min).or_insert((last_used, uv_rect));
+            entry.0 = entry.0.max(last_used);
+        };
+        for font in self.sized_family.values() {
+            for (uv_rect, last_used) in font.glyphs_in_atlas() {
+                add_glyph(uv_rect, last_used);
+            }
+        }
+        #[cfg(feature = "shaping")]
+        for font_impl in self.font_impl_cache.cache.values() {
+            for (uv_rect, last_used) in font_impl.shaped_glyphs_in_atlas() {
+                add_glyph(uv_rect, last_used);
+            }
+        }
+
+        let mut glyphs: Vec<_> = glyphs.into_iter().map(|(_, glyph)| glyph).collect();
+        glyphs.sort_by_key(|(last_used, _)| std::cmp::Reverse(*last_used));
+
+        let mut kept = vec![];
+        let mut num_texels = 0;
+        for (_, uv_rect) in glyphs {
+            let [w, h] = glyph_size(&uv_rect);
+            num_texels += (w + 1) * (h + 1); // including padding
+            if num_texels > texel_budget {
+                break;
+            }
+            kept.push(uv_rect);
+        }
+
+        // Tallest first, so glyphs of similar height share rows:
+        kept.sort_by_key(|uv_rect| std::cmp::Reverse(glyph_size(uv_rect)[1]));
+
+        let old_image = atlas.clear_glyphs();
+        let moved: ahash::AHashMap<[u16; 2], [u16; 2]> = kept
+            .iter()
+            .map(|uv_rect| {
+                let [w, h] = glyph_size(uv_rect);
+                let pos = (uv_rect.min[0] as usize, uv_rect.min[1] as usize);
+                let (x, y) = atlas.copy_glyph(&old_image, pos, (w, h));
+                (uv_rect.min, [x as u16, y as u16])
+            })
+            .collect();
+        for font_impl in self.font_impl_cache.cache.values() {
+            self.num_evictions += font_impl.repack_glyphs(&moved) as u64;
+        }
+        self.num_repacks += 1;
+        drop(atlas); // the fonts need it to look up their replacement glyphs again
+
+        for font in self.sized_family.values_mut() {
+            font.clear_glyph_cache();
+        }
+    }
+
+    fn atlas_stats(&self) -> FontAtlasStats {
+        let mut stats = FontAtlasStats {
+            evictions: self.num_evictions,
+            repacks: self.num_repacks,
+            ..Default::default()
+        };
+        for font in self.sized_family.values() {
+            let (hits, misses) = font.cache_hits_and_misses();
+            stats.hits += hits;
+            stats.misses += misses;
+        }
+        stats
+    }
+}
+
+fn glyph_size(uv_rect: &UvRect) -> [usize; 2] {
+    [
+        (uv_rect.max[0] - uv_rect.min[0]) as usize,
+        (uv_rect.max[1] - uv_rect.min[1]) as usize,
+    ]
+}
+
+/// Counters for the glyph caches and the font atlas, for debugging.
+///
+/// See [`Fonts::font_atlas_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FontAtlasStats {
+    /// Glyph lookups that were already cached.
+    pub hits: u64,
+
+    /// Glyph lookups that were not cached, and so may have rasterized a new glyph.
+    pub misses: u64,
+
+    /// Glyphs removed from the atlas to make room for new ones.
+    pub evictions: u64,
+
+    /// How many times the atlas has been cleared of old glyphs and repacked.
+    pub repacks: u64,
 }
 
 // ----------------------------------------------------------------------------
@@ -672,17 +926,27 @@ struct FontImplCache {
     pixels_per_point: f32,
     ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
 
-    /// Map font pixel sizes and names to the cached [`FontImpl`].
-    cache: ahash::AHashMap<(u32, String), Arc<FontImpl>>,
+    /// See [`FontDefinitions::faces`].
+    faces: BTreeMap<String, Vec<FontFace>>,
+
+    /// The axes of the variable fonts.
+    variation_axes: BTreeMap<String, Vec<ttf_parser::VariationAxis>>,
+
+    /// The font files, for text shaping and variable fonts.
+    font_data: BTreeMap<String, Arc<FontData>>,
+
+    /// Map font pixel sizes, names and styles to the cached [`FontImpl`].
+    cache: ahash::AHashMap<(u32, String, FaceStyle), Arc<FontImpl>>,
 }
 
 impl FontImplCache {
     pub fn new(
         atlas: Arc<Mutex<TextureAtlas>>,
         pixels_per_point: f32,
-        font_data: &BTreeMap<String, FontData>,
+        definitions: &FontDefinitions,
     ) -> Self {
-        let ab_glyph_fonts = font_data
+        let ab_glyph_fonts = definitions
+            .font_data
             .iter()
             .map(|(name, font_data)| {
                 let tweak = font_data.tweak;
@@ -691,10 +955,35 @@ impl FontImplCache {
             })
             .collect();
 
+        let variation_axes: BTreeMap<String, Vec<ttf_parser::VariationAxis>> = definitions
+            .font_data
+            .iter()
+            .filter_map(|(name, font_data)| {
+                let face = ttf_parser::Face::from_slice(&font_data.font, font_data.index).ok()?;
+                let axes = face.variation_axes().into_iter().collect();
+                face.is_variable().then(|| (name.clone(), axes))
+            })
+            .collect();
+
+        // Only keep the font files we need, since they can be big:
+        let font_data = definitions
+            .font_data
+            .iter()
+            .filter(|(name, font_data)| {
+                cfg!(feature = "shaping")
+                    || variation_axes.contains_key(*name)
+                    || (cfg!(feature = "color_glyphs") && has_color_layers(font_data))
+            })
+            .map(|(name, font_data)| (name.clone(), Arc::new(font_data.clone())))
+            .collect();
+
         Self {
             atlas,
             pixels_per_point,
             ab_glyph_fonts,
+            faces: definitions.faces.clone(),
+            variation_axes,
+            font_data,
             cache: Default::default(),
         }
     }
@@ -708,11 +997,18 @@ impl FontImplCache {
         scale_in_pixels.round() as u32
     }
 
-    pub fn font_impl(&mut self, scale_in_pixels: u32, font_name: &str) -> Arc<FontImpl> {
+    pub fn font_impl(
+        &mut self,
+        scale_in_pixels: u32,
+        font_name: &str,
+        weight: FontWeight,
+        italic: bool,
+    ) -> Arc<FontImpl> {
+        let face = self.closest_face(font_name, weight, italic);
         let (tweak, ab_glyph_font) = self
             .ab_glyph_fonts
-            .get(font_name)
-            .unwrap_or_else(|| panic!("No font data found for {:?}", font_name))
+            .get(&face.name)
+            .unwrap_or_else(|| panic!("No font data found for {:?}", face.name))
             .clone();
 
         let scale_in_pixels = (scale_in_pixels as f32 * tweak.scale).round() as u32;
@@ -722,18 +1018,186 @@ impl FontImplCache {
             scale_in_points * tweak.y_offset_factor
         } + tweak.y_offset;
 
+        let style = self.face_style(&face, scale_in_pixels, weight, italic);
+
         self.cache
-            .entry((scale_in_pixels, font_name.to_owned()))
+            .entry((scale_in_pixels, face.name.clone(), style.clone()))
             .or_insert_with(|| {
-                Arc::new(FontImpl::new(
+                let font_impl = FontImpl::new(
                     self.atlas.clone(),
                     self.pixels_per_point,
-                    font_name.to_owned(),
+                    face.name.clone(),
                     ab_glyph_font,
                     scale_in_pixels,
                     y_offset_points,
-                ))
+                )
+                .with_style(style);
+                let font_impl = match self.font_data.get(&face.name) {
+                    Some(font_data) => font_impl.with_font_data(font_data.clone()),
+                    None => font_impl,
+                };
+                Arc::new(font_impl)
             })
             .clone()
     }
+
+    /// The face of the font closest to the given weight, and italic if possible.
+    ///
+    /// The font itself is the regular, upright face.
+    fn closest_face(&self, font_name: &str, weight: FontWeight, italic: bool) -> FontFace {
+        let regular = FontFace::new(font_name, FontWeight::REGULAR, false);
+        let other_faces = self.faces.get(font_name).into_iter().flatten();
+        std::iter::once(&regular)
+            .chain(other_faces)
+            .min_by_key(|face| {
+                let distance = (face.weight.0 as i32 - weight.0 as i32).abs();
+                // We can make a face bolder, but not lighter, so prefer the lighter face on a tie:
+                (face.italic != italic, distance, face.weight > weight)
+            })
+            .unwrap_or(&regular)
+            .clone()
+    }
+
+    /// How to make `face` look like the requested weight and italics.
+    fn face_style(
+        &self,
+        face: &FontFace,
+        scale_in_pixels: u32,
+        weight: FontWeight,
+        italic: bool,
+    ) -> FaceStyle {
+        let axes = self.variation_axes.get(&face.name);
+        let axis = |tag: &[u8; 4]| {
+            let tag = ttf_parser::Tag::from_bytes(tag);
+            axes.into_iter().flatten().find(|axis| axis.tag == tag)
+        };
+
+        let mut style = FaceStyle {
+            italic: face.italic,
+            ..Default::default()
+        };
+
+        let extra_weight = weight.0 as f32 - face.weight.0 as f32;
+        if let Some(wght) = axis(b"wght") {
+            if extra_weight != 0.0 {
+                let value = (wght.def_value + extra_weight).clamp(wght.min_value, wght.max_value);
+                style.variations.push((wght.tag, value));
+            }
+        } else if extra_weight > 0.0 {
+            // From regular to bold is a twentyfourth of the font size, like in FreeType:
+            style.embolden = scale_in_pixels as f32 / 24.0 * extra_weight / 300.0;
+        }
+
+        if italic && !face.italic {
+            if let Some(ital) = axis(b"ital") {
+                style.variations.push((ital.tag, ital.max_value.min(1.0)));
+                style.italic = true;
+            } else if let Some(slnt) = axis(b"slnt") {
+                // Negative angles lean to the right:
+                style.variations.push((slnt.tag, slnt.min_value.max(-12.0)));
+                style.italic = true;
+            }
+        }
+
+        style
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_font_atlas_eviction() {
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let layout = |size: f32| {
+        let text = ('!'..='~').collect::<String>();
+        fonts.layout_no_wrap(text, FontId::proportional(size), crate::Color32::WHITE);
+    };
+
+    let mut size = 10.0;
+    while fonts.font_atlas_fill_ratio() <= 0.8 {
+        fonts.begin_frame(1.0, 1024);
+        layout(size);
+        size += 1.0;
+    }
+    assert_eq!(fonts.font_atlas_stats().repacks, 0);
+
+    fonts.begin_frame(1.0, 1024);
+    let stats = fonts.font_atlas_stats();
+    assert_eq!(stats.repacks, 1);
+    assert!(stats.evictions > 0);
+    assert!(!fonts.texture_atlas().lock().overflowed());
+
+    // The most recently used glyphs were kept, so they need not be rasterized again:
+    let fill_ratio = fonts.font_atlas_fill_ratio();
+    assert!(fill_ratio < 0.6);
+    layout(size - 1.0);
+    assert_eq!(fonts.font_atlas_fill_ratio(), fill_ratio);
+
+    // The evicted ones have to be:
+    layout(10.0);
+    assert!(fonts.font_atlas_fill_ratio() > fill_ratio);
+}
+
+#[test]
+fn test_font_faces() {
+    use crate::{text::TextFormat, Color32};
+
+    // Pretend that Hack is the bold and the italic face of Ubuntu-Light:
+    let mut definitions = FontDefinitions::default();
+    definitions.faces.insert(
+        "Ubuntu-Light".to_owned(),
+        vec![
+            FontFace::new("Hack", FontWeight::BOLD, false),
+            FontFace::new("Hack", FontWeight::REGULAR, true),
+        ],
+    );
+    let fonts = Fonts::new(1.0, 1024, definitions);
+    let layout = |font_id: FontId, italics: bool| {
+        let mut job = LayoutJob::default();
+        job.append(
+            "Hello",
+            0.0,
+            TextFormat {
+                font_id,
+                italics,
+                color: Color32::WHITE,
+                ..Default::default()
+            },
+        );
+        fonts.layout_job(job)
+    };
+    let width = |font_id: FontId| layout(font_id, false).size().x;
+
+    let monospace = width(FontId::monospace(14.0));
+    let bold = width(FontId::proportional(14.0).weight(FontWeight::BOLD));
+    assert_eq!(bold, monospace, "Should use the bold face");
+
+    let black = width(FontId::proportional(14.0).weight(FontWeight::BLACK));
+    assert!(
+        black > bold,
+        "There is no black face, so it should be emboldened"
+    );
+    let bold_monospace = width(FontId::monospace(14.0).weight(FontWeight::BOLD));
+    assert!(bold_monospace > monospace, "Should be emboldened");
+
+    let italic_glyphs = |font_id: FontId| {
+        let galley = layout(font_id, true);
+        let row = &galley.rows[0];
+        let uv_rects = row.glyphs.iter().map(|glyph| glyph.uv_rect);
+        #[cfg(feature = "shaping")]
+        let uv_rects = uv_rects.chain(row.shaped.iter().map(|glyph| glyph.uv_rect));
+        let uv_rects: Vec<_> = uv_rects.filter(|uv_rect| !uv_rect.is_nothing()).collect();
+        assert!(!uv_rects.is_empty());
+        uv_rects.iter().filter(|uv_rect| uv_rect.italic).count()
+    };
+    assert_eq!(
+        italic_glyphs(FontId::proportional(14.0)),
+        5,
+        "Should use the italic face"
+    );
+    assert_eq!(
+        italic_glyphs(FontId::monospace(14.0)),
+        0,
+        "Should be slanted instead"
+    );
 }
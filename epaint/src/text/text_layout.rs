@@ -1,7 +1,9 @@
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 use std::sync::Arc;
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{
+    font::UvRect, FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals, ShapedGlyph,
+};
 use crate::{Color32, Mesh, Stroke, Vertex};
 use emath::*;
 
@@ -43,6 +45,8 @@ struct Paragraph {
     /// Start of the next glyph to be added.
     pub cursor_x: f32,
     pub glyphs: Vec<Glyph>,
+    /// Glyphs from text shaping, with clusters indexing into [`Self::glyphs`].
+    pub shaped: Vec<ShapedGlyph>,
     /// In case of an empty paragraph ("\n"), use this as height.
     pub empty_paragraph_height: f32,
 }
@@ -78,6 +82,8 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
+    reorder_bidi_runs(&mut rows);
+
     galley_from_rows(point_scale, job, rows)
 }
 
@@ -93,25 +99,47 @@ fn layout_section(
         byte_range,
         format,
     } = section;
-    let font = fonts.font(&format.font_id);
+    let font = fonts.styled_font(&format.font_id, format.italics);
     let font_height = font.row_height();
+    let line_height = font.round_to_pixel(font_height * format.line_height);
+    let extra_letter_spacing = format.extra_letter_spacing;
 
     let mut paragraph = out_paragraphs.last_mut().unwrap();
     if paragraph.glyphs.is_empty() {
-        paragraph.empty_paragraph_height = font_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
+        paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
     }
 
     paragraph.cursor_x += leading_space;
 
     let mut last_glyph_id = None;
 
+    #[cfg(feature = "shaping")]
+    let mut run = ShapingRun::default();
+
     for chr in job.text[byte_range.clone()].chars() {
+        #[cfg(feature = "shaping")]
+        {
+            let font_impl = font.shaping_font_impl(chr);
+            let script = ShapingRun::script(chr);
+            if !run.accepts(font_impl.as_ref(), script) {
+                run.flush(section_index, font_height, extra_letter_spacing, paragraph);
+                last_glyph_id = None;
+            }
+            if let Some(font_impl) = font_impl {
+                run.push(font_impl, script, chr);
+                continue;
+            }
+        }
+
         if job.break_on_newline && chr == '\n' {
             out_paragraphs.push(Paragraph::default());
             paragraph = out_paragraphs.last_mut().unwrap();
-            paragraph.empty_paragraph_height = font_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
+            paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
         } else {
             let (font_impl, glyph_info) = font.glyph_info_and_font_impl(chr);
+            if !paragraph.glyphs.is_empty() {
+                paragraph.cursor_x += extra_letter_spacing;
+            }
             if let Some(font_impl) = font_impl {
                 if let Some(last_glyph_id) = last_glyph_id {
                     paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
@@ -124,6 +152,7 @@ fn layout_section(
                 size: vec2(glyph_info.advance_width, font_height),
                 uv_rect: glyph_info.uv_rect,
                 section_index,
+                rtl: false,
             });
 
             paragraph.cursor_x += glyph_info.advance_width;
@@ -131,6 +160,116 @@ fn layout_section(
             last_glyph_id = Some(glyph_info.id);
         }
     }
+
+    #[cfg(feature = "shaping")]
+    run.flush(section_index, font_height, extra_letter_spacing, paragraph);
+}
+
+/// Consecutive characters of a [`LayoutSection`] that are shaped together,
+/// i.e. that have the same font and script.
+#[cfg(feature = "shaping")]
+#[derive(Default)]
+struct ShapingRun {
+    font_impl: Option<Arc<super::font::FontImpl>>,
+    /// `None` until the first character of a specific script.
+    script: Option<unicode_script::Script>,
+    text: String,
+}
+
+#[cfg(feature = "shaping")]
+impl ShapingRun {
+    /// The script of `chr`, or `None` for characters shared between scripts,
+    /// like spaces, digits and punctuation.
+    fn script(chr: char) -> Option<unicode_script::Script> {
+        use unicode_script::{Script, UnicodeScript as _};
+        match chr.script() {
+            Script::Common | Script::Inherited | Script::Unknown => None,
+            script => Some(script),
+        }
+    }
+
+    /// Can a character shaped with `font_impl` in `script` continue this run?
+    /// Characters without a `font_impl` are not shaped, and end the run.
+    fn accepts(
+        &self,
+        font_impl: Option<&Arc<super::font::FontImpl>>,
+        script: Option<unicode_script::Script>,
+    ) -> bool {
+        if self.text.is_empty() {
+            return true;
+        }
+        let same_font = match (&self.font_impl, font_impl) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        };
+        let same_script = match (self.script, script) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        same_font && same_script
+    }
+
+    fn push(
+        &mut self,
+        font_impl: Arc<super::font::FontImpl>,
+        script: Option<unicode_script::Script>,
+        chr: char,
+    ) {
+        self.font_impl = Some(font_impl);
+        self.script = self.script.or(script);
+        self.text.push(chr);
+    }
+
+    /// Shape the run and add it to the paragraph, leaving the run empty.
+    fn flush(
+        &mut self,
+        section_index: u32,
+        font_height: f32,
+        extra_letter_spacing: f32,
+        paragraph: &mut Paragraph,
+    ) {
+        let font_impl = match self.font_impl.take() {
+            Some(font_impl) if !self.text.is_empty() => font_impl,
+            _ => return,
+        };
+        let point_scale = PointScale::new(font_impl.pixels_per_point());
+
+        let mut chars = self.text.chars();
+        for cluster in font_impl.shape(&self.text) {
+            if !paragraph.glyphs.is_empty() {
+                paragraph.cursor_x += extra_letter_spacing;
+            }
+
+            // The characters share the width of the cluster, to give the cursor somewhere to go.
+            let first_glyph = paragraph.glyphs.len();
+            let char_width = cluster.advance_width / cluster.chars as f32;
+            for (i, chr) in chars.by_ref().take(cluster.chars).enumerate() {
+                paragraph.glyphs.push(Glyph {
+                    chr,
+                    pos: pos2(paragraph.cursor_x + i as f32 * char_width, f32::NAN),
+                    size: vec2(char_width, font_height),
+                    uv_rect: Default::default(),
+                    section_index,
+                    rtl: false,
+                });
+            }
+
+            let glyphs = first_glyph..paragraph.glyphs.len();
+            for (glyph_info, offset) in cluster.glyphs {
+                paragraph.shaped.push(ShapedGlyph {
+                    cluster: glyphs.clone(),
+                    offset,
+                    uv_rect: glyph_info.uv_rect,
+                });
+            }
+
+            paragraph.cursor_x += cluster.advance_width;
+            paragraph.cursor_x = point_scale.round_to_pixel(paragraph.cursor_x);
+        }
+
+        self.text.clear();
+        self.script = None;
+    }
 }
 
 /// We ignore y at this stage
@@ -153,6 +292,7 @@ fn rows_from_paragraphs(
         if paragraph.glyphs.is_empty() {
             rows.push(Row {
                 glyphs: vec![],
+                shaped: vec![],
                 visuals: Default::default(),
                 rect: Rect::from_min_size(
                     pos2(paragraph.cursor_x, 0.0),
@@ -167,6 +307,7 @@ fn rows_from_paragraphs(
                 let paragraph_min_x = paragraph.glyphs[0].pos.x;
                 rows.push(Row {
                     glyphs: paragraph.glyphs,
+                    shaped: paragraph.shaped,
                     visuals: Default::default(),
                     rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                     ends_with_newline: !is_last_paragraph,
@@ -210,6 +351,7 @@ fn line_break(
                 // TODO(emilk): this records the height of this first row as zero, though that is probably fine since first_row_indentation usually comes with a first_row_min_height.
                 out_rows.push(Row {
                     glyphs: vec![],
+                    shaped: vec![],
                     visuals: Default::default(),
                     rect: rect_from_x_range(first_row_indentation..=first_row_indentation),
                     ends_with_newline: false,
@@ -232,6 +374,7 @@ fn line_break(
 
                 out_rows.push(Row {
                     glyphs,
+                    shaped: shaped_glyphs_in(&paragraph.shaped, row_start_idx..last_kept_index + 1),
                     visuals: Default::default(),
                     rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                     ends_with_newline: false,
@@ -269,6 +412,7 @@ fn line_break(
 
             out_rows.push(Row {
                 glyphs,
+                shaped: shaped_glyphs_in(&paragraph.shaped, row_start_idx..paragraph.glyphs.len()),
                 visuals: Default::default(),
                 rect: rect_from_x_range(paragraph_min_x..=paragraph_max_x),
                 ends_with_newline: false,
@@ -277,6 +421,20 @@ fn line_break(
     }
 }
 
+/// The shaped glyphs of the clusters starting within the `glyphs` of a paragraph,
+/// with the clusters relative to the start of `glyphs`.
+fn shaped_glyphs_in(shaped: &[ShapedGlyph], glyphs: Range<usize>) -> Vec<ShapedGlyph> {
+    shaped
+        .iter()
+        .filter(|shaped_glyph| glyphs.contains(&shaped_glyph.cluster.start))
+        .map(|shaped_glyph| ShapedGlyph {
+            cluster: (shaped_glyph.cluster.start - glyphs.start)
+                ..(shaped_glyph.cluster.end.min(glyphs.end) - glyphs.start),
+            ..shaped_glyph.clone()
+        })
+        .collect()
+}
+
 fn replace_last_glyph_with_overflow_character(
     fonts: &mut FontsImpl,
     job: &LayoutJob,
@@ -295,7 +453,7 @@ fn replace_last_glyph_with_overflow_character(
         };
 
         let section = &job.sections[last_glyph.section_index as usize];
-        let font = fonts.font(&section.format.font_id);
+        let font = fonts.styled_font(&section.format.font_id, section.format.italics);
         let font_height = font.row_height();
 
         let prev_glyph_id = prev_glyph.map(|prev_glyph| {
@@ -332,6 +490,11 @@ fn replace_last_glyph_with_overflow_character(
 
         row.glyphs.pop();
     }
+
+    // The last glyph is no longer part of a shaped cluster.
+    let num_glyphs = row.glyphs.len();
+    row.shaped
+        .retain(|shaped_glyph| shaped_glyph.cluster.end < num_glyphs);
 }
 
 fn halign_and_jusitfy_row(
@@ -424,6 +587,92 @@ fn halign_and_jusitfy_row(
     row.rect.max.x = target_max_x;
 }
 
+/// Apply the Unicode bidirectional algorithm, so that right-to-left text (e.g. Hebrew or Arabic)
+/// runs from right to left, also when mixed with left-to-right text.
+///
+/// The glyphs stay in logical order within each row, only their positions change.
+fn reorder_bidi_runs(rows: &mut [Row]) {
+    let mut paragraph_start = 0;
+    let num_rows = rows.len();
+    for row_nr in 0..num_rows {
+        if rows[row_nr].ends_with_newline || row_nr + 1 == num_rows {
+            reorder_paragraph(&mut rows[paragraph_start..=row_nr]);
+            paragraph_start = row_nr + 1;
+        }
+    }
+}
+
+/// Reorder the rows of one paragraph, which are wrapped but not yet reordered.
+fn reorder_paragraph(rows: &mut [Row]) {
+    use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+    // Byte offset of each glyph in `text`, and the end of the text last.
+    let mut offsets = vec![];
+    let mut text = String::new();
+    for glyph in rows.iter().flat_map(|row| &row.glyphs) {
+        offsets.push(text.len());
+        // We want a single bidi paragraph, even if newlines are not breaking the text into rows.
+        if bidi_class(glyph.chr) == BidiClass::B {
+            text.push(' ');
+        } else {
+            text.push(glyph.chr);
+        }
+    }
+    offsets.push(text.len());
+
+    let bidi_info = BidiInfo::new(&text, None);
+    if !bidi_info.has_rtl() {
+        return;
+    }
+    let paragraph = &bidi_info.paragraphs[0];
+    let glyph_index = |offset: usize| offsets.binary_search(&offset).unwrap_or_default();
+
+    let mut row_start = 0;
+    for row in rows {
+        let row_end = row_start + row.glyphs.len();
+        if row.glyphs.is_empty() {
+            continue;
+        }
+        let (levels, runs) = bidi_info.visual_runs(paragraph, offsets[row_start]..offsets[row_end]);
+
+        // Place the runs next to each other, keeping the widths and spacing of the glyphs.
+        let logical = row.glyphs.clone();
+        let mut x = logical[0].pos.x;
+        for run in runs {
+            let rtl = levels[run.start].is_rtl();
+            let glyphs = (glyph_index(run.start) - row_start)..(glyph_index(run.end) - row_start);
+            let run_min_x = logical[glyphs.start].pos.x;
+            let run_max_x = logical
+                .get(glyphs.end)
+                .map_or_else(|| logical[glyphs.end - 1].max_x(), |next| next.pos.x);
+            for (before, glyph) in logical[glyphs.clone()].iter().zip(&mut row.glyphs[glyphs]) {
+                glyph.rtl = rtl;
+                glyph.pos.x = if rtl {
+                    x + run_max_x - before.max_x()
+                } else {
+                    x + before.pos.x - run_min_x
+                };
+            }
+            x += run_max_x - run_min_x;
+        }
+        row_start = row_end;
+    }
+}
+
+/// The glyphs of a row from left to right.
+fn glyphs_in_visual_order(row: &Row) -> Vec<&Glyph> {
+    let mut glyphs: Vec<&Glyph> = row.glyphs.iter().collect();
+    if row.has_rtl() {
+        glyphs.sort_by(|a, b| {
+            a.pos
+                .x
+                .partial_cmp(&b.pos.x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+    glyphs
+}
+
 /// Calculate the Y positions and tessellate the text.
 fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<Row>) -> Galley {
     let mut first_row_min_height = job.first_row_min_height;
@@ -434,14 +683,19 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         let mut row_height = first_row_min_height.max(row.rect.height());
         first_row_min_height = 0.0;
         for glyph in &row.glyphs {
-            row_height = row_height.max(glyph.size.y);
+            let format = &job.sections[glyph.section_index as usize].format;
+            row_height = row_height.max(glyph.size.y * format.line_height);
         }
         row_height = point_scale.round_to_pixel(row_height);
 
         // Now positions each glyph:
         for glyph in &mut row.glyphs {
             let format = &job.sections[glyph.section_index as usize].format;
-            glyph.pos.y = cursor_y + format.valign.to_factor() * (row_height - glyph.size.y);
+            // Center the glyph in its own line height, then align that within the row:
+            let line_height = glyph.size.y * format.line_height;
+            let half_leading = 0.5 * (line_height - glyph.size.y);
+            glyph.pos.y =
+                cursor_y + half_leading + format.valign.to_factor() * (row_height - line_height);
             glyph.pos.y = point_scale.round_to_pixel(glyph.pos.y);
         }
 
@@ -451,6 +705,9 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         min_x = min_x.min(row.rect.min.x);
         max_x = max_x.max(row.rect.max.x);
         cursor_y += row_height;
+        if row.ends_with_newline {
+            cursor_y += job.paragraph_spacing;
+        }
         cursor_y = point_scale.round_to_pixel(cursor_y);
     }
 
@@ -516,9 +773,12 @@ fn tessellate_row(
     }
 
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    tessellate_glyphs(point_scale, job, row, false, &mut mesh);
     let glyph_vertex_end = mesh.vertices.len();
 
+    // Keep glyphs with their own colors out of `glyph_vertex_range`, so they are never tinted.
+    tessellate_glyphs(point_scale, job, row, true, &mut mesh);
+
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
@@ -564,7 +824,7 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     let mut run_start = None;
     let mut last_rect = Rect::NAN;
 
-    for glyph in &row.glyphs {
+    for glyph in glyphs_in_visual_order(row) {
         let format = &job.sections[glyph.section_index as usize].format;
         let color = format.background;
         let rect = glyph.logical_rect();
@@ -591,55 +851,109 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
-    for glyph in &row.glyphs {
-        let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
-            let mut left_top = glyph.pos + uv_rect.offset;
-            left_top.x = point_scale.round_to_pixel(left_top.x);
-            left_top.y = point_scale.round_to_pixel(left_top.y);
+/// Tessellate either the glyphs with their own colors (e.g. emojis) or the others.
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    row: &Row,
+    colored: bool,
+    mesh: &mut Mesh,
+) {
+    for glyph in row
+        .glyphs
+        .iter()
+        .filter(|glyph| glyph.uv_rect.colored == colored)
+    {
+        tessellate_glyph(
+            point_scale,
+            job,
+            glyph.section_index,
+            glyph.pos,
+            glyph.uv_rect,
+            mesh,
+        );
+    }
 
-            let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
-            let uv = Rect::from_min_max(
-                pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
-                pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
-            );
+    let shaped_glyphs = row.shaped.iter();
+    for shaped_glyph in shaped_glyphs.filter(|glyph| glyph.uv_rect.colored == colored) {
+        let cluster = &row.glyphs[shaped_glyph.cluster.clone()];
+        // The glyphs of a right-to-left cluster are in reverse order.
+        let min_x = cluster
+            .iter()
+            .map(|glyph| glyph.pos.x)
+            .fold(f32::INFINITY, f32::min);
+        let pos = pos2(min_x, cluster[0].pos.y) + shaped_glyph.offset;
+        let section_index = cluster[0].section_index;
+        tessellate_glyph(
+            point_scale,
+            job,
+            section_index,
+            pos,
+            shaped_glyph.uv_rect,
+            mesh,
+        );
+    }
+}
 
-            let format = &job.sections[glyph.section_index as usize].format;
+fn tessellate_glyph(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    section_index: u32,
+    pos: Pos2,
+    uv_rect: UvRect,
+    mesh: &mut Mesh,
+) {
+    if uv_rect.is_nothing() {
+        return;
+    }
 
-            let color = format.color;
+    let mut left_top = pos + uv_rect.offset;
+    left_top.x = point_scale.round_to_pixel(left_top.x);
+    left_top.y = point_scale.round_to_pixel(left_top.y);
 
-            if format.italics {
-                let idx = mesh.vertices.len() as u32;
-                mesh.add_triangle(idx, idx + 1, idx + 2);
-                mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+    let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
+    let uv = Rect::from_min_max(
+        pos2(uv_rect.min[0] as f32, uv_rect.min[1] as f32),
+        pos2(uv_rect.max[0] as f32, uv_rect.max[1] as f32),
+    );
 
-                let top_offset = rect.height() * 0.25 * Vec2::X;
+    let format = &job.sections[section_index as usize].format;
 
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_top() + top_offset,
-                    uv: uv.left_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_top() + top_offset,
-                    uv: uv.right_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_bottom(),
-                    uv: uv.left_bottom(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_bottom(),
-                    uv: uv.right_bottom(),
-                    color,
-                });
-            } else {
-                mesh.add_rect_with_uv(rect, uv, color);
-            }
-        }
+    let color = if uv_rect.colored {
+        Color32::from_white_alpha(format.color.a())
+    } else {
+        format.color
+    };
+
+    if format.italics && !uv_rect.italic {
+        let idx = mesh.vertices.len() as u32;
+        mesh.add_triangle(idx, idx + 1, idx + 2);
+        mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+
+        let top_offset = rect.height() * 0.25 * Vec2::X;
+
+        mesh.vertices.push(Vertex {
+            pos: rect.left_top() + top_offset,
+            uv: uv.left_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_top() + top_offset,
+            uv: uv.right_top(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.left_bottom(),
+            uv: uv.left_bottom(),
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: rect.right_bottom(),
+            uv: uv.right_bottom(),
+            color,
+        });
+    } else {
+        mesh.add_rect_with_uv(rect, uv, color);
     }
 }
 
@@ -659,7 +973,7 @@ fn add_row_hline(
     let mut line_start = None;
     let mut last_right_x = f32::NAN;
 
-    for glyph in &row.glyphs {
+    for glyph in glyphs_in_visual_order(row) {
         let (stroke, y) = stroke_and_y(glyph);
 
         if stroke == Stroke::none() {
@@ -804,3 +1118,79 @@ fn test_zero_max_width() {
     let galley = super::layout(&mut fonts, layout_job.into());
     assert_eq!(galley.rows.len(), 1);
 }
+
+#[test]
+fn test_bidi_reordering() {
+    use super::cursor::CCursor;
+
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let layout_job = LayoutJob::single_section("ab אבג".into(), super::TextFormat::default());
+    let galley = super::layout(&mut fonts, layout_job.into());
+    let glyphs = &galley.rows[0].glyphs;
+    assert!(!glyphs[1].rtl && glyphs[3].rtl);
+
+    // The Hebrew word is placed right of the latin one, from right to left:
+    assert!(glyphs[2].max_x() <= glyphs[5].pos.x);
+    assert!(glyphs[5].pos.x < glyphs[4].pos.x && glyphs[4].pos.x < glyphs[3].pos.x);
+
+    // Moving left from the right end walks forwards through the Hebrew word:
+    let mut cursor = galley.from_ccursor(CCursor::new(3));
+    let mut indices = vec![];
+    for _ in 0..4 {
+        cursor = galley.cursor_left_one_character(&cursor);
+        indices.push(cursor.ccursor.index);
+    }
+    assert_eq!(indices, vec![4, 5, 6, 2]);
+}
+
+#[test]
+fn test_text_spacing() {
+    use super::cursor::CCursor;
+
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let plain = LayoutJob::single_section("ab\ncd".into(), super::TextFormat::default());
+    let mut spaced = plain.clone();
+    spaced.sections[0].format.extra_letter_spacing = 3.0;
+    spaced.sections[0].format.line_height = 2.0;
+    spaced.paragraph_spacing = 5.0;
+    let plain = super::layout(&mut fonts, plain.into());
+    let spaced = super::layout(&mut fonts, spaced.into());
+
+    let (plain_row, row) = (&plain.rows[0], &spaced.rows[0]);
+    assert_eq!(row.glyphs[1].pos.x, plain_row.glyphs[1].pos.x + 3.0);
+    assert_eq!(row.height(), 2.0 * plain_row.height());
+    assert_eq!(row.glyphs[0].pos.y, 0.5 * plain_row.height());
+    assert_eq!(spaced.rows[1].min_y(), row.max_y() + 5.0);
+
+    // The cursor follows the spacing:
+    let caret = spaced.pos_from_cursor(&spaced.from_ccursor(CCursor::new(1)));
+    assert_eq!(caret.min.x, row.glyphs[1].pos.x);
+    assert_eq!(caret.height(), row.height());
+    let cursor = spaced.cursor_from_pos(spaced.rows[1].rect.center().to_vec2());
+    assert_eq!(cursor.rcursor.row, 1);
+    let cursor = spaced.cursor_from_pos(vec2(0.0, row.max_y() + 2.0));
+    assert_eq!(cursor.rcursor.row, 0);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_shaped_clusters() {
+    let mut fonts = FontsImpl::new(1.0, 1024, super::FontDefinitions::default());
+    let text = "office אבג";
+    let layout_job = LayoutJob::single_section(text.into(), super::TextFormat::default());
+    let galley = super::layout(&mut fonts, layout_job.into());
+    let row = &galley.rows[0];
+
+    // Still one glyph per char, for the cursor:
+    assert_eq!(row.glyphs.len(), text.chars().count());
+
+    // The default font has a ligature for "ffi":
+    let ligature = row.shaped.iter().find(|glyph| glyph.cluster == (1..4));
+    assert!(ligature.is_some());
+    assert!(row.glyphs[1..4]
+        .iter()
+        .all(|glyph| glyph.uv_rect.is_nothing()));
+
+    // The Hebrew letters are not in the font, and so are not shaped:
+    assert!(row.shaped.iter().all(|glyph| glyph.cluster.end <= 7));
+}
@@ -68,6 +68,10 @@ pub struct LayoutJob {
 
     /// Justify text so that word-wrapped rows fill the whole [`TextWrapping::max_width`]
     pub justify: bool,
+
+    /// Extra vertical space between paragraphs, i.e. after each `\n`, in points.
+    /// Default: `0.0`.
+    pub paragraph_spacing: f32,
 }
 
 impl Default for LayoutJob {
@@ -81,6 +85,7 @@ impl Default for LayoutJob {
             break_on_newline: true,
             halign: Align::LEFT,
             justify: false,
+            paragraph_spacing: 0.0,
         }
     }
 }
@@ -174,6 +179,7 @@ impl std::hash::Hash for LayoutJob {
             break_on_newline,
             halign,
             justify,
+            paragraph_spacing,
         } = self;
 
         text.hash(state);
@@ -183,6 +189,7 @@ impl std::hash::Hash for LayoutJob {
         break_on_newline.hash(state);
         halign.hash(state);
         justify.hash(state);
+        crate::f32_hash(state, *paragraph_spacing);
     }
 }
 
@@ -214,7 +221,7 @@ impl std::hash::Hash for LayoutSection {
 
 // ----------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Hash, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TextFormat {
     pub font_id: FontId,
@@ -228,6 +235,13 @@ pub struct TextFormat {
     /// can get the effect of raised text.
     pub valign: Align,
     // TODO(emilk): lowered
+    /// Extra horizontal space between characters, in points.
+    /// Default: `0.0`.
+    pub extra_letter_spacing: f32,
+    /// Row height as a multiple of the font height.
+    /// The extra space is split evenly above and below the text.
+    /// Default: `1.0`.
+    pub line_height: f32,
 }
 
 impl Default for TextFormat {
@@ -241,10 +255,38 @@ impl Default for TextFormat {
             underline: Stroke::none(),
             strikethrough: Stroke::none(),
             valign: Align::BOTTOM,
+            extra_letter_spacing: 0.0,
+            line_height: 1.0,
         }
     }
 }
 
+impl std::hash::Hash for TextFormat {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            font_id,
+            color,
+            background,
+            italics,
+            underline,
+            strikethrough,
+            valign,
+            extra_letter_spacing,
+            line_height,
+        } = self;
+        font_id.hash(state);
+        color.hash(state);
+        background.hash(state);
+        italics.hash(state);
+        underline.hash(state);
+        strikethrough.hash(state);
+        valign.hash(state);
+        crate::f32_hash(state, *extra_letter_spacing);
+        crate::f32_hash(state, *line_height);
+    }
+}
+
 impl TextFormat {
     #[inline]
     pub fn simple(font_id: FontId, color: Color32) -> Self {
@@ -354,6 +396,10 @@ pub struct Row {
     /// Includes leading and trailing whitespace.
     pub rect: Rect,
 
+    /// Glyphs from text shaping, drawn instead of the [`Self::glyphs`] of their clusters.
+    /// Always empty unless the `shaping` feature is enabled.
+    pub shaped: Vec<ShapedGlyph>,
+
     /// The mesh, ready to be rendered.
     pub visuals: RowVisuals,
 
@@ -405,6 +451,25 @@ pub struct Glyph {
     pub uv_rect: UvRect,
     /// Index into [`LayoutJob::sections`]. Decides color etc.
     pub section_index: u32,
+    /// Part of a right-to-left run of text (e.g. Hebrew or Arabic), which is placed
+    /// from right to left. The cursor before this glyph is at its right edge.
+    pub rtl: bool,
+}
+
+/// A glyph from text shaping, e.g. a ligature or an Arabic letter in its joining form.
+///
+/// Shaping turns a cluster of characters into any number of glyphs. The [`Glyph`]s of the
+/// characters share the width of the cluster and give the cursor positions within it,
+/// but it is the shaped glyphs that are drawn.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ShapedGlyph {
+    /// The characters of the cluster, as indices into [`Row::glyphs`].
+    pub cluster: Range<usize>,
+    /// Position relative to the left of the cluster and the top of its [`Glyph`]s.
+    pub offset: Vec2,
+    /// Position of the glyph in the font texture, in texels.
+    pub uv_rect: UvRect,
 }
 
 impl Glyph {
@@ -449,9 +514,17 @@ impl Row {
         self.rect.height()
     }
 
+    /// Does this row contain any right-to-left text? See [`Glyph::rtl`].
+    pub fn has_rtl(&self) -> bool {
+        self.glyphs.iter().any(|glyph| glyph.rtl)
+    }
+
     /// Closest char at the desired x coordinate.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
+        if self.has_rtl() {
+            return self.char_at_bidi(desired_x);
+        }
         for (i, glyph) in self.glyphs.iter().enumerate() {
             if desired_x < glyph.logical_rect().center().x {
                 return i;
@@ -460,11 +533,39 @@ impl Row {
         self.char_count_excluding_newline()
     }
 
+    /// The glyphs are not ordered by x, so find the closest one and pick the side of it.
+    fn char_at_bidi(&self, desired_x: f32) -> usize {
+        let distance = |glyph: &Glyph| {
+            (glyph.pos.x - desired_x)
+                .max(desired_x - glyph.max_x())
+                .at_least(0.0)
+        };
+        let closest = self.glyphs.iter().enumerate().min_by(|(_, a), (_, b)| {
+            distance(a)
+                .partial_cmp(&distance(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        match closest {
+            Some((i, glyph)) => {
+                let left_half = desired_x < glyph.logical_rect().center().x;
+                if left_half != glyph.rtl {
+                    i
+                } else {
+                    i + 1
+                }
+            }
+            None => 0,
+        }
+    }
+
     pub fn x_offset(&self, column: usize) -> f32 {
-        if let Some(glyph) = self.glyphs.get(column) {
-            glyph.pos.x
-        } else {
-            self.rect.right()
+        match self.glyphs.get(column) {
+            Some(glyph) if glyph.rtl => glyph.max_x(),
+            Some(glyph) => glyph.pos.x,
+            None => match self.glyphs.last() {
+                Some(last) if last.rtl => last.pos.x,
+                _ => self.rect.right(),
+            },
         }
     }
 }
@@ -795,7 +896,68 @@ impl Galley {
 
 /// ## Cursor positions
 impl Galley {
+    /// One step to the left on the screen, which is backwards in the text
+    /// except within right-to-left text.
     pub fn cursor_left_one_character(&self, cursor: &Cursor) -> Cursor {
+        if let Some(row) = self
+            .rows
+            .get(cursor.rcursor.row)
+            .filter(|row| row.has_rtl())
+        {
+            if let Some(cursor) = self.cursor_visually_one_character(cursor, -1.0) {
+                return cursor;
+            }
+            // At the left edge of the row, which is its end if the row starts with right-to-left text.
+            return if row.glyphs[0].rtl {
+                self.cursor_next_character(&self.cursor_end_of_row(cursor))
+            } else {
+                self.cursor_previous_character(&self.cursor_begin_of_row(cursor))
+            };
+        }
+        self.cursor_previous_character(cursor)
+    }
+
+    /// One step to the right on the screen, which is forwards in the text
+    /// except within right-to-left text.
+    pub fn cursor_right_one_character(&self, cursor: &Cursor) -> Cursor {
+        if let Some(row) = self
+            .rows
+            .get(cursor.rcursor.row)
+            .filter(|row| row.has_rtl())
+        {
+            if let Some(cursor) = self.cursor_visually_one_character(cursor, 1.0) {
+                return cursor;
+            }
+            // At the right edge of the row, which is its start if the row starts with right-to-left text.
+            return if row.glyphs[0].rtl {
+                self.cursor_previous_character(&self.cursor_begin_of_row(cursor))
+            } else {
+                self.cursor_next_character(&self.cursor_end_of_row(cursor))
+            };
+        }
+        self.cursor_next_character(cursor)
+    }
+
+    /// The closest cursor position within the same row in the given direction on the screen
+    /// (`-1.0` for left, `1.0` for right), if any.
+    fn cursor_visually_one_character(&self, cursor: &Cursor, direction: f32) -> Option<Cursor> {
+        let row = &self.rows[cursor.rcursor.row];
+        let x = row.x_offset(cursor.rcursor.column);
+        let (column, _) = (0..=row.char_count_excluding_newline())
+            .map(|column| (column, row.x_offset(column) - x))
+            .filter(|(_, dx)| dx * direction > 0.0)
+            .min_by(|(_, a), (_, b)| {
+                a.abs()
+                    .partial_cmp(&b.abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+        Some(self.from_rcursor(RCursor {
+            row: cursor.rcursor.row,
+            column,
+        }))
+    }
+
+    fn cursor_previous_character(&self, cursor: &Cursor) -> Cursor {
         if cursor.ccursor.index == 0 {
             Default::default()
         } else {
@@ -807,7 +969,7 @@ impl Galley {
         }
     }
 
-    pub fn cursor_right_one_character(&self, cursor: &Cursor) -> Cursor {
+    fn cursor_next_character(&self, cursor: &Cursor) -> Cursor {
         let ccursor = CCursor {
             index: cursor.ccursor.index,
             prefer_next_row: true, // default to this when navigating. It is more often useful to put cursor at the begging of a row than at the end.
@@ -1,6 +1,11 @@
 use emath::{remap_clamp, Rect};
 
-use crate::{textures::TextureFilter, FontImage, ImageDelta};
+use crate::{textures::TextureFilter, Color32, FontImage, ImageDelta};
+
+/// On some low-precision GPUs (my old iPad) characters get muddled up
+/// if we don't add some empty pixels between the characters.
+/// On modern high-precision GPUs this is not needed.
+const PADDING: usize = 1;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct Rectu {
@@ -52,6 +57,7 @@ pub struct PreparedDisc {
 /// Contains font data in an atlas, where each character occupied a small rectangle.
 ///
 /// More characters can be added, possibly expanding the texture.
+/// Once it can't grow any more, [`crate::Fonts`] evicts the least recently used glyphs.
 #[derive(Clone)]
 pub struct TextureAtlas {
     image: FontImage,
@@ -67,6 +73,9 @@ pub struct TextureAtlas {
 
     /// pre-rasterized discs of radii `2^i`, where `i` is the index.
     discs: Vec<PrerasterizedDisc>,
+
+    /// The white texel and the discs are above this, and are never evicted.
+    glyphs_top: usize,
 }
 
 impl TextureAtlas {
@@ -79,6 +88,7 @@ impl TextureAtlas {
             row_height: 0,
             overflowed: false,
             discs: vec![], // will be filled in below
+            glyphs_top: 0,
         };
 
         // Make the top left pixel fully white for `WHITE_UV`, i.e. painting something with solid color:
@@ -124,6 +134,11 @@ impl TextureAtlas {
             });
         }
 
+        // Glyphs start on a new row:
+        atlas.glyphs_top = atlas.cursor.1 + atlas.row_height + PADDING;
+        atlas.cursor = (0, atlas.glyphs_top);
+        atlas.row_height = 0;
+
         atlas
     }
 
@@ -170,6 +185,64 @@ impl TextureAtlas {
         }
     }
 
+    /// Did someone request more space than was available?
+    ///
+    /// If so, glyphs may have been painted over each other.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// How many texels there is room for in the atlas at its largest, not counting the discs.
+    pub(crate) fn glyph_capacity(&self) -> usize {
+        self.image.width() * (self.max_height() - self.glyphs_top)
+    }
+
+    /// Remove all glyphs, keeping the discs, and return the old image.
+    ///
+    /// The glyphs worth keeping can then be copied back with [`Self::copy_glyph`].
+    pub(crate) fn clear_glyphs(&mut self) -> FontImage {
+        let old_image = self.image.clone();
+
+        let start = self.glyphs_top * self.image.width();
+        self.image.pixels[start..].fill(0.0);
+        if !self.image.colors.is_empty() {
+            self.image.colors[start..].fill(Color32::TRANSPARENT);
+        }
+
+        self.cursor = (0, self.glyphs_top);
+        self.row_height = 0;
+        self.overflowed = false;
+        self.dirty = Rectu::EVERYTHING;
+
+        old_image
+    }
+
+    /// Allocate room for a glyph at `pos` in `old_image`, and copy it there.
+    ///
+    /// Returns the new position of the glyph.
+    pub(crate) fn copy_glyph(
+        &mut self,
+        old_image: &FontImage,
+        pos: (usize, usize),
+        (w, h): (usize, usize),
+    ) -> (usize, usize) {
+        let (new_pos, image) = self.allocate((w, h));
+        for y in 0..h {
+            for x in 0..w {
+                let from = (pos.0 + x, pos.1 + y);
+                let to = (new_pos.0 + x, new_pos.1 + y);
+                image[to] = old_image[from];
+                if !old_image.colors.is_empty() {
+                    let color = old_image.colors[from.0 + from.1 * old_image.width()];
+                    if color != Color32::TRANSPARENT {
+                        image.set_color(to, color);
+                    }
+                }
+            }
+        }
+        new_pos
+    }
+
     /// Call to get the change to the image since last call.
     pub fn take_delta(&mut self) -> Option<ImageDelta> {
         let dirty = std::mem::replace(&mut self.dirty, Rectu::NOTHING);
@@ -188,11 +261,6 @@ impl TextureAtlas {
     /// Returns the coordinates of where the rect ended up,
     /// and invalidates the region.
     pub fn allocate(&mut self, (w, h): (usize, usize)) -> ((usize, usize), &mut FontImage) {
-        /// On some low-precision GPUs (my old iPad) characters get muddled up
-        /// if we don't add some empty pixels between the characters.
-        /// On modern high-precision GPUs this is not needed.
-        const PADDING: usize = 1;
-
         assert!(
             w <= self.image.width(),
             "Tried to allocate a {} wide glyph in a {} wide texture atlas",
@@ -209,16 +277,17 @@ impl TextureAtlas {
         self.row_height = self.row_height.max(h);
 
         let required_height = self.cursor.1 + self.row_height;
+        let max_height = self.max_height();
 
-        if required_height > self.max_height() {
+        if required_height > max_height {
             // This is a bad place to be - we need to start reusing space :/
 
             #[cfg(feature = "tracing")]
-            tracing::wan!("epaint texture atlas overflowed!");
+            tracing::warn!("epaint texture atlas overflowed!");
 
             self.cursor = (0, self.image.height() / 3); // Restart a bit down - the top of the atlas has too many important things in it
             self.overflowed = true; // this will signal the user that we need to recreate the texture atlas next frame.
-        } else if resize_to_min_height(&mut self.image, required_height) {
+        } else if resize_to_min_height(&mut self.image, required_height, max_height) {
             self.dirty = Rectu::EVERYTHING;
         }
 
@@ -234,13 +303,14 @@ impl TextureAtlas {
     }
 }
 
-fn resize_to_min_height(image: &mut FontImage, required_height: usize) -> bool {
-    while required_height >= image.height() {
-        image.size[1] *= 2; // double the height
+fn resize_to_min_height(image: &mut FontImage, required_height: usize, max_height: usize) -> bool {
+    let mut height = image.height();
+    while required_height >= height && height < max_height {
+        height = (2 * height).min(max_height); // double the height
     }
 
-    if image.width() * image.height() > image.pixels.len() {
-        image.pixels.resize(image.width() * image.height(), 0.0);
+    if height > image.height() {
+        image.resize_height(height);
         true
     } else {
         false